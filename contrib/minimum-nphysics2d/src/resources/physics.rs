@@ -5,6 +5,7 @@ use nphysics2d::joint::DefaultJointConstraintSet;
 use nphysics2d::world::{DefaultMechanicalWorld, DefaultGeometricalWorld};
 
 use crossbeam_channel::{Sender, Receiver};
+use std::collections::HashMap;
 
 // Handles setting up the physics system and stepping it
 pub struct PhysicsResource {
@@ -16,6 +17,9 @@ pub struct PhysicsResource {
     pub force_generators: DefaultForceGeneratorSet<f32>,
     pub delete_body_tx: Sender<DefaultBodyHandle>,
     pub delete_body_rx: Receiver<DefaultBodyHandle>,
+    // Where bodies were before the most recent step. Drawing blends from these to the current
+    // positions by TimeResource::fixed_step_interpolation_alpha
+    pub previous_positions: HashMap<DefaultBodyHandle, Vec2>,
 }
 
 impl PhysicsResource {
@@ -40,6 +44,7 @@ impl PhysicsResource {
             force_generators,
             delete_body_tx,
             delete_body_rx,
+            previous_positions: Default::default(),
         }
     }
 
@@ -1,3 +1,4 @@
 mod physics_systems;
 pub use physics_systems::update_physics;
+pub use physics_systems::maintain_physics;
 pub use physics_systems::read_from_physics;
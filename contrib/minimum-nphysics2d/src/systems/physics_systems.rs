@@ -7,33 +7,67 @@ use minimum::components::TransformComponent;
 use crate::components::RigidBodyComponent;
 use crate::math_conversions::{vec2_glm_to_glam};
 
+// Do a physics simulation timestep. This belongs on the fixed tick, see fixed_update_schedule
 pub fn update_physics(schedule: &mut legion::systems::Builder) {
-    // Do a physics simulation timestep
     schedule.add_system(
         SystemBuilder::new("update physics")
+            .write_resource::<PhysicsResource>()
+            .read_resource::<TimeResource>()
+            .with_query(<Read<RigidBodyComponent>>::query())
+            .build(|_, world, (physics, time), query| {
+                physics.previous_positions.clear();
+                for body in query.iter(world) {
+                    if let Some(rigid_body) = physics.bodies.rigid_body(body.handle) {
+                        let position = vec2_glm_to_glam(rigid_body.position().translation.vector);
+                        physics.previous_positions.insert(body.handle, position);
+                    }
+                }
+
+                physics.mechanical_world.set_timestep(time.fixed_step_dt());
+                physics.step();
+            }),
+    );
+}
+
+// No steps happen while the simulation is paused, but bodies added and removed in the meantime
+// (for example by the editor) still need to be picked up. This goes on the render tick
+pub fn maintain_physics(schedule: &mut legion::systems::Builder) {
+    schedule.add_system(
+        SystemBuilder::new("maintain physics")
             .write_resource::<PhysicsResource>()
             .read_resource::<TimeResource>()
             .build(|_, _, (physics, time), _| {
                 if time.is_simulation_paused() {
                     physics.maintain()
-                } else {
-                    physics.step();
                 }
             }),
     );
 }
 
+// Copies body positions to transforms. The fixed tick rarely lines up with the frame, so bodies
+// are placed between their previous and current step's positions to move smoothly
 pub fn read_from_physics(schedule: &mut legion::systems::Builder) {
     schedule.add_system(
         SystemBuilder::new("read physics data")
             .read_resource::<PhysicsResource>()
+            .read_resource::<TimeResource>()
             .with_query(<(Write<TransformComponent>, Read<RigidBodyComponent>)>::query())
-            .build(|_, world, physics, query| {
+            .build(|_, world, (physics, time), query| {
+                let alpha = time.fixed_step_interpolation_alpha();
                 for (transform, body) in query.iter_mut(world) {
                     if let Some(rigid_body) = physics.bodies.rigid_body(body.handle) {
-                        let position = rigid_body.position().translation.vector;
+                        let mut position =
+                            vec2_glm_to_glam(rigid_body.position().translation.vector);
+
+                        // Bodies added since the last step don't have a previous position yet
+                        if let Some(previous_position) =
+                            physics.previous_positions.get(&body.handle)
+                        {
+                            position = *previous_position + (position - *previous_position) * alpha;
+                        }
+
                         //TODO: Conversion from 2D to 3D - ideally we'd use 3D physics with a constraint to force 2D
-                        let v3 = vec2_glm_to_glam(position).extend(transform.position().z());
+                        let v3 = position.extend(transform.position().z());
                         transform.set_position(v3);
                     }
                 }
@@ -1,11 +1,123 @@
-use sdl2::keyboard::Keycode;
+use sdl2::keyboard::{Keycode, Mod, TextInputUtil};
 use sdl2::mouse::MouseButton;
+use sdl2::controller::{Axis, Button, GameController};
+use sdl2::GameControllerSubsystem;
 
 use sdl2::event::Event;
 
 use minimum::input::InputState;
 
 use minimum::input as minimum_input;
+use minimum::input::{KeyboardKey, KeyModifiers, GamepadId, GamepadButton, GamepadAxis};
+
+// Every KeyboardKey and the sdl2 key that produces it, in the same order as KeyboardKey::ALL.
+// minimum-winit has the same table so that bindings behave the same with either backend.
+const KEYCODE_TO_KEYBOARD_KEY: [(Keycode, KeyboardKey); KeyboardKey::COUNT] = [
+    (Keycode::A, KeyboardKey::A),
+    (Keycode::B, KeyboardKey::B),
+    (Keycode::C, KeyboardKey::C),
+    (Keycode::D, KeyboardKey::D),
+    (Keycode::E, KeyboardKey::E),
+    (Keycode::F, KeyboardKey::F),
+    (Keycode::G, KeyboardKey::G),
+    (Keycode::H, KeyboardKey::H),
+    (Keycode::I, KeyboardKey::I),
+    (Keycode::J, KeyboardKey::J),
+    (Keycode::K, KeyboardKey::K),
+    (Keycode::L, KeyboardKey::L),
+    (Keycode::M, KeyboardKey::M),
+    (Keycode::N, KeyboardKey::N),
+    (Keycode::O, KeyboardKey::O),
+    (Keycode::P, KeyboardKey::P),
+    (Keycode::Q, KeyboardKey::Q),
+    (Keycode::R, KeyboardKey::R),
+    (Keycode::S, KeyboardKey::S),
+    (Keycode::T, KeyboardKey::T),
+    (Keycode::U, KeyboardKey::U),
+    (Keycode::V, KeyboardKey::V),
+    (Keycode::W, KeyboardKey::W),
+    (Keycode::X, KeyboardKey::X),
+    (Keycode::Y, KeyboardKey::Y),
+    (Keycode::Z, KeyboardKey::Z),
+    (Keycode::Num0, KeyboardKey::Key0),
+    (Keycode::Num1, KeyboardKey::Key1),
+    (Keycode::Num2, KeyboardKey::Key2),
+    (Keycode::Num3, KeyboardKey::Key3),
+    (Keycode::Num4, KeyboardKey::Key4),
+    (Keycode::Num5, KeyboardKey::Key5),
+    (Keycode::Num6, KeyboardKey::Key6),
+    (Keycode::Num7, KeyboardKey::Key7),
+    (Keycode::Num8, KeyboardKey::Key8),
+    (Keycode::Num9, KeyboardKey::Key9),
+    (Keycode::F1, KeyboardKey::F1),
+    (Keycode::F2, KeyboardKey::F2),
+    (Keycode::F3, KeyboardKey::F3),
+    (Keycode::F4, KeyboardKey::F4),
+    (Keycode::F5, KeyboardKey::F5),
+    (Keycode::F6, KeyboardKey::F6),
+    (Keycode::F7, KeyboardKey::F7),
+    (Keycode::F8, KeyboardKey::F8),
+    (Keycode::F9, KeyboardKey::F9),
+    (Keycode::F10, KeyboardKey::F10),
+    (Keycode::F11, KeyboardKey::F11),
+    (Keycode::F12, KeyboardKey::F12),
+    (Keycode::Escape, KeyboardKey::Escape),
+    (Keycode::Tab, KeyboardKey::Tab),
+    (Keycode::CapsLock, KeyboardKey::CapsLock),
+    (Keycode::Space, KeyboardKey::Space),
+    (Keycode::Return, KeyboardKey::Enter),
+    (Keycode::Backspace, KeyboardKey::Backspace),
+    (Keycode::Delete, KeyboardKey::Delete),
+    (Keycode::Insert, KeyboardKey::Insert),
+    (Keycode::Home, KeyboardKey::Home),
+    (Keycode::End, KeyboardKey::End),
+    (Keycode::PageUp, KeyboardKey::PageUp),
+    (Keycode::PageDown, KeyboardKey::PageDown),
+    (Keycode::Left, KeyboardKey::Left),
+    (Keycode::Right, KeyboardKey::Right),
+    (Keycode::Up, KeyboardKey::Up),
+    (Keycode::Down, KeyboardKey::Down),
+    (Keycode::Application, KeyboardKey::Menu),
+    (Keycode::LShift, KeyboardKey::LShift),
+    (Keycode::RShift, KeyboardKey::RShift),
+    (Keycode::LCtrl, KeyboardKey::LCtrl),
+    (Keycode::RCtrl, KeyboardKey::RCtrl),
+    (Keycode::LAlt, KeyboardKey::LAlt),
+    (Keycode::RAlt, KeyboardKey::RAlt),
+    (Keycode::LGui, KeyboardKey::LLogo),
+    (Keycode::RGui, KeyboardKey::RLogo),
+    (Keycode::Minus, KeyboardKey::Minus),
+    (Keycode::Equals, KeyboardKey::Equals),
+    (Keycode::LeftBracket, KeyboardKey::LBracket),
+    (Keycode::RightBracket, KeyboardKey::RBracket),
+    (Keycode::Backslash, KeyboardKey::Backslash),
+    (Keycode::Semicolon, KeyboardKey::Semicolon),
+    (Keycode::Quote, KeyboardKey::Apostrophe),
+    (Keycode::Backquote, KeyboardKey::Grave),
+    (Keycode::Comma, KeyboardKey::Comma),
+    (Keycode::Period, KeyboardKey::Period),
+    (Keycode::Slash, KeyboardKey::Slash),
+    (Keycode::Kp0, KeyboardKey::Numpad0),
+    (Keycode::Kp1, KeyboardKey::Numpad1),
+    (Keycode::Kp2, KeyboardKey::Numpad2),
+    (Keycode::Kp3, KeyboardKey::Numpad3),
+    (Keycode::Kp4, KeyboardKey::Numpad4),
+    (Keycode::Kp5, KeyboardKey::Numpad5),
+    (Keycode::Kp6, KeyboardKey::Numpad6),
+    (Keycode::Kp7, KeyboardKey::Numpad7),
+    (Keycode::Kp8, KeyboardKey::Numpad8),
+    (Keycode::Kp9, KeyboardKey::Numpad9),
+    (Keycode::KpPlus, KeyboardKey::NumpadAdd),
+    (Keycode::KpMinus, KeyboardKey::NumpadSubtract),
+    (Keycode::KpMultiply, KeyboardKey::NumpadMultiply),
+    (Keycode::KpDivide, KeyboardKey::NumpadDivide),
+    (Keycode::KpPeriod, KeyboardKey::NumpadDecimal),
+    (Keycode::KpEnter, KeyboardKey::NumpadEnter),
+    (Keycode::NumLockClear, KeyboardKey::NumLock),
+    (Keycode::PrintScreen, KeyboardKey::PrintScreen),
+    (Keycode::ScrollLock, KeyboardKey::ScrollLock),
+    (Keycode::Pause, KeyboardKey::Pause),
+];
 
 #[derive(Copy, Clone)]
 pub struct Sdl2KeyboardKey {
@@ -18,9 +130,165 @@ impl Sdl2KeyboardKey {
     }
 }
 
-impl Into<minimum_input::KeyboardKey> for Sdl2KeyboardKey {
-    fn into(self) -> minimum_input::KeyboardKey {
-        minimum_input::KeyboardKey(self.keycode as u8)
+// Keys that have no KeyboardKey equivalent convert to None
+impl Into<Option<KeyboardKey>> for Sdl2KeyboardKey {
+    fn into(self) -> Option<KeyboardKey> {
+        KEYCODE_TO_KEYBOARD_KEY
+            .iter()
+            .find(|(keycode, _)| *keycode == self.keycode)
+            .map(|(_, key)| *key)
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct Sdl2KeyModifiers {
+    keymod: Mod,
+}
+
+impl Sdl2KeyModifiers {
+    pub fn new(keymod: Mod) -> Self {
+        Sdl2KeyModifiers { keymod }
+    }
+}
+
+impl Into<KeyModifiers> for Sdl2KeyModifiers {
+    fn into(self) -> KeyModifiers {
+        KeyModifiers {
+            shift: self.keymod.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD),
+            ctrl: self.keymod.intersects(Mod::LCTRLMOD | Mod::RCTRLMOD),
+            alt: self.keymod.intersects(Mod::LALTMOD | Mod::RALTMOD),
+            logo: self.keymod.intersects(Mod::LGUIMOD | Mod::RGUIMOD),
+        }
+    }
+}
+
+const BUTTON_TO_GAMEPAD_BUTTON: [(Button, GamepadButton); GamepadButton::COUNT] = [
+    (Button::A, GamepadButton::A),
+    (Button::B, GamepadButton::B),
+    (Button::X, GamepadButton::X),
+    (Button::Y, GamepadButton::Y),
+    (Button::Back, GamepadButton::Back),
+    (Button::Guide, GamepadButton::Guide),
+    (Button::Start, GamepadButton::Start),
+    (Button::LeftStick, GamepadButton::LeftStick),
+    (Button::RightStick, GamepadButton::RightStick),
+    (Button::LeftShoulder, GamepadButton::LeftShoulder),
+    (Button::RightShoulder, GamepadButton::RightShoulder),
+    (Button::DPadUp, GamepadButton::DPadUp),
+    (Button::DPadDown, GamepadButton::DPadDown),
+    (Button::DPadLeft, GamepadButton::DPadLeft),
+    (Button::DPadRight, GamepadButton::DPadRight),
+];
+
+#[derive(Copy, Clone)]
+pub struct Sdl2GamepadButton {
+    button: Button,
+}
+
+impl Sdl2GamepadButton {
+    pub fn new(button: Button) -> Self {
+        Sdl2GamepadButton { button }
+    }
+}
+
+// Buttons that have no GamepadButton equivalent convert to None
+impl Into<Option<GamepadButton>> for Sdl2GamepadButton {
+    fn into(self) -> Option<GamepadButton> {
+        BUTTON_TO_GAMEPAD_BUTTON
+            .iter()
+            .find(|(button, _)| *button == self.button)
+            .map(|(_, gamepad_button)| *gamepad_button)
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct Sdl2GamepadAxis {
+    axis: Axis,
+    value: i16,
+}
+
+impl Sdl2GamepadAxis {
+    pub fn new(
+        axis: Axis,
+        value: i16,
+    ) -> Self {
+        Sdl2GamepadAxis { axis, value }
+    }
+}
+
+// SDL2 reports axes from -32768 to 32767 with down being positive, so the y axes are flipped to
+// make up positive
+impl Into<(GamepadAxis, f32)> for Sdl2GamepadAxis {
+    fn into(self) -> (GamepadAxis, f32) {
+        let value = (self.value as f32 / std::i16::MAX as f32).max(-1.0);
+        match self.axis {
+            Axis::LeftX => (GamepadAxis::LeftX, value),
+            Axis::LeftY => (GamepadAxis::LeftY, -value),
+            Axis::RightX => (GamepadAxis::RightX, value),
+            Axis::RightY => (GamepadAxis::RightY, -value),
+            Axis::TriggerLeft => (GamepadAxis::LeftTrigger, value),
+            Axis::TriggerRight => (GamepadAxis::RightTrigger, value),
+        }
+    }
+}
+
+// SDL2 only sends events for game controllers that have been opened. This opens them as they are
+// plugged in (including ones already plugged in at startup) and keeps them open until removed.
+pub struct Sdl2GamepadManager {
+    subsystem: GameControllerSubsystem,
+    controllers: Vec<GameController>,
+}
+
+impl Sdl2GamepadManager {
+    pub fn new(subsystem: GameControllerSubsystem) -> Self {
+        Sdl2GamepadManager {
+            subsystem,
+            controllers: Default::default(),
+        }
+    }
+
+    /// Call with every event, along with handle_sdl2_event
+    pub fn handle_sdl2_event(
+        &mut self,
+        event: &Event,
+        input_state: &mut InputState,
+    ) {
+        match event {
+            Event::ControllerDeviceAdded { which, .. } => {
+                if !self.subsystem.is_game_controller(*which) {
+                    return;
+                }
+
+                match self.subsystem.open(*which) {
+                    Ok(controller) => {
+                        log::info!("Opened game controller {}", controller.name());
+                        input_state.handle_gamepad_connected(GamepadId(controller.instance_id()));
+                        self.controllers.push(controller);
+                    }
+                    Err(error) => log::warn!("Failed to open game controller: {:?}", error),
+                }
+            }
+            Event::ControllerDeviceRemoved { which, .. } => {
+                self.controllers
+                    .retain(|controller| controller.instance_id() != *which);
+            }
+            _ => (),
+        }
+    }
+}
+
+/// SDL2 only sends TextInput events (and shows the IME) while text input is started. Call once a
+/// frame with whether anything, for example InputState or imgui, currently wants text.
+pub fn update_sdl2_text_input(
+    text_input: &TextInputUtil,
+    wants_text_input: bool,
+) {
+    if wants_text_input != text_input.is_active() {
+        if wants_text_input {
+            text_input.start();
+        } else {
+            text_input.stop();
+        }
     }
 }
 
@@ -59,11 +327,21 @@ pub fn handle_sdl2_event(
 
     match event {
         Event::KeyDown {
-            keycode, repeat: _, ..
-        } => handle_keyboard_event(input_state, keycode, minimum_input::ButtonState::Pressed),
+            keycode, keymod, ..
+        } => handle_keyboard_event(
+            input_state,
+            keycode,
+            *keymod,
+            minimum_input::ButtonState::Pressed,
+        ),
         Event::KeyUp {
-            keycode, repeat: _, ..
-        } => handle_keyboard_event(input_state, keycode, minimum_input::ButtonState::Released),
+            keycode, keymod, ..
+        } => handle_keyboard_event(
+            input_state,
+            keycode,
+            *keymod,
+            minimum_input::ButtonState::Released,
+        ),
         Event::MouseButtonDown { mouse_btn, .. } => {
             handle_mouse_button_event(input_state, mouse_btn, minimum_input::ButtonState::Pressed)
         }
@@ -78,6 +356,29 @@ pub fn handle_sdl2_event(
                 *x as f32, *y as f32,
             ));
         }
+        Event::TextInput { text, .. } => input_state.handle_text_input_event(text),
+        Event::ControllerButtonDown { which, button, .. } => handle_gamepad_button_event(
+            input_state,
+            *which,
+            *button,
+            minimum_input::ButtonState::Pressed,
+        ),
+        Event::ControllerButtonUp { which, button, .. } => handle_gamepad_button_event(
+            input_state,
+            *which,
+            *button,
+            minimum_input::ButtonState::Released,
+        ),
+        Event::ControllerAxisMotion {
+            which, axis, value, ..
+        } => {
+            let (axis, value) = Sdl2GamepadAxis::new(*axis, *value).into();
+            input_state.handle_gamepad_axis_event(GamepadId(*which), axis, value);
+        }
+        // Connecting needs the controller to be opened, see Sdl2GamepadManager
+        Event::ControllerDeviceRemoved { which, .. } => {
+            input_state.handle_gamepad_disconnected(GamepadId(*which));
+        }
 
         // Ignore any other events
         _ => (),
@@ -95,9 +396,129 @@ fn handle_mouse_button_event(
 fn handle_keyboard_event(
     input_state: &mut InputState,
     keycode: &Option<Keycode>,
+    keymod: Mod,
     button_state: minimum_input::ButtonState,
 ) {
-    if let Some(kc) = keycode {
-        input_state.handle_keyboard_event(Sdl2KeyboardKey::new(*kc).into(), button_state)
+    // Repeats arrive as further key down events, which InputState recognizes on its own
+    input_state.handle_modifiers_event(Sdl2KeyModifiers::new(keymod).into());
+
+    let key = keycode.and_then(|kc| Sdl2KeyboardKey::new(kc).into());
+    if let Some(key) = key {
+        input_state.handle_keyboard_event(key, button_state)
+    }
+}
+
+fn handle_gamepad_button_event(
+    input_state: &mut InputState,
+    which: u32,
+    button: Button,
+    button_state: minimum_input::ButtonState,
+) {
+    let gamepad_button: Option<GamepadButton> = Sdl2GamepadButton::new(button).into();
+    if let Some(gamepad_button) = gamepad_button {
+        input_state.handle_gamepad_button_event(GamepadId(which), gamepad_button, button_state);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn gamepad_events_update_input_state() {
+        let gamepad = GamepadId(7);
+        let mut input_state = InputState::new();
+
+        let events = [
+            Event::ControllerButtonDown {
+                timestamp: 0,
+                which: 7,
+                button: Button::A,
+            },
+            Event::ControllerAxisMotion {
+                timestamp: 0,
+                which: 7,
+                axis: Axis::LeftY,
+                value: std::i16::MIN,
+            },
+            Event::ControllerAxisMotion {
+                timestamp: 0,
+                which: 7,
+                axis: Axis::TriggerRight,
+                value: std::i16::MAX,
+            },
+            Event::ControllerAxisMotion {
+                timestamp: 0,
+                which: 7,
+                axis: Axis::RightX,
+                value: 1000,
+            },
+        ];
+
+        for event in &events {
+            handle_sdl2_event(event, &mut input_state);
+        }
+
+        // The first event for an unknown controller connects it
+        assert_eq!(input_state.gamepads_just_connected(), &[gamepad]);
+        assert!(input_state.is_gamepad_button_just_down(gamepad, GamepadButton::A));
+
+        // Pushing the stick up is positive
+        assert_eq!(input_state.gamepad_axis(gamepad, GamepadAxis::LeftY), 1.0);
+        assert_eq!(
+            input_state.gamepad_axis(gamepad, GamepadAxis::RightTrigger),
+            1.0
+        );
+
+        // Small movements are inside the deadzone
+        assert_eq!(input_state.gamepad_axis(gamepad, GamepadAxis::RightX), 0.0);
+
+        input_state.end_frame();
+        let event = Event::ControllerButtonUp {
+            timestamp: 0,
+            which: 7,
+            button: Button::A,
+        };
+        handle_sdl2_event(&event, &mut input_state);
+        assert!(input_state.is_gamepad_button_just_up(gamepad, GamepadButton::A));
+
+        let event = Event::ControllerDeviceRemoved {
+            timestamp: 0,
+            which: 7,
+        };
+        handle_sdl2_event(&event, &mut input_state);
+        assert_eq!(input_state.gamepads_just_disconnected(), &[gamepad]);
+        assert_eq!(input_state.connected_gamepads().count(), 0);
+    }
+
+    // The winit backend has the same test, so together they ensure both backends produce the same
+    // KeyboardKey for the same physical key
+    #[test]
+    fn every_keyboard_key_is_mapped_once() {
+        for (key, (keycode, mapped_key)) in KeyboardKey::ALL.iter().zip(&KEYCODE_TO_KEYBOARD_KEY) {
+            assert_eq!(mapped_key, key);
+
+            // Fails if another entry earlier in the table uses the same keycode
+            let converted: Option<KeyboardKey> = Sdl2KeyboardKey::new(*keycode).into();
+            assert_eq!(converted, Some(*key));
+        }
+    }
+
+    #[test]
+    fn keys_with_different_names_map_to_the_same_key() {
+        let expected = [
+            (Keycode::Num1, KeyboardKey::Key1),
+            (Keycode::Return, KeyboardKey::Enter),
+            (Keycode::Backspace, KeyboardKey::Backspace),
+            (Keycode::LCtrl, KeyboardKey::LCtrl),
+            (Keycode::LGui, KeyboardKey::LLogo),
+            (Keycode::Kp5, KeyboardKey::Numpad5),
+            (Keycode::Quote, KeyboardKey::Apostrophe),
+        ];
+
+        for (keycode, key) in expected.iter() {
+            let converted: Option<KeyboardKey> = Sdl2KeyboardKey::new(*keycode).into();
+            assert_eq!(converted, Some(*key));
+        }
     }
 }
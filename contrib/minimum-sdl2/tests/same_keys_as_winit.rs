@@ -0,0 +1,138 @@
+// Checks that both backends turn the same physical key into the same KeyboardKey, so bindings
+// carry over between them. The pairs are written out independently of either backend's table.
+use minimum::input::KeyboardKey;
+use minimum_sdl2::input::Sdl2KeyboardKey;
+use minimum_winit::input::{VirtualKeyCode, WinitKeyboardKey};
+use sdl2::keyboard::Keycode;
+
+const SAME_PHYSICAL_KEY: [(Keycode, VirtualKeyCode); KeyboardKey::COUNT] = [
+    (Keycode::A, VirtualKeyCode::A),
+    (Keycode::B, VirtualKeyCode::B),
+    (Keycode::C, VirtualKeyCode::C),
+    (Keycode::D, VirtualKeyCode::D),
+    (Keycode::E, VirtualKeyCode::E),
+    (Keycode::F, VirtualKeyCode::F),
+    (Keycode::G, VirtualKeyCode::G),
+    (Keycode::H, VirtualKeyCode::H),
+    (Keycode::I, VirtualKeyCode::I),
+    (Keycode::J, VirtualKeyCode::J),
+    (Keycode::K, VirtualKeyCode::K),
+    (Keycode::L, VirtualKeyCode::L),
+    (Keycode::M, VirtualKeyCode::M),
+    (Keycode::N, VirtualKeyCode::N),
+    (Keycode::O, VirtualKeyCode::O),
+    (Keycode::P, VirtualKeyCode::P),
+    (Keycode::Q, VirtualKeyCode::Q),
+    (Keycode::R, VirtualKeyCode::R),
+    (Keycode::S, VirtualKeyCode::S),
+    (Keycode::T, VirtualKeyCode::T),
+    (Keycode::U, VirtualKeyCode::U),
+    (Keycode::V, VirtualKeyCode::V),
+    (Keycode::W, VirtualKeyCode::W),
+    (Keycode::X, VirtualKeyCode::X),
+    (Keycode::Y, VirtualKeyCode::Y),
+    (Keycode::Z, VirtualKeyCode::Z),
+    (Keycode::Num0, VirtualKeyCode::Key0),
+    (Keycode::Num1, VirtualKeyCode::Key1),
+    (Keycode::Num2, VirtualKeyCode::Key2),
+    (Keycode::Num3, VirtualKeyCode::Key3),
+    (Keycode::Num4, VirtualKeyCode::Key4),
+    (Keycode::Num5, VirtualKeyCode::Key5),
+    (Keycode::Num6, VirtualKeyCode::Key6),
+    (Keycode::Num7, VirtualKeyCode::Key7),
+    (Keycode::Num8, VirtualKeyCode::Key8),
+    (Keycode::Num9, VirtualKeyCode::Key9),
+    (Keycode::F1, VirtualKeyCode::F1),
+    (Keycode::F2, VirtualKeyCode::F2),
+    (Keycode::F3, VirtualKeyCode::F3),
+    (Keycode::F4, VirtualKeyCode::F4),
+    (Keycode::F5, VirtualKeyCode::F5),
+    (Keycode::F6, VirtualKeyCode::F6),
+    (Keycode::F7, VirtualKeyCode::F7),
+    (Keycode::F8, VirtualKeyCode::F8),
+    (Keycode::F9, VirtualKeyCode::F9),
+    (Keycode::F10, VirtualKeyCode::F10),
+    (Keycode::F11, VirtualKeyCode::F11),
+    (Keycode::F12, VirtualKeyCode::F12),
+    (Keycode::Escape, VirtualKeyCode::Escape),
+    (Keycode::Tab, VirtualKeyCode::Tab),
+    (Keycode::CapsLock, VirtualKeyCode::Capital),
+    (Keycode::Space, VirtualKeyCode::Space),
+    (Keycode::Return, VirtualKeyCode::Return),
+    (Keycode::Backspace, VirtualKeyCode::Back),
+    (Keycode::Delete, VirtualKeyCode::Delete),
+    (Keycode::Insert, VirtualKeyCode::Insert),
+    (Keycode::Home, VirtualKeyCode::Home),
+    (Keycode::End, VirtualKeyCode::End),
+    (Keycode::PageUp, VirtualKeyCode::PageUp),
+    (Keycode::PageDown, VirtualKeyCode::PageDown),
+    (Keycode::Left, VirtualKeyCode::Left),
+    (Keycode::Right, VirtualKeyCode::Right),
+    (Keycode::Up, VirtualKeyCode::Up),
+    (Keycode::Down, VirtualKeyCode::Down),
+    (Keycode::Application, VirtualKeyCode::Apps),
+    (Keycode::LShift, VirtualKeyCode::LShift),
+    (Keycode::RShift, VirtualKeyCode::RShift),
+    (Keycode::LCtrl, VirtualKeyCode::LControl),
+    (Keycode::RCtrl, VirtualKeyCode::RControl),
+    (Keycode::LAlt, VirtualKeyCode::LAlt),
+    (Keycode::RAlt, VirtualKeyCode::RAlt),
+    (Keycode::LGui, VirtualKeyCode::LWin),
+    (Keycode::RGui, VirtualKeyCode::RWin),
+    (Keycode::Minus, VirtualKeyCode::Minus),
+    (Keycode::Equals, VirtualKeyCode::Equals),
+    (Keycode::LeftBracket, VirtualKeyCode::LBracket),
+    (Keycode::RightBracket, VirtualKeyCode::RBracket),
+    (Keycode::Backslash, VirtualKeyCode::Backslash),
+    (Keycode::Semicolon, VirtualKeyCode::Semicolon),
+    (Keycode::Quote, VirtualKeyCode::Apostrophe),
+    (Keycode::Backquote, VirtualKeyCode::Grave),
+    (Keycode::Comma, VirtualKeyCode::Comma),
+    (Keycode::Period, VirtualKeyCode::Period),
+    (Keycode::Slash, VirtualKeyCode::Slash),
+    (Keycode::Kp0, VirtualKeyCode::Numpad0),
+    (Keycode::Kp1, VirtualKeyCode::Numpad1),
+    (Keycode::Kp2, VirtualKeyCode::Numpad2),
+    (Keycode::Kp3, VirtualKeyCode::Numpad3),
+    (Keycode::Kp4, VirtualKeyCode::Numpad4),
+    (Keycode::Kp5, VirtualKeyCode::Numpad5),
+    (Keycode::Kp6, VirtualKeyCode::Numpad6),
+    (Keycode::Kp7, VirtualKeyCode::Numpad7),
+    (Keycode::Kp8, VirtualKeyCode::Numpad8),
+    (Keycode::Kp9, VirtualKeyCode::Numpad9),
+    (Keycode::KpPlus, VirtualKeyCode::Add),
+    (Keycode::KpMinus, VirtualKeyCode::Subtract),
+    (Keycode::KpMultiply, VirtualKeyCode::Multiply),
+    (Keycode::KpDivide, VirtualKeyCode::Divide),
+    (Keycode::KpPeriod, VirtualKeyCode::Decimal),
+    (Keycode::KpEnter, VirtualKeyCode::NumpadEnter),
+    (Keycode::NumLockClear, VirtualKeyCode::Numlock),
+    (Keycode::PrintScreen, VirtualKeyCode::Snapshot),
+    (Keycode::ScrollLock, VirtualKeyCode::Scroll),
+    (Keycode::Pause, VirtualKeyCode::Pause),
+];
+
+#[test]
+fn sdl2_and_winit_map_keys_identically() {
+    for (keycode, virtual_keycode) in SAME_PHYSICAL_KEY.iter() {
+        let sdl2_key: Option<KeyboardKey> = Sdl2KeyboardKey::new(*keycode).into();
+        let winit_key: Option<KeyboardKey> = WinitKeyboardKey::new(*virtual_keycode).into();
+        assert!(sdl2_key.is_some(), "{:?} is not mapped", keycode);
+        assert_eq!(
+            sdl2_key, winit_key,
+            "{:?} vs {:?}",
+            keycode, virtual_keycode
+        );
+    }
+}
+
+#[test]
+fn every_key_is_reachable_from_both_backends() {
+    for key in KeyboardKey::ALL.iter() {
+        let reachable = SAME_PHYSICAL_KEY.iter().any(|(keycode, _)| {
+            let sdl2_key: Option<KeyboardKey> = Sdl2KeyboardKey::new(*keycode).into();
+            sdl2_key == Some(*key)
+        });
+        assert!(reachable, "{:?} can't be produced by a key", key);
+    }
+}
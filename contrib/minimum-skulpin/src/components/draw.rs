@@ -7,21 +7,79 @@ use legion::world::World;
 use ncollide3d::pipeline::{CollisionGroups, GeometricQueryType};
 use ncollide3d::shape::{Ball, Cuboid};
 use ncollide3d::shape::ShapeHandle;
-use minimum::components::{TransformComponentDef};
+use minimum::components::{TransformComponent, TransformComponentDef};
 use minimum::math::Vec3;
 use minimum::math::Vec4;
 use imgui_inspect_derive::Inspect;
+use imgui_inspect::{InspectArgsDefault, InspectRenderDefault};
+use minimum::inspect::{InspectEnum, render_enum, render_enum_mut};
 use legion::*;
 use minimum::resources::editor::OpenedPrefabState;
 
 use crate::math_conversions::{vec3_glam_to_glm, quat_glam_to_glm};
 
+// Whether a shape is drawn as an outline, filled, or both
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub enum PaintStyle {
+    Stroke,
+    Fill,
+    StrokeAndFill,
+}
+
+impl Default for PaintStyle {
+    fn default() -> Self {
+        PaintStyle::Stroke
+    }
+}
+
+impl Into<skia_safe::paint::Style> for PaintStyle {
+    fn into(self) -> skia_safe::paint::Style {
+        match self {
+            PaintStyle::Stroke => skia_safe::paint::Style::Stroke,
+            PaintStyle::Fill => skia_safe::paint::Style::Fill,
+            PaintStyle::StrokeAndFill => skia_safe::paint::Style::StrokeAndFill,
+        }
+    }
+}
+
+impl InspectEnum for PaintStyle {
+    const VARIANTS: &'static [Self] = &[
+        PaintStyle::Stroke,
+        PaintStyle::Fill,
+        PaintStyle::StrokeAndFill,
+    ];
+}
+
+impl InspectRenderDefault<PaintStyle> for PaintStyle {
+    fn render(
+        data: &[&PaintStyle],
+        label: &'static str,
+        ui: &imgui::Ui,
+        _args: &InspectArgsDefault,
+    ) {
+        render_enum(data, label, ui);
+    }
+
+    fn render_mut(
+        data: &mut [&mut PaintStyle],
+        label: &'static str,
+        ui: &imgui::Ui,
+        _args: &InspectArgsDefault,
+    ) -> bool {
+        render_enum_mut(data, label, ui)
+    }
+}
+
 // A utility struct to describe color for a skia shape
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, SerdeDiff, PartialEq, Inspect, Default)]
 pub struct PaintDef {
     #[serde_diff(opaque)]
     pub color: Vec4,
     pub stroke_width: f32,
+    // Older prefabs don't have this, so default to the outline they were drawn with before
+    #[serde(default)]
+    #[serde_diff(opaque)]
+    pub style: PaintStyle,
 }
 
 pub struct Paint(pub std::sync::Mutex<skia_safe::Paint>);
@@ -39,7 +97,7 @@ impl From<PaintDef> for Paint {
 
         let mut paint = skia_safe::Paint::new(color, None);
         paint.set_anti_alias(true);
-        paint.set_style(skia_safe::paint::Style::Stroke);
+        paint.set_style(from.style.into());
         paint.set_stroke_width(from.stroke_width);
 
         Paint(std::sync::Mutex::new(paint))
@@ -74,6 +132,29 @@ impl From<DrawSkiaBoxComponentDef> for DrawSkiaBoxComponent {
     }
 }
 
+impl DrawSkiaBoxComponent {
+    pub fn draw(
+        &self,
+        canvas: &mut skia_safe::Canvas,
+        transform: &TransformComponent,
+    ) {
+        let mut half_extents = *self.half_extents;
+        half_extents *= transform.scale();
+
+        let paint = self.paint.0.lock().unwrap();
+
+        canvas.draw_rect(
+            skia_safe::Rect {
+                left: transform.position().x() - half_extents.x(),
+                right: transform.position().x() + half_extents.x(),
+                top: transform.position().y() - half_extents.y(),
+                bottom: transform.position().y() + half_extents.y(),
+            },
+            &paint,
+        );
+    }
+}
+
 impl minimum::editor::EditorSelectable for DrawSkiaBoxComponent {
     fn create_editor_selection_world(
         &self,
@@ -135,6 +216,22 @@ impl From<DrawSkiaCircleComponentDef> for DrawSkiaCircleComponent {
     }
 }
 
+impl DrawSkiaCircleComponent {
+    pub fn draw(
+        &self,
+        canvas: &mut skia_safe::Canvas,
+        transform: &TransformComponent,
+    ) {
+        let scale = transform.uniform_scale();
+        let paint = self.paint.0.lock().unwrap();
+        canvas.draw_circle(
+            skia_safe::Point::new(transform.position().x(), transform.position().y()),
+            self.radius * scale,
+            &paint,
+        );
+    }
+}
+
 impl minimum::editor::EditorSelectable for DrawSkiaCircleComponent {
     fn create_editor_selection_world(
         &self,
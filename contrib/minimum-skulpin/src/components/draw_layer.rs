@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+use serde_diff::SerdeDiff;
+use type_uuid::TypeUuid;
+use imgui_inspect_derive::Inspect;
+use imgui_inspect::{InspectArgsDefault, InspectRenderDefault};
+
+// Edits a layer index. Layers can be negative so that they can be placed behind the default layer
+pub struct LayerInspect;
+
+impl InspectRenderDefault<i32> for LayerInspect {
+    fn render(
+        data: &[&i32],
+        label: &'static str,
+        ui: &imgui::Ui,
+        _args: &InspectArgsDefault,
+    ) {
+        if data.is_empty() {
+            return;
+        }
+
+        ui.text(&imgui::im_str!("{}: {}", label, data[0]));
+    }
+
+    fn render_mut(
+        data: &mut [&mut i32],
+        label: &'static str,
+        ui: &imgui::Ui,
+        _args: &InspectArgsDefault,
+    ) -> bool {
+        if data.is_empty() {
+            return false;
+        }
+
+        let mut value = *data[0];
+        let changed = ui
+            .input_int(&imgui::im_str!("{}", label), &mut value)
+            .build();
+
+        if changed {
+            for d in data {
+                **d = value;
+            }
+        }
+
+        changed
+    }
+}
+
+//
+// Controls the order that skia draw components on the same entity are drawn in. Lower layers are
+// drawn first, and within a layer lower z_order is drawn first. Entities without this component
+// are drawn on layer 0 with z_order 0. Layer visibility and parallax are set in DrawLayersResource.
+// Parallax only offsets a layer by the camera's X/Y position, the rest of the camera's transform
+// (rotation, height and zoom) applies to all layers the same way
+//
+#[derive(TypeUuid, Serialize, Deserialize, SerdeDiff, Debug, PartialEq, Clone, Copy, Inspect, Default)]
+#[uuid = "5c2e9a71-8d4b-4f3a-b6e0-1a7d3c9f2e58"]
+pub struct DrawLayerComponentDef {
+    #[inspect(proxy_type = "LayerInspect")]
+    pub layer: i32,
+    pub z_order: f32,
+}
+
+legion_prefab::register_component_type!(DrawLayerComponentDef);
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DrawLayerComponent {
+    pub layer: i32,
+    pub z_order: f32,
+}
+
+impl From<DrawLayerComponentDef> for DrawLayerComponent {
+    fn from(from: DrawLayerComponentDef) -> Self {
+        DrawLayerComponent {
+            layer: from.layer,
+            z_order: from.z_order,
+        }
+    }
+}
@@ -4,4 +4,42 @@ pub use draw::DrawSkiaCircleComponentDef;
 pub use draw::DrawSkiaBoxComponent;
 pub use draw::DrawSkiaBoxComponentDef;
 pub use draw::PaintDef;
+pub use draw::PaintStyle;
 pub use draw::Paint;
+
+mod tilemap;
+pub use tilemap::TilemapComponent;
+pub use tilemap::TilemapComponentDef;
+pub use tilemap::TilesetLayout;
+
+mod sprite;
+pub use sprite::DrawSkiaSpriteComponent;
+pub use sprite::DrawSkiaSpriteComponentDef;
+
+mod sprite_animation;
+pub use sprite_animation::SpriteAnimationComponent;
+pub use sprite_animation::SpriteAnimationComponentDef;
+
+mod text;
+pub use text::DrawSkiaTextComponent;
+pub use text::DrawSkiaTextComponentDef;
+pub use text::TextAlignment;
+
+mod shapes;
+pub use shapes::DrawSkiaPolygonComponent;
+pub use shapes::DrawSkiaPolygonComponentDef;
+pub use shapes::DrawSkiaPolylineComponent;
+pub use shapes::DrawSkiaPolylineComponentDef;
+pub use shapes::DrawSkiaCapsuleComponent;
+pub use shapes::DrawSkiaCapsuleComponentDef;
+pub use shapes::DrawSkiaRoundedRectComponent;
+pub use shapes::DrawSkiaRoundedRectComponentDef;
+pub use shapes::PointListInspect;
+
+mod draw_layer;
+pub use draw_layer::DrawLayerComponent;
+pub use draw_layer::DrawLayerComponentDef;
+
+mod particles;
+pub use particles::ParticleEmitterComponent;
+pub use particles::ParticleEmitterComponentDef;
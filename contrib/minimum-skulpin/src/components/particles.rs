@@ -0,0 +1,254 @@
+use serde::{Deserialize, Serialize};
+use serde_diff::SerdeDiff;
+use type_uuid::TypeUuid;
+use skulpin::skia_safe;
+use ncollide3d::world::CollisionWorld;
+use legion::world::World;
+use ncollide3d::pipeline::{CollisionGroups, GeometricQueryType};
+use ncollide3d::shape::{Ball, ShapeHandle};
+use minimum::components::{TransformComponent, TransformComponentDef};
+use minimum::math::{Vec2, Vec4};
+use imgui_inspect_derive::Inspect;
+use legion::*;
+use minimum::resources::editor::OpenedPrefabState;
+
+use crate::math_conversions::vec3_glam_to_glm;
+
+// Size of the shape used to select an emitter in the editor
+const EMITTER_SELECTION_RADIUS: f32 = 0.5;
+
+//
+// Emits particles from the transform's position. Particles are simulated in world space, so they
+// are not affected by the emitter moving after they are emitted. Particles are launched within a
+// cone of +/- spread degrees around direction (which is relative to the transform's rotation), are
+// pulled by gravity and fade from the start color/size to the end color/size over their lifetime.
+// Particles only advance while the simulation is running unless preview_in_editor is set.
+//
+#[derive(TypeUuid, Serialize, Deserialize, SerdeDiff, Debug, PartialEq, Clone, Inspect)]
+#[uuid = "9b3e6f21-7c4a-4d8e-a5b2-3f1c8e6d0a97"]
+pub struct ParticleEmitterComponentDef {
+    // Particles emitted per second
+    pub rate: f32,
+    // Seconds each particle lives for
+    pub lifetime: f32,
+    // Initial speed of each particle in world units per second
+    pub speed: f32,
+    // Angle in degrees that particles are launched in, 0 is +X
+    pub direction: f32,
+    // Particles are launched up to this many degrees to either side of direction
+    pub spread: f32,
+    #[serde_diff(opaque)]
+    pub gravity: Vec2,
+    #[serde_diff(opaque)]
+    pub start_color: Vec4,
+    #[serde_diff(opaque)]
+    pub end_color: Vec4,
+    // Diameter of each particle in world units
+    pub start_size: f32,
+    pub end_size: f32,
+    // No particles are emitted while this many are alive
+    pub max_particles: u32,
+    // Keep emitting while the simulation is paused so the effect can be seen while editing
+    pub preview_in_editor: bool,
+}
+
+legion_prefab::register_component_type!(ParticleEmitterComponentDef);
+
+impl Default for ParticleEmitterComponentDef {
+    fn default() -> Self {
+        ParticleEmitterComponentDef {
+            rate: 10.0,
+            lifetime: 1.0,
+            speed: 5.0,
+            direction: 90.0,
+            spread: 15.0,
+            gravity: Vec2::zero(),
+            start_color: glam::Vec4::new(1.0, 1.0, 1.0, 1.0).into(),
+            end_color: glam::Vec4::new(1.0, 1.0, 1.0, 0.0).into(),
+            start_size: 0.5,
+            end_size: 0.5,
+            max_particles: 100,
+            preview_in_editor: false,
+        }
+    }
+}
+
+struct Particle {
+    position: glam::Vec2,
+    velocity: glam::Vec2,
+    age: f32,
+}
+
+pub struct ParticleEmitterComponent {
+    pub rate: f32,
+    pub lifetime: f32,
+    pub speed: f32,
+    pub direction: f32,
+    pub spread: f32,
+    pub gravity: Vec2,
+    pub start_color: Vec4,
+    pub end_color: Vec4,
+    pub start_size: f32,
+    pub end_size: f32,
+    pub max_particles: u32,
+    pub preview_in_editor: bool,
+    particles: Vec<Particle>,
+    // Fractional particles that have not been emitted yet
+    emit_accumulator: f32,
+    // xorshift state. Seeded with a constant so that the same inputs produce the same particles
+    rng_state: u32,
+}
+
+impl From<ParticleEmitterComponentDef> for ParticleEmitterComponent {
+    fn from(from: ParticleEmitterComponentDef) -> Self {
+        ParticleEmitterComponent {
+            rate: from.rate,
+            lifetime: from.lifetime,
+            speed: from.speed,
+            direction: from.direction,
+            spread: from.spread,
+            gravity: from.gravity,
+            start_color: from.start_color,
+            end_color: from.end_color,
+            start_size: from.start_size,
+            end_size: from.end_size,
+            max_particles: from.max_particles,
+            preview_in_editor: from.preview_in_editor,
+            particles: Vec::with_capacity(from.max_particles as usize),
+            emit_accumulator: 0.0,
+            rng_state: 0x9E37_79B9,
+        }
+    }
+}
+
+impl ParticleEmitterComponent {
+    pub fn particle_count(&self) -> usize {
+        self.particles.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.particles.clear();
+        self.emit_accumulator = 0.0;
+    }
+
+    // Angle in radians of the center of the emission cone, including the transform's rotation
+    pub fn world_direction(
+        &self,
+        transform: &TransformComponent,
+    ) -> f32 {
+        minimum::math::functions::z_rotation_radians(transform.rotation())
+            + self.direction.to_radians()
+    }
+
+    // Returns a value in the range [0, 1)
+    fn next_random(&mut self) -> f32 {
+        let mut x = self.rng_state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.rng_state = x;
+
+        (x >> 8) as f32 / (1 << 24) as f32
+    }
+
+    // Ages and moves existing particles, then emits new ones at the transform's position
+    pub fn simulate(
+        &mut self,
+        dt: f32,
+        transform: &TransformComponent,
+    ) {
+        if dt <= 0.0 {
+            return;
+        }
+
+        let lifetime = self.lifetime;
+        let gravity: glam::Vec2 = self.gravity.into();
+        self.particles
+            .retain(|particle| particle.age + dt < lifetime);
+        for particle in &mut self.particles {
+            particle.age += dt;
+            particle.velocity += gravity * dt;
+            particle.position += particle.velocity * dt;
+        }
+
+        self.emit_accumulator += self.rate.max(0.0) * dt;
+        let emit_count = self.emit_accumulator.floor();
+        self.emit_accumulator -= emit_count;
+
+        let position = transform.position().truncate();
+        let direction = self.world_direction(transform);
+        let spread = self.spread.to_radians();
+        for _ in 0..emit_count as u32 {
+            if self.particles.len() >= self.max_particles as usize {
+                break;
+            }
+
+            let angle = direction + (self.next_random() * 2.0 - 1.0) * spread;
+            self.particles.push(Particle {
+                position,
+                velocity: glam::Vec2::new(angle.cos(), angle.sin()) * self.speed,
+                age: 0.0,
+            });
+        }
+    }
+
+    // Particles are already in world space so the transform is not needed to draw them
+    pub fn draw(
+        &self,
+        canvas: &mut skia_safe::Canvas,
+    ) {
+        let start_color: glam::Vec4 = self.start_color.into();
+        let end_color: glam::Vec4 = self.end_color.into();
+
+        for particle in &self.particles {
+            let t = if self.lifetime > 0.0 {
+                (particle.age / self.lifetime).min(1.0)
+            } else {
+                1.0
+            };
+
+            let color = start_color.lerp(end_color, t);
+            let size = self.start_size + (self.end_size - self.start_size) * t;
+
+            let mut paint = skia_safe::Paint::new(
+                skia_safe::Color4f::new(color.x(), color.y(), color.z(), color.w()),
+                None,
+            );
+            paint.set_anti_alias(true);
+            paint.set_style(skia_safe::paint::Style::Fill);
+
+            canvas.draw_circle(
+                skia_safe::Point::new(particle.position.x(), particle.position.y()),
+                size * 0.5,
+                &paint,
+            );
+        }
+    }
+}
+
+impl minimum::editor::EditorSelectable for ParticleEmitterComponent {
+    fn create_editor_selection_world(
+        &self,
+        collision_world: &mut CollisionWorld<f32, Entity>,
+        _resources: &Resources,
+        _opened_prefab: &OpenedPrefabState,
+        world: &World,
+        entity: Entity,
+    ) {
+        let entity_ref = world.entry_ref(entity).unwrap();
+        if let Ok(transform) = entity_ref.get_component::<TransformComponentDef>() {
+            // Particles are spread across the world, so only the emitter itself is selectable
+            let shape_handle = ShapeHandle::new(Ball::new(EMITTER_SELECTION_RADIUS));
+            collision_world.add(
+                ncollide3d::math::Isometry::from_parts(
+                    nalgebra::Translation::from(vec3_glam_to_glm(transform.position())),
+                    nalgebra::UnitQuaternion::identity(),
+                ),
+                shape_handle,
+                CollisionGroups::new(),
+                GeometricQueryType::Proximity(0.001),
+                entity,
+            );
+        }
+    }
+}
@@ -0,0 +1,503 @@
+use serde::{Deserialize, Serialize};
+use serde_diff::SerdeDiff;
+use type_uuid::TypeUuid;
+use skulpin::skia_safe;
+use ncollide3d::world::CollisionWorld;
+use legion::world::World;
+use ncollide3d::pipeline::{CollisionGroups, GeometricQueryType};
+use ncollide3d::shape::{Capsule, Cuboid};
+use ncollide3d::shape::ShapeHandle;
+use minimum::components::{TransformComponent, TransformComponentDef};
+use minimum::math::Vec2;
+use imgui_inspect_derive::Inspect;
+use imgui_inspect::{InspectArgsDefault, InspectRenderDefault};
+use legion::*;
+use minimum::resources::editor::OpenedPrefabState;
+
+use crate::math_conversions::{vec3_glam_to_glm, quat_glam_to_glm};
+use super::{Paint, PaintDef};
+
+// Inspector for a list of points. Each point can be edited and points can be added or removed
+// when a single entity is selected
+pub struct PointListInspect;
+
+impl InspectRenderDefault<Vec<Vec2>> for PointListInspect {
+    fn render(
+        data: &[&Vec<Vec2>],
+        label: &'static str,
+        ui: &imgui::Ui,
+        _args: &InspectArgsDefault,
+    ) {
+        if data.is_empty() {
+            return;
+        }
+
+        ui.text(&imgui::im_str!("{}: {} points", label, data[0].len()));
+        for (index, point) in data[0].iter().enumerate() {
+            ui.text(&imgui::im_str!(
+                "  [{}]: {} {}",
+                index,
+                point.x(),
+                point.y()
+            ));
+        }
+    }
+
+    fn render_mut(
+        data: &mut [&mut Vec<Vec2>],
+        label: &'static str,
+        ui: &imgui::Ui,
+        _args: &InspectArgsDefault,
+    ) -> bool {
+        // Editing multiple lists at once is ambiguous if they have different lengths
+        if data.len() != 1 {
+            return false;
+        }
+
+        let points = &mut *data[0];
+        let mut changed = false;
+
+        ui.text(&imgui::im_str!("{}", label));
+
+        let mut remove_index = None;
+        for (index, point) in points.iter_mut().enumerate() {
+            let mut val = [point.x(), point.y()];
+            if ui
+                .input_float2(&imgui::im_str!("[{}]##{}", index, label), &mut val)
+                .build()
+            {
+                point.set_x(val[0]);
+                point.set_y(val[1]);
+                changed = true;
+            }
+
+            ui.same_line(0.0);
+            if ui.small_button(&imgui::im_str!("-##{}{}", label, index)) {
+                remove_index = Some(index);
+            }
+        }
+
+        if let Some(remove_index) = remove_index {
+            points.remove(remove_index);
+            changed = true;
+        }
+
+        if ui.small_button(&imgui::im_str!("Add Point##{}", label)) {
+            let point = points.last().copied().unwrap_or_else(Vec2::zero);
+            points.push(point);
+            changed = true;
+        }
+
+        changed
+    }
+}
+
+// Translates and rotates the canvas to the transform. Scale is not applied to the canvas so that
+// stroke width is not affected by it
+fn push_transform(
+    canvas: &mut skia_safe::Canvas,
+    transform: &TransformComponent,
+) {
+    let position = transform.position();
+    let rotation_in_degrees =
+        minimum::math::functions::z_rotation_radians(transform.rotation()).to_degrees();
+
+    canvas.save();
+    canvas.translate((position.x(), position.y()));
+    canvas.rotate(rotation_in_degrees, None);
+}
+
+fn build_path(
+    points: &[Vec2],
+    scale: glam::Vec3,
+    close: bool,
+) -> skia_safe::Path {
+    let mut path = skia_safe::Path::new();
+    for (index, point) in points.iter().enumerate() {
+        let point = skia_safe::Point::new(point.x() * scale.x(), point.y() * scale.y());
+        if index == 0 {
+            path.move_to(point);
+        } else {
+            path.line_to(point);
+        }
+    }
+
+    if close {
+        path.close();
+    }
+
+    path
+}
+
+// Adds a selection shape that is offset from the transform's position by local_center (which is
+// rotated with the transform)
+fn add_selection_shape(
+    collision_world: &mut CollisionWorld<f32, Entity>,
+    transform: &TransformComponentDef,
+    shape_handle: ShapeHandle<f32>,
+    local_center: glam::Vec3,
+    local_rotation: nalgebra::UnitQuaternion<f32>,
+    entity: Entity,
+) {
+    let center = transform.position() + transform.rotation_quat() * local_center;
+    let rotation = quat_glam_to_glm(transform.rotation_quat());
+    let rotation = nalgebra::UnitQuaternion::from_quaternion(rotation) * local_rotation;
+    collision_world.add(
+        ncollide3d::math::Isometry::from_parts(
+            nalgebra::Translation::from(vec3_glam_to_glm(center)),
+            rotation,
+        ),
+        shape_handle,
+        CollisionGroups::new(),
+        GeometricQueryType::Proximity(0.001),
+        entity,
+    );
+}
+
+// Selects the bounding box of the given points
+fn add_points_selection_shape(
+    collision_world: &mut CollisionWorld<f32, Entity>,
+    transform: &TransformComponentDef,
+    points: &[Vec2],
+    entity: Entity,
+) {
+    if points.is_empty() {
+        return;
+    }
+
+    let scale = transform.scale();
+    let mut min = glam::Vec2::new(std::f32::MAX, std::f32::MAX);
+    let mut max = glam::Vec2::new(std::f32::MIN, std::f32::MIN);
+    for point in points {
+        let point = glam::Vec2::new(point.x() * scale.x(), point.y() * scale.y());
+        min = min.min(point);
+        max = max.max(point);
+    }
+
+    let half_extents = glam::Vec3::new(
+        ((max.x() - min.x()) * 0.5).max(0.01),
+        ((max.y() - min.y()) * 0.5).max(0.01),
+        0.01,
+    );
+    let local_center = ((min + max) * 0.5).extend(0.0);
+
+    let shape_handle = ShapeHandle::new(Cuboid::new(vec3_glam_to_glm(half_extents)));
+    add_selection_shape(
+        collision_world,
+        transform,
+        shape_handle,
+        local_center,
+        nalgebra::UnitQuaternion::identity(),
+        entity,
+    );
+}
+
+//
+// Draw a closed polygon. Points are relative to the transform and affected by its scale
+//
+#[derive(TypeUuid, Serialize, Deserialize, SerdeDiff, Debug, PartialEq, Clone, Inspect, Default)]
+#[uuid = "4a7e2c91-6f3b-4d8a-b1e5-9c0d7f2a6e38"]
+pub struct DrawSkiaPolygonComponentDef {
+    #[serde_diff(opaque)]
+    #[inspect(proxy_type = "PointListInspect")]
+    pub points: Vec<Vec2>,
+    pub paint: PaintDef,
+}
+
+legion_prefab::register_component_type!(DrawSkiaPolygonComponentDef);
+
+pub struct DrawSkiaPolygonComponent {
+    pub points: Vec<Vec2>,
+    pub paint: Paint,
+}
+
+impl From<DrawSkiaPolygonComponentDef> for DrawSkiaPolygonComponent {
+    fn from(from: DrawSkiaPolygonComponentDef) -> Self {
+        DrawSkiaPolygonComponent {
+            points: from.points,
+            paint: from.paint.into(),
+        }
+    }
+}
+
+impl DrawSkiaPolygonComponent {
+    pub fn draw(
+        &self,
+        canvas: &mut skia_safe::Canvas,
+        transform: &TransformComponent,
+    ) {
+        if self.points.len() < 2 {
+            return;
+        }
+
+        let paint = self.paint.0.lock().unwrap();
+        let path = build_path(&self.points, transform.scale(), true);
+
+        push_transform(canvas, transform);
+        canvas.draw_path(&path, &paint);
+        canvas.restore();
+    }
+}
+
+impl minimum::editor::EditorSelectable for DrawSkiaPolygonComponent {
+    fn create_editor_selection_world(
+        &self,
+        collision_world: &mut CollisionWorld<f32, Entity>,
+        _resources: &Resources,
+        _opened_prefab: &OpenedPrefabState,
+        world: &World,
+        entity: Entity,
+    ) {
+        let entity_ref = world.entry_ref(entity).unwrap();
+        if let Ok(transform) = entity_ref.get_component::<TransformComponentDef>() {
+            add_points_selection_shape(collision_world, transform, &self.points, entity);
+        }
+    }
+}
+
+//
+// Draw an open line strip. Points are relative to the transform and affected by its scale
+//
+#[derive(TypeUuid, Serialize, Deserialize, SerdeDiff, Debug, PartialEq, Clone, Inspect, Default)]
+#[uuid = "d8c35b0e-2a9f-47e1-8b6d-5f1e3a7c9d42"]
+pub struct DrawSkiaPolylineComponentDef {
+    #[serde_diff(opaque)]
+    #[inspect(proxy_type = "PointListInspect")]
+    pub points: Vec<Vec2>,
+    pub paint: PaintDef,
+}
+
+legion_prefab::register_component_type!(DrawSkiaPolylineComponentDef);
+
+pub struct DrawSkiaPolylineComponent {
+    pub points: Vec<Vec2>,
+    pub paint: Paint,
+}
+
+impl From<DrawSkiaPolylineComponentDef> for DrawSkiaPolylineComponent {
+    fn from(from: DrawSkiaPolylineComponentDef) -> Self {
+        DrawSkiaPolylineComponent {
+            points: from.points,
+            paint: from.paint.into(),
+        }
+    }
+}
+
+impl DrawSkiaPolylineComponent {
+    pub fn draw(
+        &self,
+        canvas: &mut skia_safe::Canvas,
+        transform: &TransformComponent,
+    ) {
+        if self.points.len() < 2 {
+            return;
+        }
+
+        let paint = self.paint.0.lock().unwrap();
+        let path = build_path(&self.points, transform.scale(), false);
+
+        push_transform(canvas, transform);
+        canvas.draw_path(&path, &paint);
+        canvas.restore();
+    }
+}
+
+impl minimum::editor::EditorSelectable for DrawSkiaPolylineComponent {
+    fn create_editor_selection_world(
+        &self,
+        collision_world: &mut CollisionWorld<f32, Entity>,
+        _resources: &Resources,
+        _opened_prefab: &OpenedPrefabState,
+        world: &World,
+        entity: Entity,
+    ) {
+        let entity_ref = world.entry_ref(entity).unwrap();
+        if let Ok(transform) = entity_ref.get_component::<TransformComponentDef>() {
+            add_points_selection_shape(collision_world, transform, &self.points, entity);
+        }
+    }
+}
+
+//
+// Draw a capsule centered on the transform and lying along the X axis. half_length is the
+// distance from the center to the center of each cap. Affected by uniform scale
+//
+#[derive(TypeUuid, Serialize, Deserialize, SerdeDiff, Debug, PartialEq, Clone, Inspect, Default)]
+#[uuid = "7b1f4e6a-3c8d-4a2e-9f5b-0d6c2e8a4b17"]
+pub struct DrawSkiaCapsuleComponentDef {
+    pub half_length: f32,
+    pub radius: f32,
+    pub paint: PaintDef,
+}
+
+legion_prefab::register_component_type!(DrawSkiaCapsuleComponentDef);
+
+pub struct DrawSkiaCapsuleComponent {
+    pub half_length: f32,
+    pub radius: f32,
+    pub paint: Paint,
+}
+
+impl From<DrawSkiaCapsuleComponentDef> for DrawSkiaCapsuleComponent {
+    fn from(from: DrawSkiaCapsuleComponentDef) -> Self {
+        DrawSkiaCapsuleComponent {
+            half_length: from.half_length,
+            radius: from.radius,
+            paint: from.paint.into(),
+        }
+    }
+}
+
+impl DrawSkiaCapsuleComponent {
+    pub fn draw(
+        &self,
+        canvas: &mut skia_safe::Canvas,
+        transform: &TransformComponent,
+    ) {
+        let paint = self.paint.0.lock().unwrap();
+        let scale = transform.uniform_scale();
+        let half_length = self.half_length * scale;
+        let radius = self.radius * scale;
+
+        let rrect = skia_safe::RRect::new_rect_xy(
+            skia_safe::Rect {
+                left: -half_length - radius,
+                right: half_length + radius,
+                top: -radius,
+                bottom: radius,
+            },
+            radius,
+            radius,
+        );
+
+        push_transform(canvas, transform);
+        canvas.draw_rrect(rrect, &paint);
+        canvas.restore();
+    }
+}
+
+impl minimum::editor::EditorSelectable for DrawSkiaCapsuleComponent {
+    fn create_editor_selection_world(
+        &self,
+        collision_world: &mut CollisionWorld<f32, Entity>,
+        _resources: &Resources,
+        _opened_prefab: &OpenedPrefabState,
+        world: &World,
+        entity: Entity,
+    ) {
+        let entity_ref = world.entry_ref(entity).unwrap();
+        if let Ok(transform) = entity_ref.get_component::<TransformComponentDef>() {
+            let scale = transform.uniform_scale();
+
+            // ncollide's capsule lies along the Y axis, rotate it to lie along X
+            let shape_handle = ShapeHandle::new(Capsule::new(
+                (self.half_length * scale).max(0.0),
+                (self.radius * scale).max(0.01),
+            ));
+            let local_rotation = nalgebra::UnitQuaternion::from_axis_angle(
+                &nalgebra::Vector3::z_axis(),
+                std::f32::consts::FRAC_PI_2,
+            );
+
+            add_selection_shape(
+                collision_world,
+                transform,
+                shape_handle,
+                glam::Vec3::zero(),
+                local_rotation,
+                entity,
+            );
+        }
+    }
+}
+
+//
+// Draw a rectangle with rounded corners centered on the transform. half_extents is affected by
+// scale, corner_radius is affected by uniform scale
+//
+#[derive(TypeUuid, Serialize, Deserialize, SerdeDiff, Debug, PartialEq, Clone, Inspect, Default)]
+#[uuid = "e2a96d3c-8b4f-4f1a-a7c0-6e3b9d1f5a84"]
+pub struct DrawSkiaRoundedRectComponentDef {
+    #[serde_diff(opaque)]
+    pub half_extents: Vec2,
+    pub corner_radius: f32,
+    pub paint: PaintDef,
+}
+
+legion_prefab::register_component_type!(DrawSkiaRoundedRectComponentDef);
+
+pub struct DrawSkiaRoundedRectComponent {
+    pub half_extents: Vec2,
+    pub corner_radius: f32,
+    pub paint: Paint,
+}
+
+impl From<DrawSkiaRoundedRectComponentDef> for DrawSkiaRoundedRectComponent {
+    fn from(from: DrawSkiaRoundedRectComponentDef) -> Self {
+        DrawSkiaRoundedRectComponent {
+            half_extents: from.half_extents,
+            corner_radius: from.corner_radius,
+            paint: from.paint.into(),
+        }
+    }
+}
+
+impl DrawSkiaRoundedRectComponent {
+    pub fn draw(
+        &self,
+        canvas: &mut skia_safe::Canvas,
+        transform: &TransformComponent,
+    ) {
+        let paint = self.paint.0.lock().unwrap();
+        let scale = transform.scale();
+        let half_width = self.half_extents.x() * scale.x();
+        let half_height = self.half_extents.y() * scale.y();
+        let corner_radius = self.corner_radius * transform.uniform_scale();
+
+        let rrect = skia_safe::RRect::new_rect_xy(
+            skia_safe::Rect {
+                left: -half_width,
+                right: half_width,
+                top: -half_height,
+                bottom: half_height,
+            },
+            corner_radius,
+            corner_radius,
+        );
+
+        push_transform(canvas, transform);
+        canvas.draw_rrect(rrect, &paint);
+        canvas.restore();
+    }
+}
+
+impl minimum::editor::EditorSelectable for DrawSkiaRoundedRectComponent {
+    fn create_editor_selection_world(
+        &self,
+        collision_world: &mut CollisionWorld<f32, Entity>,
+        _resources: &Resources,
+        _opened_prefab: &OpenedPrefabState,
+        world: &World,
+        entity: Entity,
+    ) {
+        let entity_ref = world.entry_ref(entity).unwrap();
+        if let Ok(transform) = entity_ref.get_component::<TransformComponentDef>() {
+            let scale = transform.scale();
+            let half_extents = glam::Vec3::new(
+                (self.half_extents.x() * scale.x()).max(0.01),
+                (self.half_extents.y() * scale.y()).max(0.01),
+                0.01,
+            );
+
+            let shape_handle = ShapeHandle::new(Cuboid::new(vec3_glam_to_glm(half_extents)));
+            add_selection_shape(
+                collision_world,
+                transform,
+                shape_handle,
+                glam::Vec3::zero(),
+                nalgebra::UnitQuaternion::identity(),
+                entity,
+            );
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use serde::{Deserialize, Serialize};
+use serde_diff::SerdeDiff;
+use type_uuid::TypeUuid;
+use skulpin::skia_safe;
+use ncollide3d::world::CollisionWorld;
+use legion::world::World;
+use ncollide3d::pipeline::{CollisionGroups, GeometricQueryType};
+use ncollide3d::shape::Cuboid;
+use ncollide3d::shape::ShapeHandle;
+use minimum::components::{TransformComponent, TransformComponentDef};
+use minimum::math::{Vec2, Vec4};
+use minimum::resources::AssetResource;
+use imgui_inspect_derive::Inspect;
+use legion::*;
+use legion::storage::{Archetype, Components, ComponentWriter};
+use legion_prefab::SpawnFrom;
+use minimum::resources::editor::OpenedPrefabState;
+use atelier_assets::core::AssetUuid;
+use atelier_assets::loader::handle::{AssetHandle, Handle};
+use std::ops::Range;
+
+use crate::math_conversions::{vec3_glam_to_glm, quat_glam_to_glm};
+use crate::pipeline::ImageAsset;
+
+//
+// Draw an image at the component's current location. The image is referenced by asset UUID and
+// will be reloaded automatically if the source image changes.
+//
+#[derive(TypeUuid, Serialize, Deserialize, SerdeDiff, Debug, PartialEq, Clone, Inspect)]
+#[uuid = "5d2f8a6c-0e3b-4c7d-9f1a-b4e8c2d6a039"]
+pub struct DrawSkiaSpriteComponentDef {
+    #[serde_diff(opaque)]
+    #[inspect(skip)]
+    pub image: AssetUuid,
+    // Size of the sprite in world units
+    #[serde_diff(opaque)]
+    pub size: Vec2,
+    // Point within the sprite that is placed at the transform's position. (0, 0) is top-left,
+    // (1, 1) is bottom-right
+    #[serde_diff(opaque)]
+    pub pivot: Vec2,
+    // Multiplied with the image's color
+    #[serde_diff(opaque)]
+    pub tint: Vec4,
+    // x, y, width, height in pixels. If width or height is zero, the whole image is used
+    #[serde_diff(opaque)]
+    pub sub_rect: Vec4,
+}
+
+legion_prefab::register_component_type!(DrawSkiaSpriteComponentDef);
+
+impl Default for DrawSkiaSpriteComponentDef {
+    fn default() -> Self {
+        DrawSkiaSpriteComponentDef {
+            image: Default::default(),
+            size: glam::Vec2::new(1.0, 1.0).into(),
+            pivot: glam::Vec2::new(0.5, 0.5).into(),
+            tint: glam::Vec4::new(1.0, 1.0, 1.0, 1.0).into(),
+            sub_rect: Vec4::zero(),
+        }
+    }
+}
+
+pub struct DrawSkiaSpriteComponent {
+    // None if the def's image is the default (nil) UUID, which draws nothing
+    pub image: Option<Handle<ImageAsset>>,
+    pub size: Vec2,
+    pub pivot: Vec2,
+    pub tint: Vec4,
+    pub sub_rect: Vec4,
+}
+
+impl SpawnFrom<DrawSkiaSpriteComponentDef> for DrawSkiaSpriteComponent {
+    fn spawn_from(
+        resources: &Resources,
+        src_entity_range: Range<usize>,
+        src_arch: &Archetype,
+        src_components: &Components,
+        dst: &mut ComponentWriter<Self>,
+        push_fn: fn(&mut ComponentWriter<Self>, Self),
+    ) {
+        let asset_resource = resources.get::<AssetResource>().unwrap();
+        let from = legion_prefab::get_component_slice_from_archetype::<DrawSkiaSpriteComponentDef>(
+            src_components,
+            src_arch,
+            src_entity_range,
+        )
+        .unwrap();
+
+        for from in from {
+            let image = if from.image != AssetUuid::default() {
+                let load_handle = asset_resource.loader().add_ref(from.image);
+                Some(Handle::<ImageAsset>::new(
+                    asset_resource.tx().clone(),
+                    load_handle,
+                ))
+            } else {
+                None
+            };
+
+            let sprite = DrawSkiaSpriteComponent {
+                image,
+                size: from.size,
+                pivot: from.pivot,
+                tint: from.tint,
+                sub_rect: from.sub_rect,
+            };
+
+            (push_fn)(dst, sprite);
+        }
+    }
+}
+
+impl DrawSkiaSpriteComponent {
+    // Does nothing if there is no image or it has not loaded yet
+    pub fn draw(
+        &self,
+        canvas: &mut skia_safe::Canvas,
+        transform: &TransformComponent,
+        asset_resource: &AssetResource,
+    ) {
+        let image = match self
+            .image
+            .as_ref()
+            .and_then(|image| image.asset(asset_resource.storage()))
+        {
+            Some(image) => image,
+            None => return,
+        };
+
+        let src_rect = if self.sub_rect.z() > 0.0 && self.sub_rect.w() > 0.0 {
+            skia_safe::Rect::from_xywh(
+                self.sub_rect.x(),
+                self.sub_rect.y(),
+                self.sub_rect.z(),
+                self.sub_rect.w(),
+            )
+        } else {
+            skia_safe::Rect::from_wh(image.width() as f32, image.height() as f32)
+        };
+
+        let mut paint = skia_safe::Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_color_filter(skia_safe::color_filters::blend(
+            skia_safe::Color4f::new(self.tint.x(), self.tint.y(), self.tint.z(), self.tint.w())
+                .to_color(),
+            skia_safe::BlendMode::Modulate,
+        ));
+
+        let position = transform.position();
+        let scale = transform.scale();
+        let rotation_in_degrees =
+            minimum::math::functions::z_rotation_radians(transform.rotation()).to_degrees();
+
+        // The canvas is +Y up but images are stored top to bottom, so flip Y when drawing. The
+        // sprite is drawn into a unit square that is then scaled to its size.
+        canvas.save();
+        canvas.translate((position.x(), position.y()));
+        canvas.rotate(rotation_in_degrees, None);
+        canvas.scale((self.size.x() * scale.x(), -self.size.y() * scale.y()));
+        canvas.draw_image_rect(
+            &image.image,
+            Some((&src_rect, skia_safe::canvas::SrcRectConstraint::Fast)),
+            skia_safe::Rect {
+                left: -self.pivot.x(),
+                top: -self.pivot.y(),
+                right: 1.0 - self.pivot.x(),
+                bottom: 1.0 - self.pivot.y(),
+            },
+            &paint,
+        );
+        canvas.restore();
+    }
+}
+
+impl minimum::editor::EditorSelectable for DrawSkiaSpriteComponent {
+    fn create_editor_selection_world(
+        &self,
+        collision_world: &mut CollisionWorld<f32, Entity>,
+        _resources: &Resources,
+        _opened_prefab: &OpenedPrefabState,
+        world: &World,
+        entity: Entity,
+    ) {
+        let entity_ref = world.entry_ref(entity).unwrap();
+        if let Ok(transform) = entity_ref.get_component::<TransformComponentDef>() {
+            let scale = transform.scale();
+            let size = glam::Vec3::new(self.size.x() * scale.x(), self.size.y() * scale.y(), 0.0);
+            let half_extents = glam::Vec3::new(size.x() * 0.5, size.y() * 0.5, 0.01);
+
+            // Offset from the pivot to the center of the sprite, which rotates with the sprite
+            let rotation = transform.rotation_quat();
+            let center = transform.position()
+                + rotation
+                    * glam::Vec3::new(
+                        (0.5 - self.pivot.x()) * size.x(),
+                        (self.pivot.y() - 0.5) * size.y(),
+                        0.0,
+                    );
+
+            let shape_handle = ShapeHandle::new(Cuboid::new(vec3_glam_to_glm(half_extents)));
+            let rotation = quat_glam_to_glm(rotation);
+            let rotation = nalgebra::UnitQuaternion::from_quaternion(rotation);
+            collision_world.add(
+                ncollide3d::math::Isometry::from_parts(
+                    nalgebra::Translation::from(vec3_glam_to_glm(center)),
+                    rotation,
+                ),
+                shape_handle,
+                CollisionGroups::new(),
+                GeometricQueryType::Proximity(0.001),
+                entity,
+            );
+        }
+    }
+}
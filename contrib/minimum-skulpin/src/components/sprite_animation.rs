@@ -0,0 +1,122 @@
+use serde::{Deserialize, Serialize};
+use serde_diff::SerdeDiff;
+use type_uuid::TypeUuid;
+use minimum::resources::AssetResource;
+use imgui_inspect_derive::Inspect;
+use legion::*;
+use legion::storage::{Archetype, Components, ComponentWriter};
+use legion_prefab::SpawnFrom;
+use atelier_assets::core::AssetUuid;
+use atelier_assets::loader::handle::Handle;
+use std::ops::Range;
+
+use crate::pipeline::SpriteAnimationAsset;
+
+//
+// Plays a clip from a sprite animation asset by changing the sub_rect of the sprite on the same
+// entity. Time only advances while the simulation is running unless preview_in_editor is set.
+//
+#[derive(TypeUuid, Serialize, Deserialize, SerdeDiff, Debug, PartialEq, Clone, Inspect)]
+#[uuid = "f1c8e3a5-2b9d-4e7f-a6c0-8d3b5e1f9a24"]
+pub struct SpriteAnimationComponentDef {
+    #[serde_diff(opaque)]
+    #[inspect(skip)]
+    pub animation: AssetUuid,
+    // Name of the clip to play when spawned
+    pub clip: String,
+    // Negative values are treated as 0, clips can't be played in reverse
+    pub playback_speed: f32,
+    // Keep playing while the simulation is paused so the animation can be seen while editing
+    pub preview_in_editor: bool,
+}
+
+legion_prefab::register_component_type!(SpriteAnimationComponentDef);
+
+impl Default for SpriteAnimationComponentDef {
+    fn default() -> Self {
+        SpriteAnimationComponentDef {
+            animation: Default::default(),
+            clip: Default::default(),
+            playback_speed: 1.0,
+            preview_in_editor: false,
+        }
+    }
+}
+
+pub struct SpriteAnimationComponent {
+    pub animation: Handle<SpriteAnimationAsset>,
+    pub playback_speed: f32,
+    pub preview_in_editor: bool,
+    clip: String,
+    clip_time: f32,
+}
+
+impl SpriteAnimationComponent {
+    pub fn clip(&self) -> &str {
+        &self.clip
+    }
+
+    // Seconds since the current clip started, scaled by playback speed
+    pub fn clip_time(&self) -> f32 {
+        self.clip_time
+    }
+
+    // Switch to the named clip, starting from its first frame. Does nothing if the clip is already
+    // playing, use restart() to play it again from the beginning
+    pub fn play(
+        &mut self,
+        clip: &str,
+    ) {
+        if self.clip != clip {
+            self.clip = clip.to_string();
+            self.clip_time = 0.0;
+        }
+    }
+
+    pub fn restart(&mut self) {
+        self.clip_time = 0.0;
+    }
+
+    pub fn advance(
+        &mut self,
+        dt: f32,
+    ) {
+        self.clip_time += dt * self.playback_speed.max(0.0);
+    }
+}
+
+impl SpawnFrom<SpriteAnimationComponentDef> for SpriteAnimationComponent {
+    fn spawn_from(
+        resources: &Resources,
+        src_entity_range: Range<usize>,
+        src_arch: &Archetype,
+        src_components: &Components,
+        dst: &mut ComponentWriter<Self>,
+        push_fn: fn(&mut ComponentWriter<Self>, Self),
+    ) {
+        let asset_resource = resources.get::<AssetResource>().unwrap();
+        let from =
+            legion_prefab::get_component_slice_from_archetype::<SpriteAnimationComponentDef>(
+                src_components,
+                src_arch,
+                src_entity_range,
+            )
+            .unwrap();
+
+        for from in from {
+            let load_handle = asset_resource.loader().add_ref(from.animation);
+            let animation =
+                Handle::<SpriteAnimationAsset>::new(asset_resource.tx().clone(), load_handle);
+
+            let sprite_animation = SpriteAnimationComponent {
+                animation,
+                playback_speed: from.playback_speed.max(0.0),
+                preview_in_editor: from.preview_in_editor,
+                clip: from.clip.clone(),
+                clip_time: 0.0,
+            };
+
+            (push_fn)(dst, sprite_animation);
+        }
+    }
+}
@@ -0,0 +1,348 @@
+use serde::{Deserialize, Serialize};
+use serde_diff::SerdeDiff;
+use type_uuid::TypeUuid;
+use skulpin::skia_safe;
+use ncollide3d::world::CollisionWorld;
+use legion::world::World;
+use ncollide3d::pipeline::{CollisionGroups, GeometricQueryType};
+use ncollide3d::shape::Cuboid;
+use ncollide3d::shape::ShapeHandle;
+use minimum::components::{TransformComponent, TransformComponentDef};
+use minimum::math::Vec4;
+use minimum::resources::AssetResource;
+use imgui_inspect_derive::Inspect;
+use imgui_inspect::{InspectArgsDefault, InspectRenderDefault};
+use minimum::inspect::{InspectEnum, render_enum, render_enum_mut};
+use legion::*;
+use legion::storage::{Archetype, Components, ComponentWriter};
+use legion_prefab::SpawnFrom;
+use minimum::resources::editor::OpenedPrefabState;
+use atelier_assets::core::AssetUuid;
+use atelier_assets::loader::handle::{AssetHandle, Handle};
+use std::ops::Range;
+
+use crate::math_conversions::{vec3_glam_to_glm, quat_glam_to_glm};
+use crate::pipeline::FontAsset;
+use super::Paint;
+
+// Text is laid out at this size and then scaled. Skia does not handle very small font sizes
+// (which are common when using world units) well.
+const LAYOUT_FONT_SIZE: f32 = 64.0;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum TextAlignment {
+    Left,
+    Center,
+    Right,
+}
+
+impl Default for TextAlignment {
+    fn default() -> Self {
+        TextAlignment::Left
+    }
+}
+
+impl InspectEnum for TextAlignment {
+    const VARIANTS: &'static [Self] = &[
+        TextAlignment::Left,
+        TextAlignment::Center,
+        TextAlignment::Right,
+    ];
+}
+
+impl InspectRenderDefault<TextAlignment> for TextAlignment {
+    fn render(
+        data: &[&TextAlignment],
+        label: &'static str,
+        ui: &imgui::Ui,
+        _args: &InspectArgsDefault,
+    ) {
+        render_enum(data, label, ui);
+    }
+
+    fn render_mut(
+        data: &mut [&mut TextAlignment],
+        label: &'static str,
+        ui: &imgui::Ui,
+        _args: &InspectArgsDefault,
+    ) -> bool {
+        render_enum_mut(data, label, ui)
+    }
+}
+
+//
+// Draw text at the component's current location. The transform's position is the top of the
+// first line, horizontally aligned based on alignment. If screen_space is set, the position is in
+// logical pixels from the top-left of the window and size is in pixels, which is useful for HUDs.
+//
+#[derive(TypeUuid, Serialize, Deserialize, SerdeDiff, Debug, PartialEq, Clone, Inspect)]
+#[uuid = "9e3a7c5f-0b6d-4f2a-8e1c-7a4d2b9f5e03"]
+pub struct DrawSkiaTextComponentDef {
+    pub text: String,
+    // The default (nil) UUID uses skia's default typeface
+    #[serde_diff(opaque)]
+    #[inspect(skip)]
+    pub font: AssetUuid,
+    pub size: f32,
+    #[serde_diff(opaque)]
+    pub color: Vec4,
+    #[serde_diff(opaque)]
+    pub alignment: TextAlignment,
+    // Lines longer than this are wrapped at spaces. Zero disables wrapping
+    pub wrap_width: f32,
+    pub screen_space: bool,
+}
+
+legion_prefab::register_component_type!(DrawSkiaTextComponentDef);
+
+impl Default for DrawSkiaTextComponentDef {
+    fn default() -> Self {
+        DrawSkiaTextComponentDef {
+            text: "Text".to_string(),
+            font: Default::default(),
+            size: 0.5,
+            color: glam::Vec4::new(1.0, 1.0, 1.0, 1.0).into(),
+            alignment: Default::default(),
+            wrap_width: 0.0,
+            screen_space: false,
+        }
+    }
+}
+
+pub struct DrawSkiaTextComponent {
+    pub text: String,
+    pub font: Option<Handle<FontAsset>>,
+    pub size: f32,
+    pub paint: Paint,
+    pub alignment: TextAlignment,
+    pub wrap_width: f32,
+    pub screen_space: bool,
+}
+
+impl SpawnFrom<DrawSkiaTextComponentDef> for DrawSkiaTextComponent {
+    fn spawn_from(
+        resources: &Resources,
+        src_entity_range: Range<usize>,
+        src_arch: &Archetype,
+        src_components: &Components,
+        dst: &mut ComponentWriter<Self>,
+        push_fn: fn(&mut ComponentWriter<Self>, Self),
+    ) {
+        let asset_resource = resources.get::<AssetResource>().unwrap();
+        let from = legion_prefab::get_component_slice_from_archetype::<DrawSkiaTextComponentDef>(
+            src_components,
+            src_arch,
+            src_entity_range,
+        )
+        .unwrap();
+
+        for from in from {
+            let font = if from.font == AssetUuid::default() {
+                None
+            } else {
+                let load_handle = asset_resource.loader().add_ref(from.font);
+                Some(Handle::<FontAsset>::new(
+                    asset_resource.tx().clone(),
+                    load_handle,
+                ))
+            };
+
+            let color = skia_safe::Color4f::new(
+                from.color.x(),
+                from.color.y(),
+                from.color.z(),
+                from.color.w(),
+            );
+            let mut paint = skia_safe::Paint::new(color, None);
+            paint.set_anti_alias(true);
+            paint.set_style(skia_safe::paint::Style::Fill);
+
+            let text = DrawSkiaTextComponent {
+                text: from.text.clone(),
+                font,
+                size: from.size,
+                paint: Paint(std::sync::Mutex::new(paint)),
+                alignment: from.alignment,
+                wrap_width: from.wrap_width,
+                screen_space: from.screen_space,
+            };
+
+            (push_fn)(dst, text);
+        }
+    }
+}
+
+impl DrawSkiaTextComponent {
+    // Falls back to the default typeface if the font asset has not loaded yet
+    fn layout_font(
+        &self,
+        asset_resource: &AssetResource,
+    ) -> skia_safe::Font {
+        let typeface = self
+            .font
+            .as_ref()
+            .and_then(|font| font.asset(asset_resource.storage()))
+            .map(|font| font.typeface.clone())
+            .unwrap_or_default();
+
+        skia_safe::Font::from_typeface(typeface, LAYOUT_FONT_SIZE)
+    }
+
+    // Splits the text into lines, returning each line and its width in layout units
+    fn layout_lines(
+        &self,
+        font: &skia_safe::Font,
+        scale: f32,
+    ) -> Vec<(String, f32)> {
+        let measure = |text: &str| font.measure_str(text, None).0;
+        let wrap_width = if self.wrap_width > 0.0 && scale > 0.0 {
+            Some(self.wrap_width / scale)
+        } else {
+            None
+        };
+
+        let mut lines = Vec::new();
+        for paragraph in self.text.lines() {
+            let wrap_width = match wrap_width {
+                Some(wrap_width) => wrap_width,
+                None => {
+                    lines.push((paragraph.to_string(), measure(paragraph)));
+                    continue;
+                }
+            };
+
+            let mut line = String::new();
+            for word in paragraph.split(' ') {
+                let candidate = if line.is_empty() {
+                    word.to_string()
+                } else {
+                    format!("{} {}", line, word)
+                };
+
+                if !line.is_empty() && measure(&candidate) > wrap_width {
+                    let width = measure(&line);
+                    lines.push((std::mem::replace(&mut line, word.to_string()), width));
+                } else {
+                    line = candidate;
+                }
+            }
+
+            let width = measure(&line);
+            lines.push((line, width));
+        }
+
+        lines
+    }
+
+    fn line_offset(
+        &self,
+        width: f32,
+    ) -> f32 {
+        match self.alignment {
+            TextAlignment::Left => 0.0,
+            TextAlignment::Center => -width * 0.5,
+            TextAlignment::Right => -width,
+        }
+    }
+
+    // Returns the size of the laid out text in world units (or pixels if screen_space is set)
+    pub fn extents(
+        &self,
+        asset_resource: &AssetResource,
+        scale: f32,
+    ) -> glam::Vec2 {
+        let font = self.layout_font(asset_resource);
+        let scale = self.size * scale / LAYOUT_FONT_SIZE;
+        let lines = self.layout_lines(&font, scale);
+        let (line_spacing, _) = font.metrics();
+
+        let width = lines.iter().map(|(_, width)| *width).fold(0.0, f32::max);
+        let height = lines.len() as f32 * line_spacing;
+        glam::Vec2::new(width, height) * scale
+    }
+
+    pub fn draw(
+        &self,
+        canvas: &mut skia_safe::Canvas,
+        transform: &TransformComponent,
+        asset_resource: &AssetResource,
+    ) {
+        let font = self.layout_font(asset_resource);
+        let scale = self.size * transform.uniform_scale() / LAYOUT_FONT_SIZE;
+        let lines = self.layout_lines(&font, scale);
+        let (line_spacing, metrics) = font.metrics();
+        let paint = self.paint.0.lock().unwrap();
+
+        let position = transform.position();
+        let rotation_in_degrees =
+            minimum::math::functions::z_rotation_radians(transform.rotation()).to_degrees();
+
+        // World space is +Y up but text is laid out +Y down, so flip Y when drawing in the world.
+        // Screen space is already +Y down.
+        let y_scale = if self.screen_space { scale } else { -scale };
+
+        canvas.save();
+        canvas.translate((position.x(), position.y()));
+        canvas.rotate(rotation_in_degrees, None);
+        canvas.scale((scale, y_scale));
+
+        for (index, (line, width)) in lines.iter().enumerate() {
+            // ascent is negative, this places the top of the first line at the origin
+            let baseline = index as f32 * line_spacing - metrics.ascent;
+            canvas.draw_str(line, (self.line_offset(*width), baseline), &font, &paint);
+        }
+
+        canvas.restore();
+    }
+}
+
+impl minimum::editor::EditorSelectable for DrawSkiaTextComponent {
+    fn create_editor_selection_world(
+        &self,
+        collision_world: &mut CollisionWorld<f32, Entity>,
+        resources: &Resources,
+        _opened_prefab: &OpenedPrefabState,
+        world: &World,
+        entity: Entity,
+    ) {
+        // Screen space text doesn't have a location in the world to click on
+        if self.screen_space {
+            return;
+        }
+
+        let entity_ref = world.entry_ref(entity).unwrap();
+        if let Ok(transform) = entity_ref.get_component::<TransformComponentDef>() {
+            let asset_resource = resources.get::<AssetResource>().unwrap();
+            let extents = self.extents(&*asset_resource, transform.uniform_scale());
+            let half_extents = glam::Vec3::new(
+                (extents.x() * 0.5).max(0.01),
+                (extents.y() * 0.5).max(0.01),
+                0.01,
+            );
+
+            let center_x = match self.alignment {
+                TextAlignment::Left => half_extents.x(),
+                TextAlignment::Center => 0.0,
+                TextAlignment::Right => -half_extents.x(),
+            };
+            // The offset to the center of the text rotates with the text
+            let rotation = transform.rotation_quat();
+            let center =
+                transform.position() + rotation * glam::Vec3::new(center_x, -half_extents.y(), 0.0);
+
+            let shape_handle = ShapeHandle::new(Cuboid::new(vec3_glam_to_glm(half_extents)));
+            let rotation = quat_glam_to_glm(rotation);
+            let rotation = nalgebra::UnitQuaternion::from_quaternion(rotation);
+            collision_world.add(
+                ncollide3d::math::Isometry::from_parts(
+                    nalgebra::Translation::from(vec3_glam_to_glm(center)),
+                    rotation,
+                ),
+                shape_handle,
+                CollisionGroups::new(),
+                GeometricQueryType::Proximity(0.001),
+                entity,
+            );
+        }
+    }
+}
@@ -0,0 +1,259 @@
+use serde::{Deserialize, Serialize};
+use serde_diff::SerdeDiff;
+use type_uuid::TypeUuid;
+use skulpin::skia_safe;
+use ncollide3d::world::CollisionWorld;
+use legion::world::World;
+use ncollide3d::pipeline::{CollisionGroups, GeometricQueryType};
+use ncollide3d::shape::Cuboid;
+use ncollide3d::shape::ShapeHandle;
+use minimum::components::{TransformComponent, TransformComponentDef};
+use minimum::math::Vec2;
+use minimum::resources::AssetResource;
+use imgui_inspect_derive::Inspect;
+use legion::*;
+use legion::storage::{Archetype, Components, ComponentWriter};
+use legion_prefab::SpawnFrom;
+use minimum::resources::editor::OpenedPrefabState;
+use atelier_assets::core::AssetUuid;
+use atelier_assets::loader::handle::{AssetHandle, Handle};
+use std::ops::Range;
+
+use crate::math_conversions::{vec3_glam_to_glm, quat_glam_to_glm};
+use crate::pipeline::ImageAsset;
+use super::{Paint, PaintDef};
+
+//
+// A grid of tiles, i.e. a tile layer imported from Tiled. The transform's position is the
+// top-left corner of the map. Rows go down (-Y) and columns go right (+X), and the whole map
+// rotates around its top-left corner. Tiles are cut from the tileset image, laid out the way Tiled
+// lays them out. Until the image is set and loaded, tiles are drawn as blocks using the paint.
+//
+#[derive(TypeUuid, Serialize, Deserialize, SerdeDiff, Debug, PartialEq, Clone, Inspect, Default)]
+#[uuid = "0b6b2e6f-3c8a-4f3e-9d4b-6a1f0c5e7d21"]
+pub struct TilemapComponentDef {
+    pub width: u32,
+    pub height: u32,
+    #[serde_diff(opaque)]
+    pub tile_size: Vec2,
+    // Row-major starting from the top-left. 0 is an empty tile, anything else is the Tiled
+    // global tile ID
+    #[serde_diff(opaque)]
+    #[inspect(skip)]
+    pub tiles: Vec<u32>,
+    // Only the alpha is used when tiles are drawn from the tileset image
+    pub paint: PaintDef,
+    #[serde(default)]
+    #[serde_diff(opaque)]
+    #[inspect(skip)]
+    pub tileset_image: AssetUuid,
+    #[serde(default)]
+    pub tileset: TilesetLayout,
+}
+
+legion_prefab::register_component_type!(TilemapComponentDef);
+
+// Where tiles are in the tileset image, matching the tileset settings in Tiled. All sizes are in
+// pixels.
+#[derive(Serialize, Deserialize, SerdeDiff, Debug, PartialEq, Clone, Copy, Inspect, Default)]
+pub struct TilesetLayout {
+    // Global tile ID of the first tile in the image. Tiles with a lower ID aren't drawn from the
+    // image
+    pub first_gid: u32,
+    pub columns: u32,
+    #[serde_diff(opaque)]
+    pub tile_size: Vec2,
+    pub margin: u32,
+    pub spacing: u32,
+}
+
+impl TilesetLayout {
+    // The part of the tileset image the tile with the given global ID is in
+    pub fn source_rect(
+        &self,
+        gid: u32,
+    ) -> Option<skia_safe::Rect> {
+        if self.columns == 0 || gid < self.first_gid {
+            return None;
+        }
+
+        let index = gid - self.first_gid;
+        let column = index % self.columns;
+        let row = index / self.columns;
+        Some(skia_safe::Rect::from_xywh(
+            (self.margin + column * self.spacing) as f32 + column as f32 * self.tile_size.x(),
+            (self.margin + row * self.spacing) as f32 + row as f32 * self.tile_size.y(),
+            self.tile_size.x(),
+            self.tile_size.y(),
+        ))
+    }
+}
+
+pub struct TilemapComponent {
+    pub width: u32,
+    pub height: u32,
+    pub tile_size: Vec2,
+    pub tiles: Vec<u32>,
+    pub paint: Paint,
+    pub tileset_image: Option<Handle<ImageAsset>>,
+    pub tileset: TilesetLayout,
+}
+
+impl SpawnFrom<TilemapComponentDef> for TilemapComponent {
+    fn spawn_from(
+        resources: &Resources,
+        src_entity_range: Range<usize>,
+        src_arch: &Archetype,
+        src_components: &Components,
+        dst: &mut ComponentWriter<Self>,
+        push_fn: fn(&mut ComponentWriter<Self>, Self),
+    ) {
+        let asset_resource = resources.get::<AssetResource>().unwrap();
+        let from = legion_prefab::get_component_slice_from_archetype::<TilemapComponentDef>(
+            src_components,
+            src_arch,
+            src_entity_range,
+        )
+        .unwrap();
+
+        for from in from {
+            let tileset_image = if from.tileset_image != AssetUuid::default() {
+                let load_handle = asset_resource.loader().add_ref(from.tileset_image);
+                Some(Handle::<ImageAsset>::new(
+                    asset_resource.tx().clone(),
+                    load_handle,
+                ))
+            } else {
+                None
+            };
+
+            let tilemap = TilemapComponent {
+                width: from.width,
+                height: from.height,
+                tile_size: from.tile_size,
+                tiles: from.tiles.clone(),
+                paint: from.paint.into(),
+                tileset_image,
+                tileset: from.tileset,
+            };
+
+            (push_fn)(dst, tilemap);
+        }
+    }
+}
+
+impl TilemapComponent {
+    pub fn tile(
+        &self,
+        column: u32,
+        row: u32,
+    ) -> u32 {
+        if column >= self.width || row >= self.height {
+            return 0;
+        }
+
+        self.tiles
+            .get((row * self.width + column) as usize)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    pub fn draw(
+        &self,
+        canvas: &mut skia_safe::Canvas,
+        transform: &TransformComponent,
+        asset_resource: &AssetResource,
+    ) {
+        let paint = self.paint.0.lock().unwrap();
+        let image = self
+            .tileset_image
+            .as_ref()
+            .and_then(|image| image.asset(asset_resource.storage()));
+
+        let mut image_paint = skia_safe::Paint::default();
+        image_paint.set_alpha_f(paint.alpha_f());
+
+        let position = transform.position();
+        let scale = transform.scale();
+        let rotation_in_degrees =
+            minimum::math::functions::z_rotation_radians(transform.rotation()).to_degrees();
+
+        // Draw in tile units with +Y down so that each tile is the unit square at (column, row).
+        // This also flips Y so that the tileset image is drawn the right way up.
+        canvas.save();
+        canvas.translate((position.x(), position.y()));
+        canvas.rotate(rotation_in_degrees, None);
+        canvas.scale((
+            self.tile_size.x() * scale.x(),
+            -self.tile_size.y() * scale.y(),
+        ));
+
+        for row in 0..self.height {
+            for column in 0..self.width {
+                let gid = self.tile(column, row);
+                if gid == 0 {
+                    continue;
+                }
+
+                let dst_rect = skia_safe::Rect::from_xywh(column as f32, row as f32, 1.0, 1.0);
+                let src_rect = self.tileset.source_rect(gid);
+                match (image, src_rect) {
+                    (Some(image), Some(src_rect)) => {
+                        canvas.draw_image_rect(
+                            &image.image,
+                            Some((&src_rect, skia_safe::canvas::SrcRectConstraint::Strict)),
+                            dst_rect,
+                            &image_paint,
+                        );
+                    }
+                    _ => {
+                        canvas.draw_rect(dst_rect, &paint);
+                    }
+                }
+            }
+        }
+
+        canvas.restore();
+    }
+}
+
+impl minimum::editor::EditorSelectable for TilemapComponent {
+    fn create_editor_selection_world(
+        &self,
+        collision_world: &mut CollisionWorld<f32, Entity>,
+        _resources: &Resources,
+        _opened_prefab: &OpenedPrefabState,
+        world: &World,
+        entity: Entity,
+    ) {
+        let entity_ref = world.entry_ref(entity).unwrap();
+        if let Ok(transform) = entity_ref.get_component::<TransformComponentDef>() {
+            let scale = transform.scale();
+            let half_extents = glam::Vec3::new(
+                self.width as f32 * self.tile_size.x() * scale.x() * 0.5,
+                self.height as f32 * self.tile_size.y() * scale.y() * 0.5,
+                0.01,
+            );
+
+            // The position is the top-left corner that the map rotates around, the shape is
+            // centered
+            let center = transform.position()
+                + transform.rotation_quat()
+                    * glam::Vec3::new(half_extents.x(), -half_extents.y(), 0.0);
+
+            let shape_handle = ShapeHandle::new(Cuboid::new(vec3_glam_to_glm(half_extents)));
+            let rotation = quat_glam_to_glm(transform.rotation_quat());
+            let rotation = nalgebra::UnitQuaternion::from_quaternion(rotation);
+            collision_world.add(
+                ncollide3d::math::Isometry::from_parts(
+                    nalgebra::Translation::from(vec3_glam_to_glm(center)),
+                    rotation,
+                ),
+                shape_handle,
+                CollisionGroups::new(),
+                GeometricQueryType::Proximity(0.001),
+                entity,
+            );
+        }
+    }
+}
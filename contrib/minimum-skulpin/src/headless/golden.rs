@@ -0,0 +1,474 @@
+use legion::*;
+use legion::world::EntityHasher;
+use legion_prefab::{ComponentRegistration, CookedPrefab};
+use prefab_format::ComponentTypeUuid;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use minimum::ComponentRegistry;
+use minimum::resources::{ComponentRegistryResource, TimeResource};
+
+use super::HeadlessRenderer;
+
+// If this environment variable is set, reference images are (re)written from the rendered frame
+// instead of being compared against
+const UPDATE_GOLDEN_IMAGES_ENV_VAR: &str = "MINIMUM_UPDATE_GOLDEN_IMAGES";
+
+pub struct GoldenImageOptions {
+    pub width: u32,
+    pub height: u32,
+    // Number of times the update schedule runs before the frame is rendered
+    pub frame_count: u32,
+    // Time that passes during each update
+    pub frame_dt: std::time::Duration,
+    // Largest difference in any color channel before a pixel is considered to not match
+    pub channel_tolerance: u8,
+    // Fraction of pixels (0.0 - 1.0) that may not match before the test fails
+    pub max_mismatched_ratio: f32,
+}
+
+impl Default for GoldenImageOptions {
+    fn default() -> Self {
+        GoldenImageOptions {
+            width: 256,
+            height: 256,
+            frame_count: 60,
+            frame_dt: std::time::Duration::from_secs_f32(1.0 / 60.0),
+            channel_tolerance: 2,
+            max_mismatched_ratio: 0.0,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum GoldenImageError {
+    Io(std::io::Error),
+    Image(image::ImageError),
+    Prefab(String),
+    RenderFailed,
+    MissingReference {
+        reference_path: PathBuf,
+        actual_path: PathBuf,
+    },
+    SizeMismatch {
+        expected: (u32, u32),
+        actual: (u32, u32),
+    },
+    Mismatch {
+        mismatched_pixels: usize,
+        total_pixels: usize,
+        actual_path: PathBuf,
+        diff_path: PathBuf,
+    },
+}
+
+impl std::fmt::Display for GoldenImageError {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            GoldenImageError::Io(e) => write!(f, "IO error: {}", e),
+            GoldenImageError::Image(e) => write!(f, "Image error: {}", e),
+            GoldenImageError::Prefab(e) => write!(f, "Failed to load prefab: {}", e),
+            GoldenImageError::RenderFailed => write!(f, "Failed to render frame"),
+            GoldenImageError::MissingReference {
+                reference_path,
+                actual_path,
+            } => write!(
+                f,
+                "Reference image {:?} does not exist, the rendered frame was written to {:?}. Set {} to accept it",
+                reference_path, actual_path, UPDATE_GOLDEN_IMAGES_ENV_VAR
+            ),
+            GoldenImageError::SizeMismatch { expected, actual } => write!(
+                f,
+                "Reference image is {}x{} but the rendered frame is {}x{}",
+                expected.0, expected.1, actual.0, actual.1
+            ),
+            GoldenImageError::Mismatch {
+                mismatched_pixels,
+                total_pixels,
+                actual_path,
+                diff_path,
+            } => write!(
+                f,
+                "{} of {} pixels do not match the reference image. Rendered frame written to {:?}, diff written to {:?}",
+                mismatched_pixels, total_pixels, actual_path, diff_path
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GoldenImageError {}
+
+impl From<std::io::Error> for GoldenImageError {
+    fn from(e: std::io::Error) -> Self {
+        GoldenImageError::Io(e)
+    }
+}
+
+impl From<image::ImageError> for GoldenImageError {
+    fn from(e: image::ImageError) -> Self {
+        GoldenImageError::Image(e)
+    }
+}
+
+// Reads a .prefab file and cooks it without going through the asset daemon. The prefab must not
+// override other prefabs. Assets the prefab references (images, fonts, etc.) are not loaded by
+// this, components that need them will draw as they do while the asset is still loading.
+pub fn load_prefab_without_daemon<P: AsRef<Path>>(
+    path: P,
+    component_registry: &ComponentRegistry,
+) -> Result<CookedPrefab, GoldenImageError> {
+    let bytes = std::fs::read(path)?;
+    let mut de = ron::de::Deserializer::from_bytes(bytes.as_slice())
+        .map_err(|e| GoldenImageError::Prefab(format!("{:?}", e)))?;
+
+    //NOTE: PrefabSerdeContext is not generic over the hasher
+    let registered_components: HashMap<ComponentTypeUuid, ComponentRegistration> =
+        component_registry
+            .components_by_uuid()
+            .iter()
+            .map(|(k, v)| (*k, v.clone()))
+            .collect();
+
+    let prefab_serde_context = legion_prefab::PrefabSerdeContext {
+        registered_components: &registered_components,
+    };
+
+    let prefab_deser = legion_prefab::PrefabFormatDeserializer::new(prefab_serde_context);
+    prefab_format::deserialize(&mut de, &prefab_deser)
+        .map_err(|e| GoldenImageError::Prefab(format!("{:?}", e)))?;
+    let prefab = prefab_deser.prefab();
+
+    let prefab_cook_order = vec![prefab.prefab_id()];
+    let mut prefab_lookup = HashMap::new();
+    prefab_lookup.insert(prefab.prefab_id(), &prefab);
+
+    Ok(legion_prefab::cook_prefab(
+        component_registry.components(),
+        component_registry.components_by_uuid(),
+        prefab_cook_order.as_slice(),
+        &prefab_lookup,
+    ))
+}
+
+// Spawns a cooked prefab into the world, returning the mapping from prefab entities to world
+// entities
+pub fn spawn_prefab(
+    world: &mut World,
+    resources: &Resources,
+    component_registry: &ComponentRegistry,
+    cooked_prefab: &CookedPrefab,
+) -> HashMap<Entity, Entity, EntityHasher> {
+    let entity_map = HashMap::default();
+    world.clone_from(
+        &cooked_prefab.world,
+        &legion::query::any(),
+        &mut component_registry.spawn_clone_impl(resources, &entity_map),
+    )
+}
+
+pub struct ImageComparison {
+    pub mismatched_pixels: usize,
+    pub total_pixels: usize,
+    // RGBA8 image highlighting mismatched pixels in red over a faded copy of the expected image
+    pub diff: Vec<u8>,
+}
+
+// Compares two tightly packed RGBA8 images of the same size
+pub fn compare_rgba8(
+    actual: &[u8],
+    expected: &[u8],
+    channel_tolerance: u8,
+) -> ImageComparison {
+    assert_eq!(actual.len(), expected.len());
+
+    let mut mismatched_pixels = 0;
+    let mut diff = Vec::with_capacity(expected.len());
+    for (actual, expected) in actual.chunks_exact(4).zip(expected.chunks_exact(4)) {
+        let matches = actual
+            .iter()
+            .zip(expected)
+            .all(|(a, e)| (*a as i32 - *e as i32).abs() <= channel_tolerance as i32);
+
+        if matches {
+            let luminance =
+                (expected[0] as u32 * 3 + expected[1] as u32 * 6 + expected[2] as u32) / 10;
+            let faded = (luminance / 4) as u8;
+            diff.extend_from_slice(&[faded, faded, faded, 255]);
+        } else {
+            mismatched_pixels += 1;
+            diff.extend_from_slice(&[255, 0, 0, 255]);
+        }
+    }
+
+    ImageComparison {
+        mismatched_pixels,
+        total_pixels: expected.len() / 4,
+        diff,
+    }
+}
+
+fn write_png(
+    path: &Path,
+    rgba8: &[u8],
+    width: u32,
+    height: u32,
+) -> Result<(), GoldenImageError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    image::save_buffer(path, rgba8, width, height, image::ColorType::Rgba8)?;
+    Ok(())
+}
+
+// Compares a rendered frame against the reference image. On failure, the rendered frame is
+// written next to the reference as <name>.actual.png along with <name>.diff.png
+fn check_against_reference(
+    actual: &[u8],
+    width: u32,
+    height: u32,
+    reference_path: &Path,
+    options: &GoldenImageOptions,
+) -> Result<(), GoldenImageError> {
+    let actual_path = reference_path.with_extension("actual.png");
+    let diff_path = reference_path.with_extension("diff.png");
+
+    if std::env::var_os(UPDATE_GOLDEN_IMAGES_ENV_VAR).is_some() {
+        log::info!("Writing reference image {:?}", reference_path);
+        return write_png(reference_path, actual, width, height);
+    }
+
+    if !reference_path.exists() {
+        write_png(&actual_path, actual, width, height)?;
+        return Err(GoldenImageError::MissingReference {
+            reference_path: reference_path.to_path_buf(),
+            actual_path,
+        });
+    }
+
+    let expected = image::open(reference_path)?.to_rgba8();
+    if expected.width() != width || expected.height() != height {
+        write_png(&actual_path, actual, width, height)?;
+        return Err(GoldenImageError::SizeMismatch {
+            expected: (expected.width(), expected.height()),
+            actual: (width, height),
+        });
+    }
+
+    let comparison = compare_rgba8(actual, expected.as_raw(), options.channel_tolerance);
+    let mismatched_ratio = comparison.mismatched_pixels as f32 / comparison.total_pixels as f32;
+    if mismatched_ratio > options.max_mismatched_ratio {
+        write_png(&actual_path, actual, width, height)?;
+        write_png(&diff_path, &comparison.diff, width, height)?;
+        return Err(GoldenImageError::Mismatch {
+            mismatched_pixels: comparison.mismatched_pixels,
+            total_pixels: comparison.total_pixels,
+            actual_path,
+            diff_path,
+        });
+    }
+
+    Ok(())
+}
+
+// Loads the prefab into the world without the asset daemon, runs the update schedule
+// options.frame_count times with a fixed dt, renders a frame with the draw schedule and compares
+// it against the reference image.
+//
+// resources must already contain everything the schedules need, including
+// ComponentRegistryResource, TimeResource and CanvasDrawResource.
+pub fn run_golden_image_test<P: AsRef<Path>, R: AsRef<Path>>(
+    world: &mut World,
+    resources: &mut Resources,
+    update_schedule: &mut Schedule,
+    draw_schedule: &mut Schedule,
+    prefab_path: P,
+    reference_path: R,
+    options: &GoldenImageOptions,
+) -> Result<(), GoldenImageError> {
+    {
+        let component_registry = resources.get::<ComponentRegistryResource>().unwrap();
+        let cooked_prefab = load_prefab_without_daemon(prefab_path, &*component_registry)?;
+        spawn_prefab(world, resources, &*component_registry, &cooked_prefab);
+    }
+
+    resources
+        .get_mut::<TimeResource>()
+        .unwrap()
+        .set_fixed_update_time(Some(options.frame_dt));
+
+    for _ in 0..options.frame_count {
+        update_schedule.execute(world, resources);
+    }
+
+    let mut renderer = HeadlessRenderer::new(options.width, options.height)
+        .ok_or(GoldenImageError::RenderFailed)?;
+    renderer.draw(world, resources, draw_schedule);
+    let actual = renderer
+        .read_rgba8()
+        .ok_or(GoldenImageError::RenderFailed)?;
+
+    check_against_reference(
+        &actual,
+        options.width,
+        options.height,
+        reference_path.as_ref(),
+        options,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use atelier_assets::loader::rpc_loader::RpcLoader;
+
+    use minimum::ComponentRegistryBuilder;
+    use minimum::components::{TransformComponent, TransformComponentDef};
+    use minimum::resources::{AssetResource, CameraResource, ViewportResource};
+
+    use crate::components::{DrawSkiaBoxComponent, DrawSkiaBoxComponentDef};
+    use crate::resources::{CanvasDrawResource, DrawLayersResource};
+
+    // A fresh directory for files written by a test
+    fn test_output_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir()
+            .join("minimum-skulpin-golden")
+            .join(name);
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn compare_within_tolerance() {
+        let actual = [10, 20, 30, 255];
+        let expected = [12, 18, 30, 255];
+
+        assert_eq!(compare_rgba8(&actual, &expected, 2).mismatched_pixels, 0);
+        assert_eq!(compare_rgba8(&actual, &expected, 1).mismatched_pixels, 1);
+    }
+
+    #[test]
+    fn compare_diff_output() {
+        let actual = [40, 80, 120, 255, 0, 0, 0, 255];
+        let expected = [40, 80, 120, 255, 255, 255, 255, 255];
+
+        let comparison = compare_rgba8(&actual, &expected, 0);
+        assert_eq!(comparison.mismatched_pixels, 1);
+        assert_eq!(comparison.total_pixels, 2);
+
+        // Matching pixels are a faded grayscale of the expected image, mismatches are red
+        assert_eq!(comparison.diff, vec![18, 18, 18, 255, 255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn reference_size_mismatch() {
+        let dir = test_output_dir("reference_size_mismatch");
+        let reference_path = dir.join("reference.png");
+        write_png(&reference_path, &[0; 8], 2, 1).unwrap();
+
+        let result = check_against_reference(
+            &[0; 4],
+            1,
+            1,
+            &reference_path,
+            &GoldenImageOptions::default(),
+        );
+
+        match &result {
+            Err(GoldenImageError::SizeMismatch { expected, actual }) => {
+                assert_eq!(*expected, (2, 1));
+                assert_eq!(*actual, (1, 1));
+            }
+            _ => panic!("Expected a size mismatch, got {:?}", result),
+        }
+
+        assert!(reference_path.with_extension("actual.png").exists());
+    }
+
+    #[test]
+    fn reference_mismatch_writes_diff() {
+        let dir = test_output_dir("reference_mismatch_writes_diff");
+        let reference_path = dir.join("reference.png");
+        write_png(&reference_path, &[0, 0, 0, 255], 1, 1).unwrap();
+
+        let result = check_against_reference(
+            &[255, 255, 255, 255],
+            1,
+            1,
+            &reference_path,
+            &GoldenImageOptions::default(),
+        );
+
+        match &result {
+            Err(GoldenImageError::Mismatch {
+                mismatched_pixels,
+                total_pixels,
+                diff_path,
+                ..
+            }) => {
+                assert_eq!(*mismatched_pixels, 1);
+                assert_eq!(*total_pixels, 1);
+
+                let diff = image::open(diff_path).unwrap().to_rgba8();
+                assert_eq!(diff.as_raw(), &vec![255, 0, 0, 255]);
+            }
+            _ => panic!("Expected a mismatch, got {:?}", result),
+        }
+    }
+
+    // The box's edges land on pixel boundaries, so the reference image has no antialiasing and
+    // doesn't depend on how skia rasterizes edges. draw_begin clears to a transparent color, so
+    // the rest of the frame reads back as zeros
+    #[test]
+    fn golden_box() {
+        let component_registry = ComponentRegistryBuilder::new()
+            .auto_register_components()
+            .add_spawn_mapping_into::<DrawSkiaBoxComponentDef, DrawSkiaBoxComponent>()
+            .add_spawn_mapping_into::<TransformComponentDef, TransformComponent>()
+            .build();
+
+        // The asset daemon is never connected to, the box doesn't use any assets
+        let loader = RpcLoader::new("127.0.0.1:9999".to_string()).unwrap();
+
+        let mut resources = Resources::default();
+        resources.insert(ComponentRegistryResource::new(component_registry));
+        resources.insert(TimeResource::new());
+        resources.insert(CanvasDrawResource::default());
+        resources.insert(AssetResource::new(loader));
+        resources.insert(CameraResource::new(glam::Vec2::zero(), 2.0));
+        resources.insert(ViewportResource::empty());
+        resources.insert(DrawLayersResource::new());
+
+        let mut update_schedule = Schedule::builder().build();
+        let mut draw_schedule = {
+            let mut builder = Schedule::builder();
+            crate::systems::draw_begin(&mut builder);
+            crate::systems::draw_skia_components(&mut builder);
+            builder.build()
+        };
+
+        let options = GoldenImageOptions {
+            width: 64,
+            height: 64,
+            frame_count: 1,
+            ..Default::default()
+        };
+
+        let test_data = Path::new(env!("CARGO_MANIFEST_DIR")).join("test_data/golden");
+        run_golden_image_test(
+            &mut World::default(),
+            &mut resources,
+            &mut update_schedule,
+            &mut draw_schedule,
+            test_data.join("box.prefab"),
+            test_data.join("box.png"),
+            &options,
+        )
+        .unwrap();
+    }
+}
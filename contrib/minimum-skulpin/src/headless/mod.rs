@@ -0,0 +1,11 @@
+mod renderer;
+pub use renderer::HeadlessRenderer;
+
+mod golden;
+pub use golden::compare_rgba8;
+pub use golden::load_prefab_without_daemon;
+pub use golden::run_golden_image_test;
+pub use golden::spawn_prefab;
+pub use golden::GoldenImageError;
+pub use golden::GoldenImageOptions;
+pub use golden::ImageComparison;
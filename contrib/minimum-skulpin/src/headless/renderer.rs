@@ -0,0 +1,128 @@
+use legion::*;
+use skulpin::skia_safe;
+use skulpin::CoordinateSystemHelper;
+use std::path::Path;
+
+use crate::resources::CanvasDrawResource;
+
+// Renders a draw schedule into a CPU (raster) skia surface instead of a vulkan swapchain. This
+// allows rendering scenes without a GPU or a window, i.e. on CI or for generating thumbnails.
+//
+// The draw schedule is run exactly as it would be with skulpin::Renderer, so the same systems can
+// be used as long as they get the viewport size from the CoordinateSystemHelper rather than the
+// window.
+pub struct HeadlessRenderer {
+    surface: skia_safe::Surface,
+    width: u32,
+    height: u32,
+}
+
+impl HeadlessRenderer {
+    pub fn new(
+        width: u32,
+        height: u32,
+    ) -> Option<Self> {
+        let surface = skia_safe::Surface::new_raster_n32_premul((width as i32, height as i32))?;
+
+        Some(HeadlessRenderer {
+            surface,
+            width,
+            height,
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    // There is no window, so logical and physical size are the same as the surface
+    pub fn coordinate_system_helper(&self) -> CoordinateSystemHelper {
+        CoordinateSystemHelper::new(
+            skulpin::ash::vk::Extent2D {
+                width: self.width,
+                height: self.height,
+            },
+            skulpin::LogicalSize {
+                width: self.width,
+                height: self.height,
+            },
+            skulpin::PhysicalSize {
+                width: self.width,
+                height: self.height,
+            },
+            1.0,
+        )
+    }
+
+    // Runs the draw schedule against the surface. CanvasDrawResource must be in resources
+    pub fn draw(
+        &mut self,
+        world: &mut World,
+        resources: &mut Resources,
+        draw_schedule: &mut Schedule,
+    ) {
+        let coordinate_system_helper = self.coordinate_system_helper();
+        let canvas = self.surface.canvas();
+
+        // Match skulpin::Renderer, which clears and resets the canvas before every frame
+        canvas.reset_matrix();
+        canvas.clear(skia_safe::Color::from_argb(0, 0, 0, 0));
+
+        resources
+            .get_mut::<CanvasDrawResource>()
+            .unwrap()
+            .begin_draw_context(canvas, coordinate_system_helper);
+
+        draw_schedule.execute(world, resources);
+
+        resources
+            .get_mut::<CanvasDrawResource>()
+            .unwrap()
+            .end_draw_context();
+
+        self.surface.flush();
+    }
+
+    // Returns the last drawn frame as tightly packed, unpremultiplied RGBA8 pixels
+    pub fn read_rgba8(&mut self) -> Option<Vec<u8>> {
+        let image_info = skia_safe::ImageInfo::new(
+            (self.width as i32, self.height as i32),
+            skia_safe::ColorType::RGBA8888,
+            skia_safe::AlphaType::Unpremul,
+            None,
+        );
+
+        let row_bytes = self.width as usize * 4;
+        let mut pixels = vec![0; row_bytes * self.height as usize];
+        if self
+            .surface
+            .read_pixels(&image_info, &mut pixels, row_bytes, (0, 0))
+        {
+            Some(pixels)
+        } else {
+            None
+        }
+    }
+
+    // Returns the last drawn frame encoded as a PNG
+    pub fn encode_png(&mut self) -> Option<Vec<u8>> {
+        let image = self.surface.image_snapshot();
+        let data = image.encode_to_data(skia_safe::EncodedImageFormat::PNG)?;
+        Some(data.as_bytes().to_vec())
+    }
+
+    pub fn write_png<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> std::io::Result<()> {
+        let png = self.encode_png().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::Other, "Failed to encode frame as PNG")
+        })?;
+
+        std::fs::write(path, png)
+    }
+}
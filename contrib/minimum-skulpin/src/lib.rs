@@ -2,4 +2,12 @@ pub mod components;
 
 pub mod resources;
 
+pub mod systems;
+
+pub mod pipeline;
+
 pub mod math_conversions;
+
+pub mod headless;
+
+pub mod viewport;
@@ -0,0 +1,66 @@
+use atelier_assets::loader::{
+    crossbeam_channel::Sender, handle::RefOp, AssetLoadOp, LoadHandle, LoaderInfoProvider,
+};
+use serde::{Deserialize, Serialize};
+use type_uuid::TypeUuid;
+use skulpin::skia_safe;
+use std::error::Error;
+
+use minimum::{DynAssetLoader, UpdateAssetResult};
+
+// The contents of a .ttf/.otf file. This is what gets stored by the asset daemon.
+#[derive(TypeUuid, Serialize, Deserialize, Clone)]
+#[uuid = "8f4c1a6e-2d9b-4c3f-b7a0-5e1d8c4a2f96"]
+pub struct FontAssetData {
+    pub data: Vec<u8>,
+}
+
+// Runtime font that can be used to draw text on a skia canvas. Created from FontAssetData by
+// FontAssetLoader
+#[derive(TypeUuid)]
+#[uuid = "d2b9e6f3-1a7c-4d5e-8f0b-3c6a9e2d7b14"]
+pub struct FontAsset {
+    pub typeface: skia_safe::Typeface,
+}
+
+unsafe impl Send for FontAsset {}
+unsafe impl Sync for FontAsset {}
+
+// Turns FontAssetData into a skia typeface. Register with
+// AssetResource::add_storage_with_loader::<FontAssetData, FontAsset, _>
+#[derive(Default)]
+pub struct FontAssetLoader;
+
+impl DynAssetLoader<FontAsset> for FontAssetLoader {
+    fn update_asset(
+        &mut self,
+        _refop_sender: &Sender<RefOp>,
+        _loader_info: &dyn LoaderInfoProvider,
+        data: &[u8],
+        _load_handle: LoadHandle,
+        load_op: AssetLoadOp,
+        _version: u32,
+    ) -> Result<UpdateAssetResult<FontAsset>, Box<dyn Error>> {
+        let font_data = bincode::deserialize::<FontAssetData>(data)?;
+
+        let typeface =
+            skia_safe::Typeface::from_data(skia_safe::Data::new_copy(&font_data.data), None)
+                .ok_or("Failed to create skia typeface from font data")?;
+
+        load_op.complete();
+        Ok(UpdateAssetResult::Result(FontAsset { typeface }))
+    }
+
+    fn commit_asset_version(
+        &mut self,
+        _handle: LoadHandle,
+        _version: u32,
+    ) {
+    }
+
+    fn free(
+        &mut self,
+        _handle: LoadHandle,
+    ) {
+    }
+}
@@ -0,0 +1,68 @@
+use atelier_assets::importer::{ImportedAsset, Importer, ImporterValue};
+use atelier_assets::core::AssetUuid;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use type_uuid::TypeUuid;
+
+use super::FontAssetData;
+
+use atelier_assets::importer as atelier_importer;
+
+#[derive(Default, Deserialize, Serialize, TypeUuid, Clone, Copy)]
+#[uuid = "b3e7a1c9-4d2f-4b8e-9c6a-1f5d3e8b0a72"]
+pub struct FontImporterOptions {}
+
+/// A simple state for Importer to retain the same UUID between imports
+/// for all single-asset source files
+#[derive(Default, Deserialize, Serialize, TypeUuid)]
+#[uuid = "6c0f2e8a-9b3d-4a7c-b5e1-4d8a2f6c0e39"]
+pub struct FontImporterState {
+    pub id: Option<AssetUuid>,
+}
+
+/// Stores a .ttf or .otf file as-is, it's parsed by skia when loaded
+#[derive(Default, TypeUuid)]
+#[uuid = "2a8d5f1b-7c4e-4e9a-a3b6-0e7c1d9f4b58"]
+pub struct FontImporter {}
+
+impl Importer for FontImporter {
+    type State = FontImporterState;
+    type Options = FontImporterOptions;
+
+    fn version_static() -> u32 {
+        1
+    }
+
+    fn version(&self) -> u32 {
+        Self::version_static()
+    }
+
+    fn import(
+        &self,
+        source: &mut dyn Read,
+        _: &Self::Options,
+        state: &mut Self::State,
+    ) -> atelier_importer::Result<ImporterValue> {
+        // Read in the data
+        let mut bytes = Vec::new();
+        source.read_to_end(&mut bytes)?;
+
+        let font_data = FontAssetData { data: bytes };
+
+        // Reuse the ID from previous imports so that components referencing the font stay valid
+        let id = *state
+            .id
+            .get_or_insert_with(|| AssetUuid(*uuid::Uuid::new_v4().as_bytes()));
+
+        Ok(ImporterValue {
+            assets: vec![ImportedAsset {
+                id,
+                search_tags: Vec::new(),
+                build_deps: Vec::new(),
+                load_deps: Vec::new(),
+                asset_data: Box::new(font_data),
+                build_pipeline: None,
+            }],
+        })
+    }
+}
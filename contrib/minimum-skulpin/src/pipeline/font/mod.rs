@@ -0,0 +1,7 @@
+mod importers;
+pub use importers::FontImporter;
+
+mod assets;
+pub use assets::FontAssetData;
+pub use assets::FontAsset;
+pub use assets::FontAssetLoader;
@@ -0,0 +1,89 @@
+use atelier_assets::loader::{
+    crossbeam_channel::Sender, handle::RefOp, AssetLoadOp, LoadHandle, LoaderInfoProvider,
+};
+use serde::{Deserialize, Serialize};
+use type_uuid::TypeUuid;
+use skulpin::skia_safe;
+use std::error::Error;
+
+use minimum::{DynAssetLoader, UpdateAssetResult};
+
+// Decoded pixels as produced by the importer. This is what gets stored by the asset daemon.
+#[derive(TypeUuid, Serialize, Deserialize, Clone)]
+#[uuid = "7a3b1e5d-9c2f-4d8a-b6e0-1f4c8d2a5e93"]
+pub struct ImageAssetData {
+    pub width: u32,
+    pub height: u32,
+    // RGBA8, not premultiplied, rows top to bottom
+    pub data: Vec<u8>,
+}
+
+// Runtime image that can be drawn on a skia canvas. Created from ImageAssetData by
+// ImageAssetLoader
+#[derive(TypeUuid)]
+#[uuid = "c1d6f2a8-4e7b-4b3c-9a5d-8e2f0b6c4a17"]
+pub struct ImageAsset {
+    pub image: skia_safe::Image,
+}
+
+unsafe impl Send for ImageAsset {}
+unsafe impl Sync for ImageAsset {}
+
+impl ImageAsset {
+    pub fn width(&self) -> u32 {
+        self.image.width() as u32
+    }
+
+    pub fn height(&self) -> u32 {
+        self.image.height() as u32
+    }
+}
+
+// Turns ImageAssetData into a skia image. Register with
+// AssetResource::add_storage_with_loader::<ImageAssetData, ImageAsset, _>
+#[derive(Default)]
+pub struct ImageAssetLoader;
+
+impl DynAssetLoader<ImageAsset> for ImageAssetLoader {
+    fn update_asset(
+        &mut self,
+        _refop_sender: &Sender<RefOp>,
+        _loader_info: &dyn LoaderInfoProvider,
+        data: &[u8],
+        _load_handle: LoadHandle,
+        load_op: AssetLoadOp,
+        _version: u32,
+    ) -> Result<UpdateAssetResult<ImageAsset>, Box<dyn Error>> {
+        let image_data = bincode::deserialize::<ImageAssetData>(data)?;
+
+        let image_info = skia_safe::ImageInfo::new(
+            (image_data.width as i32, image_data.height as i32),
+            skia_safe::ColorType::RGBA8888,
+            skia_safe::AlphaType::Unpremul,
+            None,
+        );
+
+        let image = skia_safe::Image::from_raster_data(
+            &image_info,
+            skia_safe::Data::new_copy(&image_data.data),
+            image_data.width as usize * 4,
+        )
+        .ok_or("Failed to create skia image from image data")?;
+
+        load_op.complete();
+        Ok(UpdateAssetResult::Result(ImageAsset { image }))
+    }
+
+    fn commit_asset_version(
+        &mut self,
+        _handle: LoadHandle,
+        _version: u32,
+    ) {
+    }
+
+    fn free(
+        &mut self,
+        _handle: LoadHandle,
+    ) {
+    }
+}
@@ -0,0 +1,77 @@
+use atelier_assets::importer::{ImportedAsset, Importer, ImporterValue};
+use atelier_assets::core::AssetUuid;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use type_uuid::TypeUuid;
+
+use super::ImageAssetData;
+
+use atelier_assets::importer as atelier_importer;
+
+#[derive(Default, Deserialize, Serialize, TypeUuid, Clone, Copy)]
+#[uuid = "4f8e2c6a-1b5d-4a9e-8c3f-7d0b2e6a1c54"]
+pub struct ImageImporterOptions {}
+
+/// A simple state for Importer to retain the same UUID between imports
+/// for all single-asset source files
+#[derive(Default, Deserialize, Serialize, TypeUuid)]
+#[uuid = "9b2d7f4e-3c1a-4e8b-a5d6-0f9c3b7e2a81"]
+pub struct ImageImporterState {
+    pub id: Option<AssetUuid>,
+}
+
+/// Decodes a .png or .jpg into RGBA8 ImageAssetData
+#[derive(Default, TypeUuid)]
+#[uuid = "e6a0c3f9-2d8b-4f1e-b7c4-5a9d1e3f6b28"]
+pub struct ImageImporter {}
+
+impl Importer for ImageImporter {
+    type State = ImageImporterState;
+    type Options = ImageImporterOptions;
+
+    fn version_static() -> u32 {
+        1
+    }
+
+    fn version(&self) -> u32 {
+        Self::version_static()
+    }
+
+    fn import(
+        &self,
+        source: &mut dyn Read,
+        _: &Self::Options,
+        state: &mut Self::State,
+    ) -> atelier_importer::Result<ImporterValue> {
+        // Read in the data
+        let mut bytes = Vec::new();
+        source.read_to_end(&mut bytes)?;
+
+        // The format is detected from the file's contents
+        let decoded = image::load_from_memory(&bytes)
+            .map_err(|e| atelier_importer::Error::Boxed(Box::new(e)))?
+            .to_rgba8();
+
+        let image_data = ImageAssetData {
+            width: decoded.width(),
+            height: decoded.height(),
+            data: decoded.into_raw(),
+        };
+
+        // Reuse the ID from previous imports so that handles to the image stay valid
+        let id = *state
+            .id
+            .get_or_insert_with(|| AssetUuid(*uuid::Uuid::new_v4().as_bytes()));
+
+        Ok(ImporterValue {
+            assets: vec![ImportedAsset {
+                id,
+                search_tags: Vec::new(),
+                build_deps: Vec::new(),
+                load_deps: Vec::new(),
+                asset_data: Box::new(image_data),
+                build_pipeline: None,
+            }],
+        })
+    }
+}
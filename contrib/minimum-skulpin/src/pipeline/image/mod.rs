@@ -0,0 +1,7 @@
+mod importers;
+pub use importers::ImageImporter;
+
+mod assets;
+pub use assets::ImageAssetData;
+pub use assets::ImageAsset;
+pub use assets::ImageAssetLoader;
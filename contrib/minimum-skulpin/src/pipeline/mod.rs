@@ -0,0 +1,21 @@
+mod image;
+pub use self::image::ImageAssetData;
+pub use self::image::ImageAsset;
+pub use self::image::ImageAssetLoader;
+pub use self::image::ImageImporter;
+
+mod sprite_animation;
+pub use sprite_animation::SpriteAnimationAsset;
+pub use sprite_animation::SpriteAnimationClip;
+pub use sprite_animation::SpriteAnimationFrame;
+pub use sprite_animation::SpriteAnimationLoopMode;
+pub use sprite_animation::SpriteAnimationImporter;
+
+mod font;
+pub use font::FontAssetData;
+pub use font::FontAsset;
+pub use font::FontAssetLoader;
+pub use font::FontImporter;
+
+mod tiled;
+pub use self::tiled::TiledImporter;
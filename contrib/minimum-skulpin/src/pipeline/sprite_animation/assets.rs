@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+use type_uuid::TypeUuid;
+use minimum::math::Vec4;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum SpriteAnimationLoopMode {
+    // Play through once and hold the last frame
+    Once,
+    // Start over from the first frame after the last one
+    Loop,
+    // Play forward, then backward, then forward again
+    PingPong,
+}
+
+impl Default for SpriteAnimationLoopMode {
+    fn default() -> Self {
+        SpriteAnimationLoopMode::Loop
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SpriteAnimationFrame {
+    // x, y, width, height in pixels within the sprite sheet
+    pub sub_rect: Vec4,
+    // How long the frame is shown, in seconds
+    pub duration: f32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SpriteAnimationClip {
+    pub name: String,
+    pub frames: Vec<SpriteAnimationFrame>,
+    #[serde(default)]
+    pub loop_mode: SpriteAnimationLoopMode,
+}
+
+impl SpriteAnimationClip {
+    pub fn duration(&self) -> f32 {
+        self.frames.iter().map(|frame| frame.duration).sum()
+    }
+
+    // Returns the frame that should be shown the given number of seconds after the clip started.
+    // Negative times wrap backwards from the end of the clip when looping.
+    pub fn frame_at(
+        &self,
+        time: f32,
+    ) -> Option<&SpriteAnimationFrame> {
+        let duration = self.duration();
+        if self.frames.is_empty() || duration <= 0.0 {
+            return self.frames.first();
+        }
+
+        let time = match self.loop_mode {
+            SpriteAnimationLoopMode::Once => time.max(0.0).min(duration),
+            SpriteAnimationLoopMode::Loop => time.rem_euclid(duration),
+            SpriteAnimationLoopMode::PingPong => {
+                let time = time.rem_euclid(duration * 2.0);
+                if time > duration {
+                    duration * 2.0 - time
+                } else {
+                    time
+                }
+            }
+        };
+
+        let mut frame_end = 0.0;
+        for frame in &self.frames {
+            frame_end += frame.duration;
+            if time < frame_end {
+                return Some(frame);
+            }
+        }
+
+        self.frames.last()
+    }
+}
+
+// A set of named clips that can be played on a sprite. The frames index into whatever image the
+// sprite component is using.
+#[derive(TypeUuid, Serialize, Deserialize, Debug, Clone)]
+#[uuid = "a4e9c7b2-6d1f-4a3e-8b5c-2f0d9e7a1c36"]
+pub struct SpriteAnimationAsset {
+    pub clips: Vec<SpriteAnimationClip>,
+}
+
+impl SpriteAnimationAsset {
+    pub fn clip(
+        &self,
+        name: &str,
+    ) -> Option<&SpriteAnimationClip> {
+        self.clips.iter().find(|clip| clip.name == name)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn clip(loop_mode: SpriteAnimationLoopMode) -> SpriteAnimationClip {
+        let frame = |x| SpriteAnimationFrame {
+            sub_rect: Vec4::new(x, 0.0, 16.0, 16.0),
+            duration: 0.5,
+        };
+
+        SpriteAnimationClip {
+            name: "walk".to_string(),
+            frames: vec![frame(0.0), frame(16.0)],
+            loop_mode,
+        }
+    }
+
+    fn frame_x(
+        clip: &SpriteAnimationClip,
+        time: f32,
+    ) -> f32 {
+        clip.frame_at(time).unwrap().sub_rect.x()
+    }
+
+    #[test]
+    fn negative_time_wraps_from_the_end() {
+        let looping = clip(SpriteAnimationLoopMode::Loop);
+        assert_eq!(frame_x(&looping, 0.25), 0.0);
+        assert_eq!(frame_x(&looping, 1.25), 0.0);
+        assert_eq!(frame_x(&looping, -0.25), 16.0);
+        assert_eq!(frame_x(&looping, -0.75), 0.0);
+
+        let ping_pong = clip(SpriteAnimationLoopMode::PingPong);
+        assert_eq!(frame_x(&ping_pong, -0.25), 0.0);
+        assert_eq!(frame_x(&ping_pong, -0.75), 16.0);
+
+        let once = clip(SpriteAnimationLoopMode::Once);
+        assert_eq!(frame_x(&once, -1.0), 0.0);
+        assert_eq!(frame_x(&once, 2.0), 16.0);
+    }
+}
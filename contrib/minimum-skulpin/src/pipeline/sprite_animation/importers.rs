@@ -0,0 +1,84 @@
+use atelier_assets::importer::{ImportedAsset, Importer, ImporterValue};
+use atelier_assets::core::AssetUuid;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use type_uuid::TypeUuid;
+
+use super::SpriteAnimationAsset;
+
+use atelier_assets::importer as atelier_importer;
+
+#[derive(Default, Deserialize, Serialize, TypeUuid, Clone, Copy)]
+#[uuid = "3b7f1d9c-5e2a-4c8f-a0d6-9e4b2c7f1a85"]
+pub struct SpriteAnimationImporterOptions {}
+
+/// A simple state for Importer to retain the same UUID between imports
+/// for all single-asset source files
+#[derive(Default, Deserialize, Serialize, TypeUuid)]
+#[uuid = "d8c2a6f0-7b4e-4d1a-9f3c-6a0e8b5d2c47"]
+pub struct SpriteAnimationImporterState {
+    pub id: Option<AssetUuid>,
+}
+
+/// Reads a SpriteAnimationAsset from a RON file, for example:
+///
+/// ```ron
+/// (
+///     clips: [
+///         (
+///             name: "walk",
+///             loop_mode: Loop,
+///             frames: [
+///                 (sub_rect: Vec4(0, 0, 32, 32), duration: 0.1),
+///                 (sub_rect: Vec4(32, 0, 32, 32), duration: 0.1),
+///             ],
+///         ),
+///     ],
+/// )
+/// ```
+#[derive(Default, TypeUuid)]
+#[uuid = "7e0a4c2f-9d6b-4f3e-b1a8-5c2d0f7e9b63"]
+pub struct SpriteAnimationImporter {}
+
+impl Importer for SpriteAnimationImporter {
+    type State = SpriteAnimationImporterState;
+    type Options = SpriteAnimationImporterOptions;
+
+    fn version_static() -> u32 {
+        1
+    }
+
+    fn version(&self) -> u32 {
+        Self::version_static()
+    }
+
+    fn import(
+        &self,
+        source: &mut dyn Read,
+        _: &Self::Options,
+        state: &mut Self::State,
+    ) -> atelier_importer::Result<ImporterValue> {
+        // Read in the data
+        let mut bytes = Vec::new();
+        source.read_to_end(&mut bytes)?;
+
+        let animation_asset = ron::de::from_bytes::<SpriteAnimationAsset>(&bytes)?;
+
+        // Reuse the ID from previous imports so that components referencing the animation stay
+        // valid
+        let id = *state
+            .id
+            .get_or_insert_with(|| AssetUuid(*uuid::Uuid::new_v4().as_bytes()));
+
+        Ok(ImporterValue {
+            assets: vec![ImportedAsset {
+                id,
+                search_tags: Vec::new(),
+                build_deps: Vec::new(),
+                load_deps: Vec::new(),
+                asset_data: Box::new(animation_asset),
+                build_pipeline: None,
+            }],
+        })
+    }
+}
@@ -0,0 +1,8 @@
+mod importers;
+pub use importers::SpriteAnimationImporter;
+
+mod assets;
+pub use assets::SpriteAnimationAsset;
+pub use assets::SpriteAnimationClip;
+pub use assets::SpriteAnimationFrame;
+pub use assets::SpriteAnimationLoopMode;
@@ -0,0 +1,372 @@
+use atelier_assets::importer::{ImportedAsset, Importer, ImporterValue};
+use atelier_assets::core::AssetUuid;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use type_uuid::TypeUuid;
+
+use minimum::pipeline::{component_json, PrefabDocumentBuilder};
+use minimum::components::{TransformComponentDef, EditorMetadataComponent};
+use crate::components::{
+    TilemapComponentDef, TilesetLayout, DrawSkiaBoxComponentDef, DrawSkiaCircleComponentDef,
+    PaintDef, PaintStyle, DrawLayerComponentDef,
+};
+#[cfg(feature = "tiled_rigid_bodies")]
+use minimum_nphysics2d::components::{RigidBodyBoxComponentDef, RigidBodyBallComponentDef};
+
+use std::collections::HashMap;
+
+use atelier_assets::importer as atelier_importer;
+
+// Tile layers are placed on this draw layer so that they are behind objects on the default layer
+const TILE_LAYER_DRAW_LAYER: i32 = -1;
+
+#[derive(Deserialize, Serialize, TypeUuid, Clone, Copy)]
+#[uuid = "2c9e4f0a-7b1d-4e5c-8a3f-9d6b0e1c2a47"]
+pub struct TiledImporterOptions {
+    /// Tiled positions are in pixels, this converts them to world units
+    pub pixels_per_unit: f32,
+}
+
+impl Default for TiledImporterOptions {
+    fn default() -> Self {
+        TiledImporterOptions {
+            pixels_per_unit: 32.0,
+        }
+    }
+}
+
+/// Retains the prefab UUID and the UUID of each entity between imports so that saving the map in
+/// Tiled hot-reloads the existing prefab rather than replacing it.
+#[derive(Default, Deserialize, Serialize, TypeUuid)]
+#[uuid = "8e1a5d3c-0f6b-4c2e-b7a9-4d0c3e5f1b68"]
+pub struct TiledImporterState {
+    pub id: Option<AssetUuid>,
+    // Keyed by "layer:<layer name>" or "object:<tiled object id>"
+    pub entity_ids: HashMap<String, AssetUuid>,
+}
+
+/// Imports a Tiled .tmx map as a prefab
+/// - Each tile layer becomes an entity with a `TilemapComponentDef` per tileset it uses. The map's
+///   top-left corner is placed at the origin. Tiles are drawn from the image asset whose UUID is in
+///   the tileset's `image` string property (copy it from the image's .meta file). Tilesets without
+///   it are drawn as blocks.
+/// - Each rectangle/ellipse in an object layer becomes an entity with a box/ball draw component
+///   component. With the `tiled_rigid_bodies` feature it also gets a rigid body, which is static
+///   unless the object has an `is_static` property set to false.
+#[derive(Default, TypeUuid)]
+#[uuid = "f4b7c2d9-1e3a-4a6f-9c8b-5d2e0a7f3c16"]
+pub struct TiledImporter {}
+
+impl Importer for TiledImporter {
+    type State = TiledImporterState;
+    type Options = TiledImporterOptions;
+
+    fn version_static() -> u32 {
+        3
+    }
+
+    fn version(&self) -> u32 {
+        Self::version_static()
+    }
+
+    fn import(
+        &self,
+        source: &mut dyn Read,
+        options: &Self::Options,
+        state: &mut Self::State,
+    ) -> atelier_importer::Result<ImporterValue> {
+        ///////////////////////////////////////////////////////////////
+        // STEP 1: Read in the data
+        ///////////////////////////////////////////////////////////////
+
+        let map = tiled::parse(source).map_err(|e| atelier_importer::Error::Boxed(Box::new(e)))?;
+
+        // Reuse the prefab ID from previous imports if we have one
+        let prefab_id = *state
+            .id
+            .get_or_insert_with(|| AssetUuid(*uuid::Uuid::new_v4().as_bytes()));
+
+        ///////////////////////////////////////////////////////////////
+        // STEP 2: Convert layers and objects into entities
+        ///////////////////////////////////////////////////////////////
+
+        let mut builder = PrefabDocumentBuilder::new(&state.entity_ids);
+        let pixels_per_unit = options.pixels_per_unit;
+        let tile_size = glam::Vec2::new(
+            map.tile_width as f32 / pixels_per_unit,
+            map.tile_height as f32 / pixels_per_unit,
+        );
+
+        // Tile layers are drawn in the order they appear in the map
+        for (layer_index, layer) in map.layers.iter().enumerate() {
+            let tiles: Vec<u32> = match &layer.tiles {
+                tiled::LayerData::Finite(rows) => rows
+                    .iter()
+                    .flat_map(|row| row.iter().map(|tile| tile.gid))
+                    .collect(),
+                tiled::LayerData::Infinite(_) => {
+                    log::warn!(
+                        "Skipping tile layer {}, infinite maps are not supported",
+                        layer.name
+                    );
+                    continue;
+                }
+            };
+
+            let alpha = if layer.visible { layer.opacity } else { 0.0 };
+            let paint = PaintDef {
+                color: glam::Vec4::new(1.0, 1.0, 1.0, alpha).into(),
+                stroke_width: 0.0,
+                style: PaintStyle::Fill,
+            };
+
+            // A tilemap can only draw from one image, so split the layer by tileset
+            let mut tilesets_used: Vec<&tiled::Tileset> = Vec::new();
+            for gid in &tiles {
+                if let Some(tileset) = tileset_for_gid(&map.tilesets, *gid) {
+                    if !tilesets_used
+                        .iter()
+                        .any(|used| used.first_gid == tileset.first_gid)
+                    {
+                        tilesets_used.push(tileset);
+                    }
+                }
+            }
+
+            for tileset in &tilesets_used {
+                let tileset_tiles = tiles
+                    .iter()
+                    .map(|gid| match tileset_for_gid(&map.tilesets, *gid) {
+                        Some(t) if t.first_gid == tileset.first_gid => *gid,
+                        _ => 0,
+                    })
+                    .collect();
+
+                let tilemap = TilemapComponentDef {
+                    width: map.width,
+                    height: map.height,
+                    tile_size: tile_size.into(),
+                    tiles: tileset_tiles,
+                    paint,
+                    tileset_image: tileset_image(tileset),
+                    tileset: tileset_layout(tileset),
+                };
+
+                // Keep the key of layers that use a single tileset stable
+                let key = if tilesets_used.len() == 1 {
+                    format!("layer:{}", layer.name)
+                } else {
+                    format!("layer:{}:{}", layer.name, tileset.name)
+                };
+
+                builder.add_entity(
+                    key,
+                    vec![
+                        component_json(&TransformComponentDef::default()),
+                        component_json(&EditorMetadataComponent {
+                            name: layer.name.clone(),
+                        }),
+                        component_json(&tilemap),
+                        component_json(&DrawLayerComponentDef {
+                            layer: TILE_LAYER_DRAW_LAYER,
+                            z_order: layer_index as f32,
+                        }),
+                    ],
+                );
+            }
+        }
+
+        for object_group in &map.object_groups {
+            let color = object_group
+                .colour
+                .map(|c| {
+                    glam::Vec4::new(
+                        c.red as f32 / 255.0,
+                        c.green as f32 / 255.0,
+                        c.blue as f32 / 255.0,
+                        1.0,
+                    )
+                })
+                .unwrap_or_else(|| glam::Vec4::new(0.6, 0.6, 0.6, 1.0));
+
+            let paint = PaintDef {
+                color: color.into(),
+                stroke_width: 0.02,
+                style: PaintStyle::Stroke,
+            };
+
+            for object in &object_group.objects {
+                builder.add_entity(
+                    format!("object:{}", object.id),
+                    object_components(object, paint, pixels_per_unit),
+                );
+            }
+        }
+
+        ///////////////////////////////////////////////////////////////
+        // STEP 3: Deserialize the entities into a prefab
+        ///////////////////////////////////////////////////////////////
+
+        let (prefab_asset, entity_ids) = builder.build(prefab_id)?;
+
+        // Drop IDs for layers/objects that no longer exist
+        state.entity_ids = entity_ids;
+
+        Ok(ImporterValue {
+            assets: vec![ImportedAsset {
+                id: prefab_id,
+                search_tags: Vec::new(),
+                build_deps: Vec::new(),
+                load_deps: Vec::new(),
+                asset_data: Box::new(prefab_asset),
+                build_pipeline: None,
+            }],
+        })
+    }
+}
+
+// Tilesets are sorted by first_gid, each one covers the IDs up to the next one's first_gid
+fn tileset_for_gid(
+    tilesets: &[tiled::Tileset],
+    gid: u32,
+) -> Option<&tiled::Tileset> {
+    if gid == 0 {
+        return None;
+    }
+
+    tilesets
+        .iter()
+        .rev()
+        .find(|tileset| tileset.first_gid <= gid)
+}
+
+fn tileset_image(tileset: &tiled::Tileset) -> AssetUuid {
+    let uuid = match tileset.properties.get("image") {
+        Some(tiled::PropertyValue::StringValue(uuid)) => uuid::Uuid::parse_str(uuid).ok(),
+        _ => None,
+    };
+
+    match uuid {
+        Some(uuid) => AssetUuid(*uuid.as_bytes()),
+        None => {
+            log::warn!(
+                "Tileset {} has no valid image property, its tiles will be drawn as blocks",
+                tileset.name
+            );
+            AssetUuid::default()
+        }
+    }
+}
+
+fn tileset_layout(tileset: &tiled::Tileset) -> TilesetLayout {
+    // Tiled fits as many tiles as it can across the image
+    let columns = tileset
+        .images
+        .first()
+        .map(|image| {
+            let usable_width =
+                (image.width.max(0) as u32 + tileset.spacing).saturating_sub(tileset.margin * 2);
+            usable_width / (tileset.tile_width + tileset.spacing).max(1)
+        })
+        .unwrap_or(0);
+
+    TilesetLayout {
+        first_gid: tileset.first_gid,
+        columns,
+        tile_size: glam::Vec2::new(tileset.tile_width as f32, tileset.tile_height as f32).into(),
+        margin: tileset.margin,
+        spacing: tileset.spacing,
+    }
+}
+
+// Produces the components for a single object in an object layer
+fn object_components(
+    object: &tiled::Object,
+    paint: PaintDef,
+    pixels_per_unit: f32,
+) -> Vec<serde_json::Value> {
+    let name = if object.name.is_empty() {
+        format!("Object {}", object.id)
+    } else {
+        object.name.clone()
+    };
+
+    let (width, height) = match object.shape {
+        tiled::ObjectShape::Rect { width, height }
+        | tiled::ObjectShape::Ellipse { width, height } => (width, height),
+        _ => (0.0, 0.0),
+    };
+
+    // Tiled positions are the top-left corner (bottom-left for tile objects) in pixels with +Y
+    // down, and objects rotate clockwise around that corner. Find the center in world space.
+    let top = if object.gid != 0 {
+        object.y - height
+    } else {
+        object.y
+    };
+    let rotation = object.rotation.to_radians();
+    let center_x = object.x + rotation.cos() * width * 0.5 - rotation.sin() * height * 0.5;
+    let center_y = top + rotation.sin() * width * 0.5 + rotation.cos() * height * 0.5;
+
+    let transform = TransformComponentDef {
+        position: glam::Vec3::new(center_x / pixels_per_unit, -center_y / pixels_per_unit, 0.0)
+            .into(),
+        rotation: glam::Vec3::new(0.0, 0.0, -rotation).into(),
+        ..Default::default()
+    };
+
+    let mut components = vec![
+        component_json(&transform),
+        component_json(&EditorMetadataComponent { name }),
+    ];
+
+    let half_extents = glam::Vec3::new(width * 0.5, height * 0.5, 0.0) / pixels_per_unit;
+    match object.shape {
+        tiled::ObjectShape::Rect { .. } => {
+            components.push(component_json(&DrawSkiaBoxComponentDef {
+                half_extents: half_extents.into(),
+                paint,
+            }));
+        }
+        tiled::ObjectShape::Ellipse { .. } => {
+            let radius = half_extents.x().max(half_extents.y());
+            components.push(component_json(&DrawSkiaCircleComponentDef {
+                radius,
+                paint,
+            }));
+        }
+        _ => log::debug!(
+            "Tiled object {} is not a rectangle or ellipse, it will only have a transform",
+            object.id
+        ),
+    }
+
+    #[cfg(feature = "tiled_rigid_bodies")]
+    components.extend(rigid_body_component(object, half_extents));
+
+    components
+}
+
+// Rectangles and ellipses get a matching rigid body
+#[cfg(feature = "tiled_rigid_bodies")]
+fn rigid_body_component(
+    object: &tiled::Object,
+    half_extents: glam::Vec3,
+) -> Option<serde_json::Value> {
+    let is_static = match object.properties.get("is_static") {
+        Some(tiled::PropertyValue::BoolValue(is_static)) => *is_static,
+        _ => true,
+    };
+
+    match object.shape {
+        tiled::ObjectShape::Rect { .. } => Some(component_json(&RigidBodyBoxComponentDef {
+            half_extents: half_extents.into(),
+            is_static,
+        })),
+        tiled::ObjectShape::Ellipse { .. } => Some(component_json(&RigidBodyBallComponentDef {
+            radius: half_extents.x().max(half_extents.y()),
+            is_static,
+        })),
+        _ => None,
+    }
+}
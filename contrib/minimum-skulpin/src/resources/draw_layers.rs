@@ -0,0 +1,88 @@
+// Settings for a layer referenced by DrawLayerComponent
+pub struct DrawLayer {
+    pub layer: i32,
+    pub name: String,
+    pub visible: bool,
+    // How far the layer scrolls when the camera's position changes. (1, 1) moves with the world,
+    // values less than 1 make the layer appear further away and (0, 0) keeps it fixed on screen.
+    // Only the camera's X/Y position is used, its rotation, height and zoom apply to every layer
+    pub parallax: glam::Vec2,
+}
+
+// Holds the named layers that skia draw components can be placed on. Layers that have not been
+// added are visible and have no parallax
+#[derive(Default)]
+pub struct DrawLayersResource {
+    layers: Vec<DrawLayer>,
+}
+
+impl DrawLayersResource {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    // Adds a layer, or replaces the settings of a layer that was already added
+    pub fn add_layer(
+        &mut self,
+        layer: i32,
+        name: &str,
+        parallax: glam::Vec2,
+    ) {
+        let draw_layer = DrawLayer {
+            layer,
+            name: name.to_string(),
+            visible: true,
+            parallax,
+        };
+
+        match self.layers.binary_search_by_key(&layer, |x| x.layer) {
+            Ok(index) => self.layers[index] = draw_layer,
+            Err(index) => self.layers.insert(index, draw_layer),
+        }
+    }
+
+    // All added layers, sorted by draw order
+    pub fn layers(&self) -> &[DrawLayer] {
+        &self.layers
+    }
+
+    pub fn layers_mut(&mut self) -> &mut [DrawLayer] {
+        &mut self.layers
+    }
+
+    pub fn layer(
+        &self,
+        layer: i32,
+    ) -> Option<&DrawLayer> {
+        self.layers
+            .binary_search_by_key(&layer, |x| x.layer)
+            .ok()
+            .map(|index| &self.layers[index])
+    }
+
+    pub fn is_visible(
+        &self,
+        layer: i32,
+    ) -> bool {
+        self.layer(layer).map(|x| x.visible).unwrap_or(true)
+    }
+
+    pub fn set_visible(
+        &mut self,
+        layer: i32,
+        visible: bool,
+    ) {
+        if let Ok(index) = self.layers.binary_search_by_key(&layer, |x| x.layer) {
+            self.layers[index].visible = visible;
+        }
+    }
+
+    pub fn parallax(
+        &self,
+        layer: i32,
+    ) -> glam::Vec2 {
+        self.layer(layer)
+            .map(|x| x.parallax)
+            .unwrap_or_else(|| glam::Vec2::new(1.0, 1.0))
+    }
+}
@@ -1,2 +1,6 @@
 mod canvas_draw;
 pub use canvas_draw::CanvasDrawResource;
+
+mod draw_layers;
+pub use draw_layers::DrawLayer;
+pub use draw_layers::DrawLayersResource;
@@ -0,0 +1,408 @@
+use legion::*;
+
+use imgui::im_str;
+
+use minimum::components::TransformComponent;
+use minimum::resources::{AssetResource, CameraResource, ImguiResource, ViewportResource};
+use skulpin::skia_safe;
+use minimum::resources::editor::EditorStateResource;
+
+use crate::components::{
+    DrawLayerComponent, DrawSkiaBoxComponent, DrawSkiaCapsuleComponent, DrawSkiaCircleComponent,
+    DrawSkiaPolygonComponent, DrawSkiaPolylineComponent, DrawSkiaRoundedRectComponent,
+    DrawSkiaSpriteComponent, DrawSkiaTextComponent, ParticleEmitterComponent, TilemapComponent,
+};
+use crate::resources::{CanvasDrawResource, DrawLayersResource};
+
+enum DrawCommand<'a> {
+    Box(&'a DrawSkiaBoxComponent),
+    Circle(&'a DrawSkiaCircleComponent),
+    Tilemap(&'a TilemapComponent),
+    Sprite(&'a DrawSkiaSpriteComponent),
+    Text(&'a DrawSkiaTextComponent),
+    Polygon(&'a DrawSkiaPolygonComponent),
+    Polyline(&'a DrawSkiaPolylineComponent),
+    Capsule(&'a DrawSkiaCapsuleComponent),
+    RoundedRect(&'a DrawSkiaRoundedRectComponent),
+    Particles(&'a ParticleEmitterComponent),
+}
+
+struct DrawItem<'a> {
+    layer: i32,
+    z_order: f32,
+    entity: Entity,
+    transform: &'a TransformComponent,
+    command: DrawCommand<'a>,
+}
+
+impl<'a> DrawItem<'a> {
+    fn new(
+        entity: Entity,
+        transform: &'a TransformComponent,
+        draw_layer: Option<&DrawLayerComponent>,
+        command: DrawCommand<'a>,
+    ) -> Self {
+        let draw_layer = draw_layer.copied().unwrap_or_default();
+        DrawItem {
+            layer: draw_layer.layer,
+            z_order: draw_layer.z_order,
+            entity,
+            transform,
+            command,
+        }
+    }
+
+    fn draw(
+        &self,
+        canvas: &mut skulpin::skia_safe::Canvas,
+        asset_resource: &AssetResource,
+    ) {
+        let transform = self.transform;
+        match self.command {
+            DrawCommand::Box(x) => x.draw(canvas, transform),
+            DrawCommand::Circle(x) => x.draw(canvas, transform),
+            DrawCommand::Tilemap(x) => x.draw(canvas, transform, asset_resource),
+            DrawCommand::Sprite(x) => x.draw(canvas, transform, asset_resource),
+            DrawCommand::Text(x) => x.draw(canvas, transform, asset_resource),
+            DrawCommand::Polygon(x) => x.draw(canvas, transform),
+            DrawCommand::Polyline(x) => x.draw(canvas, transform),
+            DrawCommand::Capsule(x) => x.draw(canvas, transform),
+            DrawCommand::RoundedRect(x) => x.draw(canvas, transform),
+            DrawCommand::Particles(x) => x.draw(canvas),
+        }
+    }
+}
+
+// Sets up the canvas to draw in world space and clears it. Skia draw components are drawn after
+// this by draw_skia_components
+pub fn draw_begin(schedule: &mut legion::systems::Builder) {
+    schedule.add_system(
+        SystemBuilder::new("draw_begin")
+            .write_resource::<CanvasDrawResource>()
+            .read_resource::<CameraResource>()
+            .write_resource::<ViewportResource>()
+            .build(|_, _, (draw_context, camera_state, viewport_state), _| {
+                draw_context.with_canvas(|canvas, coordinate_system_helper| {
+                    // Use the size of the surface being drawn to rather than the window so
+                    // that this also works when rendering headless
+                    let physical_size = coordinate_system_helper.window_physical_size();
+
+                    let viewport_size_in_pixels =
+                        glam::Vec2::new(physical_size.width as f32, physical_size.height as f32);
+                    crate::viewport::update_viewport(
+                        viewport_state,
+                        viewport_size_in_pixels,
+                        camera_state,
+                    );
+
+                    // Draw world space through the camera, so that its rotation and projection
+                    // apply to everything drawn on the canvas
+                    coordinate_system_helper.use_physical_coordinates(canvas);
+                    canvas.concat(&world_space_canvas_matrix(&*viewport_state));
+
+                    // Generally would want to clear data every time we draw
+                    canvas.clear(skia_safe::Color::from_argb(0, 0, 0, 255));
+                });
+            }),
+    );
+}
+
+// Maps the z=0 plane of world space to pixels. Everything drawn on the canvas lies on that plane,
+// so this 3x3 matrix is enough for skia to rotate and project it like the 4x4 camera matrices do
+fn world_space_canvas_matrix(viewport: &ViewportResource) -> skia_safe::Matrix {
+    let half_size_in_pixels = viewport.viewport_size_in_pixels() * 0.5;
+    let normalized_to_pixels = glam::Mat4::from_translation(half_size_in_pixels.extend(0.0))
+        * glam::Mat4::from_scale(half_size_in_pixels.extend(1.0));
+    let matrix = normalized_to_pixels * viewport.world_space_view_proj_matrix();
+
+    // Skip the z row and column, since z is always 0 on the canvas
+    let x_axis = matrix.x_axis();
+    let y_axis = matrix.y_axis();
+    let w_axis = matrix.w_axis();
+    skia_safe::Matrix::new_all(
+        x_axis.x(),
+        y_axis.x(),
+        w_axis.x(),
+        x_axis.y(),
+        y_axis.y(),
+        w_axis.y(),
+        x_axis.w(),
+        y_axis.w(),
+        w_axis.w(),
+    )
+}
+
+// Draws all skia draw components in world space, sorted by layer and then z_order. Ties are
+// broken by entity so that the order doesn't change when entities move between archetypes. The
+// canvas must already be set up to draw in world space. Screen space text is not drawn.
+pub fn draw_skia_components(schedule: &mut legion::systems::Builder) {
+    schedule.add_system(
+        SystemBuilder::new("draw_skia_components")
+            .write_resource::<CanvasDrawResource>()
+            .read_resource::<AssetResource>()
+            .read_resource::<CameraResource>()
+            .read_resource::<DrawLayersResource>()
+            .with_query(<(
+                Entity,
+                Read<TransformComponent>,
+                Read<DrawSkiaBoxComponent>,
+                TryRead<DrawLayerComponent>,
+            )>::query())
+            .with_query(<(
+                Entity,
+                Read<TransformComponent>,
+                Read<DrawSkiaCircleComponent>,
+                TryRead<DrawLayerComponent>,
+            )>::query())
+            .with_query(<(
+                Entity,
+                Read<TransformComponent>,
+                Read<TilemapComponent>,
+                TryRead<DrawLayerComponent>,
+            )>::query())
+            .with_query(<(
+                Entity,
+                Read<TransformComponent>,
+                Read<DrawSkiaSpriteComponent>,
+                TryRead<DrawLayerComponent>,
+            )>::query())
+            .with_query(<(
+                Entity,
+                Read<TransformComponent>,
+                Read<DrawSkiaTextComponent>,
+                TryRead<DrawLayerComponent>,
+            )>::query())
+            .with_query(<(
+                Entity,
+                Read<TransformComponent>,
+                Read<DrawSkiaPolygonComponent>,
+                TryRead<DrawLayerComponent>,
+            )>::query())
+            .with_query(<(
+                Entity,
+                Read<TransformComponent>,
+                Read<DrawSkiaPolylineComponent>,
+                TryRead<DrawLayerComponent>,
+            )>::query())
+            .with_query(<(
+                Entity,
+                Read<TransformComponent>,
+                Read<DrawSkiaCapsuleComponent>,
+                TryRead<DrawLayerComponent>,
+            )>::query())
+            .with_query(<(
+                Entity,
+                Read<TransformComponent>,
+                Read<DrawSkiaRoundedRectComponent>,
+                TryRead<DrawLayerComponent>,
+            )>::query())
+            .with_query(<(
+                Entity,
+                Read<TransformComponent>,
+                Read<ParticleEmitterComponent>,
+                TryRead<DrawLayerComponent>,
+            )>::query())
+            .build(
+                |_,
+                 world,
+                 (draw_context, asset_resource, camera, draw_layers),
+                 (
+                    boxes_query,
+                    circles_query,
+                    tilemaps_query,
+                    sprites_query,
+                    text_query,
+                    polygons_query,
+                    polylines_query,
+                    capsules_query,
+                    rounded_rects_query,
+                    particle_emitters_query,
+                )| {
+                    let mut items = Vec::new();
+
+                    for (entity, transform, x, layer) in boxes_query.iter(world) {
+                        items.push(DrawItem::new(
+                            *entity,
+                            transform,
+                            layer,
+                            DrawCommand::Box(x),
+                        ));
+                    }
+
+                    for (entity, transform, x, layer) in circles_query.iter(world) {
+                        items.push(DrawItem::new(
+                            *entity,
+                            transform,
+                            layer,
+                            DrawCommand::Circle(x),
+                        ));
+                    }
+
+                    for (entity, transform, x, layer) in tilemaps_query.iter(world) {
+                        items.push(DrawItem::new(
+                            *entity,
+                            transform,
+                            layer,
+                            DrawCommand::Tilemap(x),
+                        ));
+                    }
+
+                    for (entity, transform, x, layer) in sprites_query.iter(world) {
+                        items.push(DrawItem::new(
+                            *entity,
+                            transform,
+                            layer,
+                            DrawCommand::Sprite(x),
+                        ));
+                    }
+
+                    for (entity, transform, x, layer) in text_query.iter(world) {
+                        if !x.screen_space {
+                            items.push(DrawItem::new(
+                                *entity,
+                                transform,
+                                layer,
+                                DrawCommand::Text(x),
+                            ));
+                        }
+                    }
+
+                    for (entity, transform, x, layer) in polygons_query.iter(world) {
+                        items.push(DrawItem::new(
+                            *entity,
+                            transform,
+                            layer,
+                            DrawCommand::Polygon(x),
+                        ));
+                    }
+
+                    for (entity, transform, x, layer) in polylines_query.iter(world) {
+                        items.push(DrawItem::new(
+                            *entity,
+                            transform,
+                            layer,
+                            DrawCommand::Polyline(x),
+                        ));
+                    }
+
+                    for (entity, transform, x, layer) in capsules_query.iter(world) {
+                        items.push(DrawItem::new(
+                            *entity,
+                            transform,
+                            layer,
+                            DrawCommand::Capsule(x),
+                        ));
+                    }
+
+                    for (entity, transform, x, layer) in rounded_rects_query.iter(world) {
+                        items.push(DrawItem::new(
+                            *entity,
+                            transform,
+                            layer,
+                            DrawCommand::RoundedRect(x),
+                        ));
+                    }
+
+                    for (entity, transform, x, layer) in particle_emitters_query.iter(world) {
+                        items.push(DrawItem::new(
+                            *entity,
+                            transform,
+                            layer,
+                            DrawCommand::Particles(x),
+                        ));
+                    }
+
+                    // The sort is stable, so multiple components on the same entity keep the order
+                    // they were gathered in above
+                    items.sort_by(|a, b| {
+                        a.layer
+                            .cmp(&b.layer)
+                            .then(
+                                a.z_order
+                                    .partial_cmp(&b.z_order)
+                                    .unwrap_or(std::cmp::Ordering::Equal),
+                            )
+                            .then(a.entity.cmp(&b.entity))
+                    });
+
+                    draw_context.with_canvas(|canvas, _coordinate_system_helper| {
+                        for batch in split_by_layer(&items) {
+                            let layer = batch[0].layer;
+                            if !draw_layers.is_visible(layer) {
+                                continue;
+                            }
+
+                            // Shifting by part of the camera's position makes the layer scroll
+                            // slower (or faster). Only the position is used, the camera's
+                            // rotation and zoom are already applied to the whole canvas
+                            let parallax = draw_layers.parallax(layer);
+                            let offset = camera.position * (glam::Vec2::one() - parallax);
+
+                            canvas.save();
+                            canvas.translate((offset.x(), offset.y()));
+                            for item in batch {
+                                item.draw(canvas, asset_resource);
+                            }
+                            canvas.restore();
+                        }
+                    });
+                },
+            ),
+    );
+}
+
+// Splits sorted draw items into runs that share a layer
+fn split_by_layer<'a, 'b>(items: &'b [DrawItem<'a>]) -> Vec<&'b [DrawItem<'a>]> {
+    let mut batches = Vec::new();
+    let mut begin = 0;
+    for end in 1..=items.len() {
+        if end == items.len() || items[end].layer != items[begin].layer {
+            batches.push(&items[begin..end]);
+            begin = end;
+        }
+    }
+
+    batches
+}
+
+// Editor window for hiding layers and adjusting their parallax
+pub fn editor_draw_layers_window(schedule: &mut legion::systems::Builder) {
+    schedule.add_system(
+        SystemBuilder::new("editor_draw_layers_window")
+            .write_resource::<ImguiResource>()
+            .read_resource::<EditorStateResource>()
+            .write_resource::<DrawLayersResource>()
+            .build(|_, _, (imgui_manager, editor_state, draw_layers), _| {
+                if !editor_state.is_editor_active() || draw_layers.layers().is_empty() {
+                    return;
+                }
+
+                imgui_manager.with_ui(|ui: &mut imgui::Ui| {
+                    imgui::Window::new(im_str!("Draw Layers"))
+                        .position([0.0, 600.0], imgui::Condition::Once)
+                        .size([350.0, 150.0], imgui::Condition::Once)
+                        .collapsed(true, imgui::Condition::Once)
+                        .build(ui, || {
+                            for draw_layer in draw_layers.layers_mut() {
+                                ui.checkbox(
+                                    &im_str!("{} ({})", draw_layer.name, draw_layer.layer),
+                                    &mut draw_layer.visible,
+                                );
+
+                                let mut parallax =
+                                    [draw_layer.parallax.x(), draw_layer.parallax.y()];
+                                if ui
+                                    .input_float2(
+                                        &im_str!("Parallax##{}", draw_layer.layer),
+                                        &mut parallax,
+                                    )
+                                    .build()
+                                {
+                                    draw_layer.parallax = glam::Vec2::new(parallax[0], parallax[1]);
+                                }
+                            }
+                        });
+                });
+            }),
+    );
+}
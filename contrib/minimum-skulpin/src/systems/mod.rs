@@ -0,0 +1,11 @@
+mod sprite_animation_systems;
+pub use sprite_animation_systems::update_sprite_animations;
+
+mod draw_systems;
+pub use draw_systems::draw_begin;
+pub use draw_systems::draw_skia_components;
+pub use draw_systems::editor_draw_layers_window;
+
+mod particle_systems;
+pub use particle_systems::update_particle_emitters;
+pub use particle_systems::editor_draw_particle_emitter_gizmos;
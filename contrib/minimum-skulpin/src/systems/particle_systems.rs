@@ -0,0 +1,98 @@
+use legion::*;
+
+use minimum::components::TransformComponent;
+use minimum::resources::{DebugDraw3DDepthBehavior, DebugDraw3DResource, TimeResource};
+use minimum::resources::editor::{EditorSelectionResource, EditorStateResource};
+
+use crate::components::ParticleEmitterComponent;
+
+// Number of line segments used to draw the arc at the end of the emission cone
+const EMISSION_CONE_ARC_SEGMENTS: usize = 16;
+
+// Moves, ages and emits particles
+pub fn update_particle_emitters(schedule: &mut legion::systems::Builder) {
+    schedule.add_system(
+        SystemBuilder::new("update_particle_emitters")
+            .read_resource::<TimeResource>()
+            .with_query(<(Read<TransformComponent>, Write<ParticleEmitterComponent>)>::query())
+            .build(|_, world, time, query| {
+                // Game time stops while the simulation is paused. Emitters that are being
+                // previewed in the editor use system time instead
+                let is_simulation_paused = time.is_simulation_paused();
+                let game_dt = time.game_time().previous_update_dt();
+                let system_dt = time.system_time().previous_update_dt();
+
+                for (transform, emitter) in query.iter_mut(world) {
+                    let dt = if !is_simulation_paused {
+                        game_dt
+                    } else if emitter.preview_in_editor {
+                        system_dt
+                    } else {
+                        0.0
+                    };
+
+                    emitter.simulate(dt, transform);
+                }
+            }),
+    );
+}
+
+// Draws the emission cone of selected emitters. The cone's length is how far a particle travels
+// over its lifetime, ignoring gravity
+pub fn editor_draw_particle_emitter_gizmos(schedule: &mut legion::systems::Builder) {
+    schedule.add_system(
+        SystemBuilder::new("editor_draw_particle_emitter_gizmos")
+            .read_resource::<EditorStateResource>()
+            .read_resource::<EditorSelectionResource>()
+            .write_resource::<DebugDraw3DResource>()
+            .with_query(<(
+                Entity,
+                Read<TransformComponent>,
+                Read<ParticleEmitterComponent>,
+            )>::query())
+            .build(
+                |_, world, (editor_state, editor_selection, debug_draw), query| {
+                    if !editor_state.is_editor_active() {
+                        return;
+                    }
+
+                    let color = glam::vec4(1.0, 0.6, 0.0, 1.0);
+                    for (entity, transform, emitter) in query.iter(world) {
+                        if !editor_selection.is_entity_selected(*entity) {
+                            continue;
+                        }
+
+                        let origin = transform.position();
+                        let length = (emitter.speed * emitter.lifetime).abs().max(1.0);
+                        let direction = emitter.world_direction(transform);
+                        let spread = emitter.spread.to_radians();
+
+                        let point_at = |angle: f32| {
+                            origin + glam::Vec3::new(angle.cos(), angle.sin(), 0.0) * length
+                        };
+
+                        // Both edges of the cone plus the arc joining them
+                        let mut points = Vec::with_capacity(EMISSION_CONE_ARC_SEGMENTS + 3);
+                        points.push(origin);
+                        for i in 0..=EMISSION_CONE_ARC_SEGMENTS {
+                            let t = i as f32 / EMISSION_CONE_ARC_SEGMENTS as f32;
+                            points.push(point_at(direction - spread + 2.0 * spread * t));
+                        }
+                        points.push(origin);
+
+                        debug_draw.add_line_strip(
+                            points,
+                            color,
+                            DebugDraw3DDepthBehavior::NoDepthTest,
+                        );
+                        debug_draw.add_line(
+                            origin,
+                            point_at(direction),
+                            color,
+                            DebugDraw3DDepthBehavior::NoDepthTest,
+                        );
+                    }
+                },
+            ),
+    );
+}
@@ -0,0 +1,48 @@
+use legion::*;
+
+use atelier_assets::loader::handle::AssetHandle;
+
+use minimum::resources::{AssetResource, TimeResource};
+
+use crate::components::{DrawSkiaSpriteComponent, SpriteAnimationComponent};
+
+// Advances sprite animations and copies the current frame's sub_rect into the sprite
+pub fn update_sprite_animations(schedule: &mut legion::systems::Builder) {
+    schedule.add_system(
+        SystemBuilder::new("update_sprite_animations")
+            .read_resource::<TimeResource>()
+            .read_resource::<AssetResource>()
+            .with_query(<(
+                Write<SpriteAnimationComponent>,
+                Write<DrawSkiaSpriteComponent>,
+            )>::query())
+            .build(|_, world, (time, asset_resource), query| {
+                // Game time stops while the simulation is paused. Animations that are being
+                // previewed in the editor use system time instead
+                let is_simulation_paused = time.is_simulation_paused();
+                let game_dt = time.game_time().previous_update_dt();
+                let system_dt = time.system_time().previous_update_dt();
+
+                for (animation, sprite) in query.iter_mut(world) {
+                    let dt = if !is_simulation_paused {
+                        game_dt
+                    } else if animation.preview_in_editor {
+                        system_dt
+                    } else {
+                        0.0
+                    };
+
+                    animation.advance(dt);
+
+                    let animation_asset = animation.animation.asset(asset_resource.storage());
+                    let frame = animation_asset
+                        .and_then(|animation_asset| animation_asset.clip(animation.clip()))
+                        .and_then(|clip| clip.frame_at(animation.clip_time()));
+
+                    if let Some(frame) = frame {
+                        sprite.sub_rect = frame.sub_rect;
+                    }
+                }
+            }),
+    );
+}
@@ -0,0 +1,46 @@
+use minimum::resources::{CameraResource, ViewportResource};
+
+// this is a virtual coordinate system
+// top-left: (0, 0)
+// bottom-right: (600 * aspect_ratio, 600) where aspect_ratio is window_width / window_height
+fn calculate_screen_space_matrix(
+    _viewport_size_in_pixels: glam::Vec2,
+    view_half_extents: glam::Vec2,
+) -> glam::Mat4 {
+    let view = glam::Mat4::look_at_rh(
+        glam::Vec3::from([0.0, 0.0, 5.0]),
+        glam::Vec3::from([0.0, 0.0, 0.0]),
+        glam::Vec3::from([0.0, 1.0, 0.0]),
+    );
+
+    let projection = glam::Mat4::orthographic_rh(
+        0.0,
+        view_half_extents.x() * 2.0,
+        view_half_extents.y() * 2.0,
+        0.0,
+        -100.0,
+        100.0,
+    );
+
+    projection * view
+}
+
+// Sets the viewport size and screen space view, and sets the world space view from the camera.
+// Returns the half extents of the camera's orthographic view
+pub fn update_viewport(
+    viewport: &mut ViewportResource,
+    viewport_size_in_pixels: glam::Vec2,
+    camera: &CameraResource,
+) -> glam::Vec2 {
+    let view_half_extents = camera.view_half_extents(viewport_size_in_pixels);
+
+    viewport.set_viewport_size_in_pixels(viewport_size_in_pixels);
+    viewport.set_screen_space_view(calculate_screen_space_matrix(
+        viewport_size_in_pixels,
+        view_half_extents,
+    ));
+
+    camera.update_viewport(viewport);
+
+    view_half_extents
+}
@@ -1,10 +1,121 @@
 use minimum::input::KeyboardKey;
+use minimum::input::KeyModifiers;
 use minimum::input::ButtonState;
 
 pub use winit::event::VirtualKeyCode;
 pub use winit::event::ElementState;
 pub use winit::event::MouseScrollDelta;
 pub use winit::event::MouseButton;
+pub use winit::event::ModifiersState;
+
+// Every KeyboardKey and the winit key that produces it, in the same order as KeyboardKey::ALL.
+// minimum-sdl2 has the same table so that bindings behave the same with either backend.
+const VIRTUAL_KEY_CODE_TO_KEYBOARD_KEY: [(VirtualKeyCode, KeyboardKey); KeyboardKey::COUNT] = [
+    (VirtualKeyCode::A, KeyboardKey::A),
+    (VirtualKeyCode::B, KeyboardKey::B),
+    (VirtualKeyCode::C, KeyboardKey::C),
+    (VirtualKeyCode::D, KeyboardKey::D),
+    (VirtualKeyCode::E, KeyboardKey::E),
+    (VirtualKeyCode::F, KeyboardKey::F),
+    (VirtualKeyCode::G, KeyboardKey::G),
+    (VirtualKeyCode::H, KeyboardKey::H),
+    (VirtualKeyCode::I, KeyboardKey::I),
+    (VirtualKeyCode::J, KeyboardKey::J),
+    (VirtualKeyCode::K, KeyboardKey::K),
+    (VirtualKeyCode::L, KeyboardKey::L),
+    (VirtualKeyCode::M, KeyboardKey::M),
+    (VirtualKeyCode::N, KeyboardKey::N),
+    (VirtualKeyCode::O, KeyboardKey::O),
+    (VirtualKeyCode::P, KeyboardKey::P),
+    (VirtualKeyCode::Q, KeyboardKey::Q),
+    (VirtualKeyCode::R, KeyboardKey::R),
+    (VirtualKeyCode::S, KeyboardKey::S),
+    (VirtualKeyCode::T, KeyboardKey::T),
+    (VirtualKeyCode::U, KeyboardKey::U),
+    (VirtualKeyCode::V, KeyboardKey::V),
+    (VirtualKeyCode::W, KeyboardKey::W),
+    (VirtualKeyCode::X, KeyboardKey::X),
+    (VirtualKeyCode::Y, KeyboardKey::Y),
+    (VirtualKeyCode::Z, KeyboardKey::Z),
+    (VirtualKeyCode::Key0, KeyboardKey::Key0),
+    (VirtualKeyCode::Key1, KeyboardKey::Key1),
+    (VirtualKeyCode::Key2, KeyboardKey::Key2),
+    (VirtualKeyCode::Key3, KeyboardKey::Key3),
+    (VirtualKeyCode::Key4, KeyboardKey::Key4),
+    (VirtualKeyCode::Key5, KeyboardKey::Key5),
+    (VirtualKeyCode::Key6, KeyboardKey::Key6),
+    (VirtualKeyCode::Key7, KeyboardKey::Key7),
+    (VirtualKeyCode::Key8, KeyboardKey::Key8),
+    (VirtualKeyCode::Key9, KeyboardKey::Key9),
+    (VirtualKeyCode::F1, KeyboardKey::F1),
+    (VirtualKeyCode::F2, KeyboardKey::F2),
+    (VirtualKeyCode::F3, KeyboardKey::F3),
+    (VirtualKeyCode::F4, KeyboardKey::F4),
+    (VirtualKeyCode::F5, KeyboardKey::F5),
+    (VirtualKeyCode::F6, KeyboardKey::F6),
+    (VirtualKeyCode::F7, KeyboardKey::F7),
+    (VirtualKeyCode::F8, KeyboardKey::F8),
+    (VirtualKeyCode::F9, KeyboardKey::F9),
+    (VirtualKeyCode::F10, KeyboardKey::F10),
+    (VirtualKeyCode::F11, KeyboardKey::F11),
+    (VirtualKeyCode::F12, KeyboardKey::F12),
+    (VirtualKeyCode::Escape, KeyboardKey::Escape),
+    (VirtualKeyCode::Tab, KeyboardKey::Tab),
+    (VirtualKeyCode::Capital, KeyboardKey::CapsLock),
+    (VirtualKeyCode::Space, KeyboardKey::Space),
+    (VirtualKeyCode::Return, KeyboardKey::Enter),
+    (VirtualKeyCode::Back, KeyboardKey::Backspace),
+    (VirtualKeyCode::Delete, KeyboardKey::Delete),
+    (VirtualKeyCode::Insert, KeyboardKey::Insert),
+    (VirtualKeyCode::Home, KeyboardKey::Home),
+    (VirtualKeyCode::End, KeyboardKey::End),
+    (VirtualKeyCode::PageUp, KeyboardKey::PageUp),
+    (VirtualKeyCode::PageDown, KeyboardKey::PageDown),
+    (VirtualKeyCode::Left, KeyboardKey::Left),
+    (VirtualKeyCode::Right, KeyboardKey::Right),
+    (VirtualKeyCode::Up, KeyboardKey::Up),
+    (VirtualKeyCode::Down, KeyboardKey::Down),
+    (VirtualKeyCode::Apps, KeyboardKey::Menu),
+    (VirtualKeyCode::LShift, KeyboardKey::LShift),
+    (VirtualKeyCode::RShift, KeyboardKey::RShift),
+    (VirtualKeyCode::LControl, KeyboardKey::LCtrl),
+    (VirtualKeyCode::RControl, KeyboardKey::RCtrl),
+    (VirtualKeyCode::LAlt, KeyboardKey::LAlt),
+    (VirtualKeyCode::RAlt, KeyboardKey::RAlt),
+    (VirtualKeyCode::LWin, KeyboardKey::LLogo),
+    (VirtualKeyCode::RWin, KeyboardKey::RLogo),
+    (VirtualKeyCode::Minus, KeyboardKey::Minus),
+    (VirtualKeyCode::Equals, KeyboardKey::Equals),
+    (VirtualKeyCode::LBracket, KeyboardKey::LBracket),
+    (VirtualKeyCode::RBracket, KeyboardKey::RBracket),
+    (VirtualKeyCode::Backslash, KeyboardKey::Backslash),
+    (VirtualKeyCode::Semicolon, KeyboardKey::Semicolon),
+    (VirtualKeyCode::Apostrophe, KeyboardKey::Apostrophe),
+    (VirtualKeyCode::Grave, KeyboardKey::Grave),
+    (VirtualKeyCode::Comma, KeyboardKey::Comma),
+    (VirtualKeyCode::Period, KeyboardKey::Period),
+    (VirtualKeyCode::Slash, KeyboardKey::Slash),
+    (VirtualKeyCode::Numpad0, KeyboardKey::Numpad0),
+    (VirtualKeyCode::Numpad1, KeyboardKey::Numpad1),
+    (VirtualKeyCode::Numpad2, KeyboardKey::Numpad2),
+    (VirtualKeyCode::Numpad3, KeyboardKey::Numpad3),
+    (VirtualKeyCode::Numpad4, KeyboardKey::Numpad4),
+    (VirtualKeyCode::Numpad5, KeyboardKey::Numpad5),
+    (VirtualKeyCode::Numpad6, KeyboardKey::Numpad6),
+    (VirtualKeyCode::Numpad7, KeyboardKey::Numpad7),
+    (VirtualKeyCode::Numpad8, KeyboardKey::Numpad8),
+    (VirtualKeyCode::Numpad9, KeyboardKey::Numpad9),
+    (VirtualKeyCode::Add, KeyboardKey::NumpadAdd),
+    (VirtualKeyCode::Subtract, KeyboardKey::NumpadSubtract),
+    (VirtualKeyCode::Multiply, KeyboardKey::NumpadMultiply),
+    (VirtualKeyCode::Divide, KeyboardKey::NumpadDivide),
+    (VirtualKeyCode::Decimal, KeyboardKey::NumpadDecimal),
+    (VirtualKeyCode::NumpadEnter, KeyboardKey::NumpadEnter),
+    (VirtualKeyCode::Numlock, KeyboardKey::NumLock),
+    (VirtualKeyCode::Snapshot, KeyboardKey::PrintScreen),
+    (VirtualKeyCode::Scroll, KeyboardKey::ScrollLock),
+    (VirtualKeyCode::Pause, KeyboardKey::Pause),
+];
 
 #[derive(Copy, Clone)]
 pub struct WinitKeyboardKey {
@@ -17,9 +128,35 @@ impl WinitKeyboardKey {
     }
 }
 
-impl Into<KeyboardKey> for WinitKeyboardKey {
-    fn into(self) -> KeyboardKey {
-        KeyboardKey(self.keycode as u8)
+// Keys that have no KeyboardKey equivalent convert to None
+impl Into<Option<KeyboardKey>> for WinitKeyboardKey {
+    fn into(self) -> Option<KeyboardKey> {
+        VIRTUAL_KEY_CODE_TO_KEYBOARD_KEY
+            .iter()
+            .find(|(keycode, _)| *keycode == self.keycode)
+            .map(|(_, key)| *key)
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct WinitKeyModifiers {
+    modifiers_state: ModifiersState,
+}
+
+impl WinitKeyModifiers {
+    pub fn new(modifiers_state: ModifiersState) -> Self {
+        WinitKeyModifiers { modifiers_state }
+    }
+}
+
+impl Into<KeyModifiers> for WinitKeyModifiers {
+    fn into(self) -> KeyModifiers {
+        KeyModifiers {
+            shift: self.modifiers_state.shift(),
+            ctrl: self.modifiers_state.ctrl(),
+            alt: self.modifiers_state.alt(),
+            logo: self.modifiers_state.logo(),
+        }
     }
 }
 
@@ -109,14 +246,24 @@ pub fn handle_winit_event<T>(
             ..
         } => {
             trace!("keyboard input {:?}", input);
-            if let Some(vk) = input.virtual_keycode {
-                input_state.handle_keyboard_event(
-                    WinitKeyboardKey::new(vk).into(),
-                    WinitElementState::new(input.state).into(),
-                );
+
+            // Repeats arrive as further pressed events, which InputState recognizes on its own
+            let key = input
+                .virtual_keycode
+                .and_then(|vk| WinitKeyboardKey::new(vk).into());
+            if let Some(key) = key {
+                input_state.handle_keyboard_event(key, WinitElementState::new(input.state).into());
             }
         }
 
+        Event::WindowEvent {
+            event: WindowEvent::ModifiersChanged(modifiers_state),
+            ..
+        } => {
+            trace!("modifiers changed {:?}", modifiers_state);
+            input_state.handle_modifiers_event(WinitKeyModifiers::new(*modifiers_state).into());
+        }
+
         Event::WindowEvent {
             event:
                 WindowEvent::MouseInput {
@@ -164,7 +311,60 @@ pub fn handle_winit_event<T>(
             input_state.handle_mouse_wheel_event(WinitMouseScrollDelta::new(*delta).into());
         }
 
+        Event::WindowEvent {
+            event: WindowEvent::ReceivedCharacter(character),
+            ..
+        } => {
+            trace!("received character {:?}", character);
+
+            // Backspace, enter, etc. also arrive as characters. SDL2 doesn't send these as text,
+            // so leave them to the key events here too
+            if !character.is_control() {
+                let mut buffer = [0; 4];
+                input_state.handle_text_input_event(character.encode_utf8(&mut buffer));
+            }
+        }
+
         // Ignore any other events
         _ => (),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // The sdl2 backend has the same test, so together they ensure both backends produce the same
+    // KeyboardKey for the same physical key
+    #[test]
+    fn every_keyboard_key_is_mapped_once() {
+        for (key, (keycode, mapped_key)) in KeyboardKey::ALL
+            .iter()
+            .zip(&VIRTUAL_KEY_CODE_TO_KEYBOARD_KEY)
+        {
+            assert_eq!(mapped_key, key);
+
+            // Fails if another entry earlier in the table uses the same keycode
+            let converted: Option<KeyboardKey> = WinitKeyboardKey::new(*keycode).into();
+            assert_eq!(converted, Some(*key));
+        }
+    }
+
+    #[test]
+    fn keys_with_different_names_map_to_the_same_key() {
+        let expected = [
+            (VirtualKeyCode::Key1, KeyboardKey::Key1),
+            (VirtualKeyCode::Return, KeyboardKey::Enter),
+            (VirtualKeyCode::Back, KeyboardKey::Backspace),
+            (VirtualKeyCode::LControl, KeyboardKey::LCtrl),
+            (VirtualKeyCode::LWin, KeyboardKey::LLogo),
+            (VirtualKeyCode::Numpad5, KeyboardKey::Numpad5),
+            (VirtualKeyCode::Apostrophe, KeyboardKey::Apostrophe),
+        ];
+
+        for (keycode, key) in expected.iter() {
+            let converted: Option<KeyboardKey> = WinitKeyboardKey::new(*keycode).into();
+            assert_eq!(converted, Some(*key));
+        }
+    }
+}
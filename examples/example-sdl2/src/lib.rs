@@ -26,7 +26,7 @@ mod registration;
 
 use minimum::resources::editor::{
     EditorMode, EditorSelectionResource, EditorInspectRegistryResource, EditorStateResource,
-    EditorDraw3DResource,
+    EditorDraw3DResource, EditorCameraResource,
 };
 use minimum_sdl2::resources::{Sdl2WindowResource, Sdl2ImguiManagerResource};
 use minimum_sdl2::imgui::Sdl2ImguiManager;
@@ -44,6 +44,11 @@ pub fn run() {
     let video_subsystem = sdl_context
         .video()
         .expect("Failed to create sdl video subsystem");
+    let game_controller_subsystem = sdl_context
+        .game_controller()
+        .expect("Failed to create sdl game controller subsystem");
+    let mut gamepad_manager =
+        minimum_sdl2::input::Sdl2GamepadManager::new(game_controller_subsystem);
 
     // Create a window
     let window_size = (900, 600);
@@ -123,6 +128,12 @@ pub fn run() {
         for event in event_pump.poll_iter() {
             log::info!("SDL2 Event: {:?}", event);
 
+            // Controllers are opened even if imgui wants input so that they are ready later
+            {
+                let mut input_resource = resources.get_mut::<InputResource>().unwrap();
+                gamepad_manager.handle_sdl2_event(&event, input_resource.input_state_mut());
+            }
+
             sdl2_imgui.handle_event(&event);
 
             if !sdl2_imgui.ignore_event(&event) {
@@ -139,12 +150,26 @@ pub fn run() {
         }
 
         // Update/Draw here
+        {
+            let input_resource = resources.get::<InputResource>().unwrap();
+            sdl2_imgui
+                .imgui_manager()
+                .set_mouse_click_settings(input_resource.mouse_click_settings());
+        }
         sdl2_imgui.begin_frame(&sdl2_window, &MouseState::new(&event_pump));
 
         update_schedule.execute(&mut world, &mut resources);
 
         sdl2_imgui.render(&sdl2_window);
 
+        {
+            let input_resource = resources.get::<InputResource>().unwrap();
+            minimum_sdl2::input::update_sdl2_text_input(
+                &video_subsystem.text_input(),
+                input_resource.is_text_input_active() || sdl2_imgui.want_text_input(),
+            );
+        }
+
         renderer
             .draw(&skulpin_window, |canvas, coordinate_system_helper| {
                 resources
@@ -197,11 +222,10 @@ fn create_resources(
 
     let mut viewport = ViewportResource::empty();
     let viewport_size_in_pixels = glam::Vec2::new(drawable.width as f32, drawable.height as f32);
-    example_shared::viewport::update_viewport(
+    minimum_skulpin::viewport::update_viewport(
         &mut viewport,
         viewport_size_in_pixels,
-        camera_resource.position,
-        camera_resource.x_half_extents,
+        &camera_resource,
     );
 
     resources.insert(viewport);
@@ -218,9 +242,18 @@ fn create_resources(
         registration::create_component_registry(),
     ));
     resources.insert(FpsTextResource::new());
+
+    // Gameplay input bindings, edit assets/input.inputmap to change them while running
+    let mut input_map = minimum::resources::InputMapResource::new();
+    input_map.load(
+        &asset_resource,
+        asset_uuid!("f5865098-3f9b-4575-b3da-97cce49fb3ed"),
+    );
+    resources.insert(input_map);
     resources.insert(asset_resource);
     resources.insert(physics_resource);
     resources.insert(camera_resource);
+    resources.insert(registration::create_draw_layers());
     resources.insert(Sdl2ImguiManagerResource::new(sdl2_imgui.clone()));
     resources.insert(ImguiResource::new(sdl2_imgui.imgui_manager()));
     resources.insert(AppControlResource::new());
@@ -229,22 +262,15 @@ fn create_resources(
     resources.insert(CanvasDrawResource::default());
     resources.insert(Sdl2WindowResource::new(&sdl2_window));
     resources.insert(EditorStateResource::new());
+    resources.insert(EditorCameraResource::new());
 
-    use minimum_sdl2::input::Sdl2KeyboardKey;
-    use sdl2::keyboard::Keycode;
-    let keybinds = minimum::resources::editor::Keybinds {
-        selection_add: Sdl2KeyboardKey::new(Keycode::LShift).into(),
-        selection_subtract: Sdl2KeyboardKey::new(Keycode::LAlt).into(),
-        selection_toggle: Sdl2KeyboardKey::new(Keycode::LCtrl).into(),
-        tool_translate: Sdl2KeyboardKey::new(Keycode::Num1).into(),
-        tool_scale: Sdl2KeyboardKey::new(Keycode::Num2).into(),
-        tool_rotate: Sdl2KeyboardKey::new(Keycode::Num3).into(),
-        action_quit: Sdl2KeyboardKey::new(Keycode::Escape).into(),
-        action_toggle_editor_pause: Sdl2KeyboardKey::new(Keycode::Space).into(),
-    };
+    let keybinds = minimum::resources::editor::Keybinds::default();
 
-    resources.insert(minimum::resources::editor::EditorSettingsResource::new(
-        keybinds,
-    ));
+    // Bindings saved from the keybinds window override the defaults
+    let mut editor_settings = minimum::resources::editor::EditorSettingsResource::new(keybinds);
+    if let Err(error) = editor_settings.load_keybinds("keybinds.ron") {
+        log::warn!("Failed to load keybinds: {:?}", error);
+    }
+    resources.insert(editor_settings);
     resources
 }
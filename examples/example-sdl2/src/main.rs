@@ -9,6 +9,19 @@ fn main() {
     std::thread::spawn(move || {
         minimum::daemon::create_default_asset_daemon()
             .with_importer("prefab", minimum::pipeline::PrefabImporter::default())
+            .with_importer("gltf", minimum::pipeline::GltfImporter::default())
+            .with_importer("glb", minimum::pipeline::GltfImporter::default())
+            .with_importer("tmx", minimum_skulpin::pipeline::TiledImporter::default())
+            .with_importer("png", minimum_skulpin::pipeline::ImageImporter::default())
+            .with_importer("jpg", minimum_skulpin::pipeline::ImageImporter::default())
+            .with_importer("jpeg", minimum_skulpin::pipeline::ImageImporter::default())
+            .with_importer(
+                "spriteanim",
+                minimum_skulpin::pipeline::SpriteAnimationImporter::default(),
+            )
+            .with_importer("ttf", minimum_skulpin::pipeline::FontImporter::default())
+            .with_importer("otf", minimum_skulpin::pipeline::FontImporter::default())
+            .with_importer("inputmap", minimum::pipeline::InputMapImporter::default())
             .run();
     });
 
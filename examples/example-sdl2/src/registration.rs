@@ -9,6 +9,11 @@ use minimum::editor::EditorInspectRegistryBuilder;
 use minimum::ComponentRegistry;
 
 use minimum_skulpin::components::*;
+use minimum_skulpin::resources::DrawLayersResource;
+use minimum_skulpin::pipeline::{
+    ImageAsset, ImageAssetData, ImageAssetLoader, SpriteAnimationAsset, FontAsset, FontAssetData,
+    FontAssetLoader,
+};
 use minimum_nphysics2d::components::*;
 
 use atelier_assets::loader::rpc_loader::RpcLoader;
@@ -17,6 +22,14 @@ use atelier_assets::loader::rpc_loader::RpcLoader;
 pub fn create_asset_manager(loader: RpcLoader) -> AssetResource {
     let mut asset_manager = AssetResource::new(loader);
     asset_manager.add_storage::<minimum::pipeline::PrefabAsset>();
+    asset_manager.add_storage_with_loader::<ImageAssetData, ImageAsset, _>(Box::new(
+        ImageAssetLoader::default(),
+    ));
+    asset_manager.add_storage::<SpriteAnimationAsset>();
+    asset_manager.add_storage::<minimum::pipeline::InputMapAsset>();
+    asset_manager.add_storage_with_loader::<FontAssetData, FontAsset, _>(Box::new(
+        FontAssetLoader::default(),
+    ));
     asset_manager
 }
 
@@ -25,6 +38,17 @@ pub fn create_component_registry() -> ComponentRegistry {
         .auto_register_components()
         .add_spawn_mapping_into::<DrawSkiaCircleComponentDef, DrawSkiaCircleComponent>()
         .add_spawn_mapping_into::<DrawSkiaBoxComponentDef, DrawSkiaBoxComponent>()
+        .add_spawn_mapping::<TilemapComponentDef, TilemapComponent>()
+        .add_spawn_mapping::<DrawSkiaSpriteComponentDef, DrawSkiaSpriteComponent>()
+        .add_spawn_mapping::<SpriteAnimationComponentDef, SpriteAnimationComponent>()
+        .add_spawn_mapping::<DrawSkiaTextComponentDef, DrawSkiaTextComponent>()
+        .add_spawn_mapping_into::<DrawSkiaPolygonComponentDef, DrawSkiaPolygonComponent>()
+        .add_spawn_mapping_into::<DrawSkiaPolylineComponentDef, DrawSkiaPolylineComponent>()
+        .add_spawn_mapping_into::<DrawSkiaCapsuleComponentDef, DrawSkiaCapsuleComponent>()
+        .add_spawn_mapping_into::<DrawSkiaRoundedRectComponentDef, DrawSkiaRoundedRectComponent>()
+        .add_spawn_mapping_into::<DrawLayerComponentDef, DrawLayerComponent>()
+        .add_spawn_mapping_into::<ParticleEmitterComponentDef, ParticleEmitterComponent>()
+        .add_spawn_mapping_into::<CameraComponentDef, CameraComponent>()
         .add_spawn_mapping::<RigidBodyBallComponentDef, RigidBodyComponent>()
         .add_spawn_mapping::<RigidBodyBoxComponentDef, RigidBodyComponent>()
         .add_spawn_mapping_into::<TransformComponentDef, TransformComponent>()
@@ -35,6 +59,14 @@ pub fn create_editor_selection_registry() -> EditorSelectRegistry {
     EditorSelectRegistryBuilder::new()
         .register::<DrawSkiaBoxComponent>()
         .register::<DrawSkiaCircleComponent>()
+        .register::<TilemapComponent>()
+        .register::<DrawSkiaSpriteComponent>()
+        .register::<DrawSkiaTextComponent>()
+        .register::<DrawSkiaPolygonComponent>()
+        .register::<DrawSkiaPolylineComponent>()
+        .register::<DrawSkiaCapsuleComponent>()
+        .register::<DrawSkiaRoundedRectComponent>()
+        .register::<ParticleEmitterComponent>()
         .register_transformed::<RigidBodyBoxComponentDef, RigidBodyComponent>()
         .register_transformed::<RigidBodyBallComponentDef, RigidBodyComponent>()
         .build()
@@ -44,8 +76,29 @@ pub fn create_editor_inspector_registry() -> EditorInspectRegistry {
     EditorInspectRegistryBuilder::default()
         .register::<DrawSkiaCircleComponentDef>()
         .register::<DrawSkiaBoxComponentDef>()
+        .register::<TilemapComponentDef>()
+        .register::<DrawSkiaSpriteComponentDef>()
+        .register::<SpriteAnimationComponentDef>()
+        .register::<DrawSkiaTextComponentDef>()
+        .register::<DrawSkiaPolygonComponentDef>()
+        .register::<DrawSkiaPolylineComponentDef>()
+        .register::<DrawSkiaCapsuleComponentDef>()
+        .register::<DrawSkiaRoundedRectComponentDef>()
+        .register::<DrawLayerComponentDef>()
+        .register::<ParticleEmitterComponentDef>()
+        .register::<CameraComponentDef>()
+        .register::<CameraTargetComponent>()
         .register::<TransformComponentDef>()
         .register::<RigidBodyBallComponentDef>()
         .register::<RigidBodyBoxComponentDef>()
         .build()
 }
+
+/// Create the layers that skia draw components can be placed on
+pub fn create_draw_layers() -> DrawLayersResource {
+    let mut draw_layers = DrawLayersResource::new();
+    draw_layers.add_layer(-1, "Background", glam::Vec2::new(1.0, 1.0));
+    draw_layers.add_layer(0, "Default", glam::Vec2::new(1.0, 1.0));
+    draw_layers.add_layer(1, "Foreground", glam::Vec2::new(1.0, 1.0));
+    draw_layers
+}
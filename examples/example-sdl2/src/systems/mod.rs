@@ -1,16 +1,14 @@
 mod app_control_systems;
 pub use app_control_systems::quit_if_escape_pressed;
 
-mod draw_systems;
-pub use draw_systems::draw;
-
 use minimum::systems::*;
 use example_shared::systems::*;
 
 use legion::*;
 
 use minimum::editor::resources::EditorMode;
-use minimum_nphysics2d::systems::{update_physics, read_from_physics};
+use minimum_nphysics2d::systems::{update_physics, maintain_physics, read_from_physics};
+use minimum_skulpin::systems::*;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct ScheduleCriteria {
@@ -94,6 +92,16 @@ impl<'a> ScheduleBuilder<'a> {
         self
     }
 
+    // Runs the schedule once per fixed step that is due, after everything added before it
+    fn fixed_tick(
+        mut self,
+        schedule: Schedule,
+    ) -> Self {
+        self.schedule
+            .add_thread_local_fn(fixed_update_schedule(schedule));
+        self
+    }
+
     fn flush(mut self) -> Self {
         self.schedule.flush();
         self
@@ -108,33 +116,56 @@ pub fn create_update_schedule(criteria: &ScheduleCriteria) -> Schedule {
         .always(advance_time)
         .always(quit_if_escape_pressed)
         .always_thread_local(update_asset_manager)
+        .always(editor_update_input_contexts)
+        .always(update_input_map)
         .always(update_fps_text)
-        .always(update_physics)
+        .always(maintain_physics)
+        .fixed_tick(create_fixed_update_schedule(criteria))
+        .always(update_sprite_animations)
+        .always(update_particle_emitters)
         .simulation_unpaused_only(read_from_physics)
+        .always(update_cameras)
         // --- Editor stuff here ---
         // Prepare to handle editor input
         .always_thread_local(editor_refresh_selection_world)
         // Editor input
         .always_thread_local(reload_editor_state_if_file_changed)
         .always(editor_keybinds)
+        .always(editor_nudge_selection)
         .always(editor_mouse_input)
         .always(editor_update_editor_draw)
         .always(editor_gizmos)
+        .always(editor_draw_grid)
         .always(editor_handle_selection)
         .always(editor_imgui_menu)
         .always(editor_entity_list_window)
+        .always(editor_transform_toolbar_window)
+        .always(editor_keybinds_window)
         .always_thread_local(editor_inspector_window)
+        .always(editor_draw_layers_window)
         // Editor processing
         .always_thread_local(editor_process_edit_diffs)
         .always_thread_local(editor_process_selection_ops)
         .always_thread_local(editor_process_editor_ops)
         // Editor output
         .always(draw_selection_shapes)
+        .always(editor_draw_particle_emitter_gizmos)
         // --- End editor stuff ---
         .always(input_reset_for_next_frame)
         .build()
 }
 
+// Systems that step the simulation. These run on the fixed tick, so zero or more times per frame
+pub fn create_fixed_update_schedule(criteria: &ScheduleCriteria) -> Schedule {
+    ScheduleBuilder::new(criteria)
+        .always(update_physics)
+        .build()
+}
+
 pub fn create_draw_schedule(criteria: &ScheduleCriteria) -> Schedule {
-    ScheduleBuilder::new(criteria).always(draw).build()
+    ScheduleBuilder::new(criteria)
+        .always(draw_begin)
+        .always(draw_skia_components)
+        .always(draw_end)
+        .build()
 }
@@ -4,4 +4,3 @@ extern crate log;
 
 pub mod resources;
 pub mod systems;
-pub mod viewport;
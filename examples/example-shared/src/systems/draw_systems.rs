@@ -0,0 +1,140 @@
+use legion::*;
+
+use skulpin::{skia_safe};
+
+use minimum::components::{TransformComponent};
+use minimum_skulpin::components::DrawSkiaTextComponent;
+
+use minimum_skulpin::resources::CanvasDrawResource;
+use minimum::resources::{AssetResource, ViewportResource, DebugDraw2DResource, DebugDraw3DResource};
+
+use crate::resources::FpsTextResource;
+
+// Draws debug shapes and screen space UI on top of everything else
+pub fn draw_end(schedule: &mut legion::systems::Builder) {
+    schedule.add_system(
+        SystemBuilder::new("draw_end")
+            .write_resource::<CanvasDrawResource>()
+            .read_resource::<FpsTextResource>()
+            .read_resource::<AssetResource>()
+            .read_resource::<ViewportResource>()
+            .write_resource::<DebugDraw2DResource>()
+            .write_resource::<DebugDraw3DResource>()
+            .with_query(<(Read<TransformComponent>, Read<DrawSkiaTextComponent>)>::query())
+            .build(
+                |_,
+                 world,
+                 (
+                    draw_context,
+                    fps_text,
+                    asset_resource,
+                    viewport,
+                    debug_draw_2d,
+                    debug_draw_3d,
+                ),
+                 draw_text_query| {
+                    draw_context.with_canvas(|canvas, coordinate_system_helper| {
+                        // Switch to pixels
+                        coordinate_system_helper.use_physical_coordinates(canvas);
+
+                        // Debug draw 3D - much of the editor logic works in 3D. Points are
+                        // projected to pixels
+                        for line_list in debug_draw_3d.take_line_lists() {
+                            if line_list.points.len() < 2 {
+                                continue;
+                            }
+
+                            let paint = skia_safe::Paint::new(
+                                skia_safe::Color4f::new(
+                                    line_list.color.x(),
+                                    line_list.color.y(),
+                                    line_list.color.z(),
+                                    line_list.color.w(),
+                                ),
+                                None,
+                            );
+
+                            // Skip segments that are behind the camera or past the clip planes
+                            let is_visible = |point: glam::Vec3| {
+                                (0.0..=1.0).contains(&viewport.world_space_clip(point))
+                            };
+
+                            // Project the points like the camera does, so that lines that aren't
+                            // on the z=0 plane are drawn correctly when the camera is rotated or
+                            // uses a perspective projection
+                            for i in 1..line_list.points.len() {
+                                let from = line_list.points[i - 1];
+                                let to = line_list.points[i];
+                                if !is_visible(from) || !is_visible(to) {
+                                    continue;
+                                }
+
+                                let from = viewport.world_space_to_viewport_space(from);
+                                let to = viewport.world_space_to_viewport_space(to);
+                                canvas.draw_line(
+                                    skia_safe::Point::new(from.x(), from.y()),
+                                    skia_safe::Point::new(to.x(), to.y()),
+                                    &paint,
+                                );
+                            }
+                        }
+
+                        // Debug draw 2D - some editor logic works in 2D
+                        for line_list in debug_draw_2d.take_line_lists() {
+                            if line_list.points.len() < 2 {
+                                continue;
+                            }
+
+                            let paint = skia_safe::Paint::new(
+                                skia_safe::Color4f::new(
+                                    line_list.color.x(),
+                                    line_list.color.y(),
+                                    line_list.color.z(),
+                                    line_list.color.w(),
+                                ),
+                                None,
+                            );
+
+                            let from = line_list.points[0];
+                            let mut from = skia_safe::Point::new(from.x(), from.y());
+                            for i in 1..line_list.points.len() {
+                                let to = line_list.points[i];
+                                let to = skia_safe::Point::new(to.x(), to.y());
+                                canvas.draw_line(from, to, &paint);
+                                from = to;
+                            }
+                        }
+
+                        debug_draw_3d.clear();
+                        debug_draw_2d.clear();
+
+                        // Switch to using logical screen-space coordinates
+                        coordinate_system_helper.use_logical_coordinates(canvas);
+
+                        // Draw HUD text
+                        for (transform, text) in draw_text_query.iter(world) {
+                            if text.screen_space {
+                                text.draw(canvas, transform, asset_resource);
+                            }
+                        }
+
+                        //
+                        // Draw FPS text
+                        //
+                        let mut text_paint = skia_safe::Paint::new(
+                            skia_safe::Color4f::new(1.0, 1.0, 0.0, 1.0),
+                            None,
+                        );
+                        text_paint.set_anti_alias(true);
+                        text_paint.set_style(skia_safe::paint::Style::StrokeAndFill);
+                        text_paint.set_stroke_width(1.0);
+
+                        let mut font = skia_safe::Font::default();
+                        font.set_size(20.0);
+                        //canvas.draw_str(self.fps_text.clone(), (50, 50), &font, &text_paint);
+                        canvas.draw_str(fps_text.fps_text.clone(), (50, 50), &font, &text_paint);
+                    });
+                },
+            ),
+    );
+}
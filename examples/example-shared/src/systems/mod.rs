@@ -1,2 +1,5 @@
 mod fps_text_systems;
 pub use fps_text_systems::update_fps_text;
+
+mod draw_systems;
+pub use draw_systems::draw_end;
@@ -212,6 +212,10 @@ impl App {
                     {
                         let _imgui_manager =
                             resources.get_mut::<WinitImguiManagerResource>().unwrap();
+                        let input_resource = resources.get::<InputResource>().unwrap();
+                        winit_imgui_manager
+                            .imgui_manager()
+                            .set_mouse_click_settings(input_resource.mouse_click_settings());
                         winit_imgui_manager.begin_frame(&winit_window);
                     }
                     app_handler.update(&mut world, &mut resources);
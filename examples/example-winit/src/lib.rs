@@ -29,6 +29,7 @@ use minimum::editor::EditorInspectRegistryBuilder;
 use minimum::editor::EditorSelectRegistry;
 use minimum::editor::resources::EditorMode;
 use minimum::editor::resources::EditorStateResource;
+use minimum::editor::resources::EditorCameraResource;
 use minimum::editor::resources::EditorDraw3DResource;
 use minimum::editor::resources::EditorSelectionResource;
 use skulpin::Window;
@@ -40,6 +41,11 @@ use minimum_nphysics2d::resources::PhysicsResource;
 use minimum_nphysics2d::components::*;
 use example_shared::resources::FpsTextResource;
 use minimum_skulpin::components::*;
+use minimum_skulpin::resources::DrawLayersResource;
+use minimum_skulpin::pipeline::{
+    ImageAsset, ImageAssetData, ImageAssetLoader, SpriteAnimationAsset, FontAsset, FontAssetData,
+    FontAssetLoader,
+};
 use atelier_assets::loader::rpc_loader::RpcLoader;
 
 pub const GROUND_HALF_EXTENTS_WIDTH: f32 = 3.0;
@@ -49,6 +55,14 @@ pub const GRAVITY: f32 = -9.81;
 pub fn create_asset_manager(loader: RpcLoader) -> AssetResource {
     let mut asset_manager = AssetResource::new(loader);
     asset_manager.add_storage::<minimum::pipeline::PrefabAsset>();
+    asset_manager.add_storage_with_loader::<ImageAssetData, ImageAsset, _>(Box::new(
+        ImageAssetLoader::default(),
+    ));
+    asset_manager.add_storage::<SpriteAnimationAsset>();
+    asset_manager.add_storage::<minimum::pipeline::InputMapAsset>();
+    asset_manager.add_storage_with_loader::<FontAssetData, FontAsset, _>(Box::new(
+        FontAssetLoader::default(),
+    ));
     asset_manager
 }
 
@@ -57,6 +71,17 @@ pub fn create_component_registry() -> ComponentRegistry {
         .auto_register_components()
         .add_spawn_mapping_into::<DrawSkiaCircleComponentDef, DrawSkiaCircleComponent>()
         .add_spawn_mapping_into::<DrawSkiaBoxComponentDef, DrawSkiaBoxComponent>()
+        .add_spawn_mapping::<TilemapComponentDef, TilemapComponent>()
+        .add_spawn_mapping::<DrawSkiaSpriteComponentDef, DrawSkiaSpriteComponent>()
+        .add_spawn_mapping::<SpriteAnimationComponentDef, SpriteAnimationComponent>()
+        .add_spawn_mapping::<DrawSkiaTextComponentDef, DrawSkiaTextComponent>()
+        .add_spawn_mapping_into::<DrawSkiaPolygonComponentDef, DrawSkiaPolygonComponent>()
+        .add_spawn_mapping_into::<DrawSkiaPolylineComponentDef, DrawSkiaPolylineComponent>()
+        .add_spawn_mapping_into::<DrawSkiaCapsuleComponentDef, DrawSkiaCapsuleComponent>()
+        .add_spawn_mapping_into::<DrawSkiaRoundedRectComponentDef, DrawSkiaRoundedRectComponent>()
+        .add_spawn_mapping_into::<DrawLayerComponentDef, DrawLayerComponent>()
+        .add_spawn_mapping_into::<ParticleEmitterComponentDef, ParticleEmitterComponent>()
+        .add_spawn_mapping_into::<CameraComponentDef, CameraComponent>()
         .add_spawn_mapping::<RigidBodyBallComponentDef, RigidBodyComponent>()
         .add_spawn_mapping::<RigidBodyBoxComponentDef, RigidBodyComponent>()
         .add_spawn_mapping_into::<TransformComponentDef, TransformComponent>()
@@ -67,6 +92,14 @@ pub fn create_editor_selection_registry() -> EditorSelectRegistry {
     EditorSelectRegistryBuilder::new()
         .register::<DrawSkiaBoxComponent>()
         .register::<DrawSkiaCircleComponent>()
+        .register::<TilemapComponent>()
+        .register::<DrawSkiaSpriteComponent>()
+        .register::<DrawSkiaTextComponent>()
+        .register::<DrawSkiaPolygonComponent>()
+        .register::<DrawSkiaPolylineComponent>()
+        .register::<DrawSkiaCapsuleComponent>()
+        .register::<DrawSkiaRoundedRectComponent>()
+        .register::<ParticleEmitterComponent>()
         .register_transformed::<RigidBodyBoxComponentDef, RigidBodyComponent>()
         .register_transformed::<RigidBodyBallComponentDef, RigidBodyComponent>()
         .build()
@@ -76,12 +109,33 @@ pub fn create_editor_inspector_registry() -> EditorInspectRegistry {
     EditorInspectRegistryBuilder::default()
         .register::<DrawSkiaCircleComponentDef>()
         .register::<DrawSkiaBoxComponentDef>()
+        .register::<TilemapComponentDef>()
+        .register::<DrawSkiaSpriteComponentDef>()
+        .register::<SpriteAnimationComponentDef>()
+        .register::<DrawSkiaTextComponentDef>()
+        .register::<DrawSkiaPolygonComponentDef>()
+        .register::<DrawSkiaPolylineComponentDef>()
+        .register::<DrawSkiaCapsuleComponentDef>()
+        .register::<DrawSkiaRoundedRectComponentDef>()
+        .register::<DrawLayerComponentDef>()
+        .register::<ParticleEmitterComponentDef>()
+        .register::<CameraComponentDef>()
+        .register::<CameraTargetComponent>()
         .register::<RigidBodyBallComponentDef>()
         .register::<RigidBodyBoxComponentDef>()
         .register::<TransformComponentDef>()
         .build()
 }
 
+/// Create the layers that skia draw components can be placed on
+pub fn create_draw_layers() -> DrawLayersResource {
+    let mut draw_layers = DrawLayersResource::new();
+    draw_layers.add_layer(-1, "Background", glam::Vec2::new(1.0, 1.0));
+    draw_layers.add_layer(0, "Default", glam::Vec2::new(1.0, 1.0));
+    draw_layers.add_layer(1, "Foreground", glam::Vec2::new(1.0, 1.0));
+    draw_layers
+}
+
 pub struct DemoApp {
     update_schedules: HashMap<ScheduleCriteria, Schedule>,
     draw_schedules: HashMap<ScheduleCriteria, Schedule>,
@@ -149,11 +203,10 @@ impl app::AppHandler for DemoApp {
             glam::Vec2::new(window_size.width as f32, window_size.height as f32);
 
         let mut viewport = ViewportResource::empty();
-        example_shared::viewport::update_viewport(
+        minimum_skulpin::viewport::update_viewport(
             &mut viewport,
             viewport_size_in_pixels,
-            camera_resource.position,
-            camera_resource.x_half_extents,
+            &camera_resource,
         );
 
         resources.insert(EditorInspectRegistryResource::new(
@@ -165,30 +218,32 @@ impl app::AppHandler for DemoApp {
         resources.insert(ComponentRegistryResource::new(create_component_registry()));
         resources.insert(physics);
         resources.insert(FpsTextResource::new());
+
+        // Gameplay input bindings, edit assets/input.inputmap to change them while running
+        let mut input_map = minimum::resources::InputMapResource::new();
+        input_map.load(
+            &asset_manager,
+            asset_uuid!("f5865098-3f9b-4575-b3da-97cce49fb3ed"),
+        );
+        resources.insert(input_map);
         resources.insert(asset_manager);
         resources.insert(EditorStateResource::new());
+        resources.insert(EditorCameraResource::new());
         resources.insert(camera_resource);
+        resources.insert(create_draw_layers());
         resources.insert(viewport);
         resources.insert(DebugDraw2DResource::new());
         resources.insert(DebugDraw3DResource::new());
         resources.insert(EditorDraw3DResource::new());
 
-        use minimum_winit::input::WinitKeyboardKey;
-        use skulpin::winit::event::VirtualKeyCode;
-        let keybinds = minimum::resources::editor::Keybinds {
-            selection_add: WinitKeyboardKey::new(VirtualKeyCode::LShift).into(),
-            selection_subtract: WinitKeyboardKey::new(VirtualKeyCode::LAlt).into(),
-            selection_toggle: WinitKeyboardKey::new(VirtualKeyCode::LControl).into(),
-            tool_translate: WinitKeyboardKey::new(VirtualKeyCode::Key1).into(),
-            tool_scale: WinitKeyboardKey::new(VirtualKeyCode::Key2).into(),
-            tool_rotate: WinitKeyboardKey::new(VirtualKeyCode::Key3).into(),
-            action_quit: WinitKeyboardKey::new(VirtualKeyCode::Escape).into(),
-            action_toggle_editor_pause: WinitKeyboardKey::new(VirtualKeyCode::Space).into(),
-        };
-
-        resources.insert(minimum::resources::editor::EditorSettingsResource::new(
-            keybinds,
-        ));
+        let keybinds = minimum::resources::editor::Keybinds::default();
+
+        // Bindings saved from the keybinds window override the defaults
+        let mut editor_settings = minimum::resources::editor::EditorSettingsResource::new(keybinds);
+        if let Err(error) = editor_settings.load_keybinds("keybinds.ron") {
+            log::warn!("Failed to load keybinds: {:?}", error);
+        }
+        resources.insert(editor_settings);
 
         // Start the application
         EditorStateResource::open_prefab(
@@ -1,8 +1,7 @@
 use legion::*;
-use skulpin::winit::event::VirtualKeyCode;
 use minimum::resources::InputResource;
 use minimum::resources::AppControlResource;
-use minimum_winit::input::WinitKeyboardKey;
+use minimum::input::KeyboardKey;
 
 pub fn quit_if_escape_pressed(schedule: &mut legion::systems::Builder) {
     schedule.add_system(
@@ -10,7 +9,7 @@ pub fn quit_if_escape_pressed(schedule: &mut legion::systems::Builder) {
             .read_resource::<InputResource>()
             .write_resource::<AppControlResource>()
             .build(|_, _, (input_state, app_control), _| {
-                if input_state.is_key_down(WinitKeyboardKey::new(VirtualKeyCode::Escape).into()) {
+                if input_state.is_key_down(KeyboardKey::Escape) {
                     app_control.enqueue_terminate_process();
                 }
             }),
@@ -0,0 +1,365 @@
+use minimum_game::resources::{CameraProjection, CameraResource, ViewportResource};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum EditorCameraMode {
+    // Orthographic view of the XY plane. Dragging pans and scrolling zooms
+    Pan2D,
+    // Rotates around a pivot point. Dragging orbits, middle dragging pans and scrolling moves
+    // toward the pivot
+    Orbit,
+    // Dragging looks around and the movement keys move the camera
+    Fly,
+}
+
+// Directions the 3D editor camera can be snapped to look from
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum EditorCameraView {
+    // Looking down -Z, the same as the 2D view
+    Top,
+    // Looking along +Y
+    Front,
+    // Looking along -X
+    Side,
+}
+
+// Camera used while the editor is active. It is kept separate from the game's camera so that
+// moving around while editing doesn't change the game's view, and playing doesn't lose the
+// editor's view.
+//
+// The 3D modes are Z-up. The camera looks from pivot - forward * distance toward the pivot, where
+// forward is determined by yaw (around Z) and pitch (0 looks straight down, PI/2 looks level)
+pub struct EditorCameraResource {
+    pub mode: EditorCameraMode,
+
+    // Pan2D
+    pub position: glam::Vec2,
+    pub x_half_extents: f32,
+
+    // Orbit and Fly
+    pub projection: CameraProjection,
+    pub pivot: glam::Vec3,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub distance: f32,
+    // Vertical field of view in degrees. Also used to size the orthographic view so that
+    // switching projection keeps things at the pivot the same size
+    pub fov_y: f32,
+    // World units per second
+    pub fly_speed: f32,
+
+    // The editor camera starts where the game camera was the first time the editor is activated
+    initialized: bool,
+
+    // The game's view from before the editor took over the camera. It's put back when the editor
+    // becomes inactive
+    saved_game_camera: Option<CameraResource>,
+}
+
+impl Default for EditorCameraResource {
+    fn default() -> Self {
+        EditorCameraResource {
+            mode: EditorCameraMode::Pan2D,
+            position: glam::Vec2::zero(),
+            x_half_extents: 10.0,
+            projection: CameraProjection::Perspective,
+            pivot: glam::Vec3::zero(),
+            yaw: 0.0,
+            pitch: std::f32::consts::FRAC_PI_4,
+            distance: 20.0,
+            fov_y: 60.0,
+            fly_speed: 10.0,
+            initialized: false,
+            saved_game_camera: None,
+        }
+    }
+}
+
+impl EditorCameraResource {
+    // Limits on how far in or out the editor camera can zoom
+    const MIN_X_HALF_EXTENTS: f32 = 0.01;
+    const MAX_X_HALF_EXTENTS: f32 = 100_000.0;
+    const MIN_DISTANCE: f32 = 0.01;
+    const MAX_DISTANCE: f32 = 100_000.0;
+
+    const NEAR_CLIP: f32 = 0.01;
+    const FAR_CLIP: f32 = 200_000.0;
+
+    // Extra space left around the selection when framing it
+    const FRAME_MARGIN: f32 = 1.2;
+
+    // Framing a point or a very small selection shouldn't zoom in all the way
+    const MIN_FRAMED_X_HALF_EXTENTS: f32 = 1.0;
+
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn is_overriding_game_camera(&self) -> bool {
+        self.saved_game_camera.is_some()
+    }
+
+    // Switches between Pan2D, Orbit and Fly
+    pub fn cycle_mode(&mut self) {
+        self.mode = match self.mode {
+            EditorCameraMode::Pan2D => {
+                // Start orbiting around whatever was in the middle of the 2D view
+                self.pivot = self.position.extend(0.0);
+                EditorCameraMode::Orbit
+            }
+            EditorCameraMode::Orbit => EditorCameraMode::Fly,
+            EditorCameraMode::Fly => {
+                self.position = self.pivot.truncate();
+                EditorCameraMode::Pan2D
+            }
+        };
+    }
+
+    pub fn toggle_projection(&mut self) {
+        self.projection = match self.projection {
+            CameraProjection::Orthographic => CameraProjection::Perspective,
+            CameraProjection::Perspective => CameraProjection::Orthographic,
+        };
+    }
+
+    pub fn rotation(&self) -> glam::Quat {
+        glam::Quat::from_rotation_z(self.yaw) * glam::Quat::from_rotation_x(self.pitch)
+    }
+
+    pub fn forward(&self) -> glam::Vec3 {
+        self.rotation() * glam::Vec3::new(0.0, 0.0, -1.0)
+    }
+
+    pub fn right(&self) -> glam::Vec3 {
+        self.rotation() * glam::Vec3::new(1.0, 0.0, 0.0)
+    }
+
+    pub fn up(&self) -> glam::Vec3 {
+        self.rotation() * glam::Vec3::new(0.0, 1.0, 0.0)
+    }
+
+    pub fn eye_position(&self) -> glam::Vec3 {
+        self.pivot - self.forward() * self.distance
+    }
+
+    // Half the height of the view at the pivot
+    fn half_height_at_pivot(&self) -> f32 {
+        self.distance * (self.fov_y.to_radians() * 0.5).tan()
+    }
+
+    // Replaces the game's view with the editor camera, saving the game's view so that it can be
+    // restored later. Call every frame while the editor is active
+    pub fn override_game_camera(
+        &mut self,
+        camera: &mut CameraResource,
+        viewport: &ViewportResource,
+    ) {
+        if !self.initialized {
+            self.position = camera.position;
+            self.x_half_extents = camera.x_half_extents;
+            self.initialized = true;
+        }
+
+        if self.saved_game_camera.is_none() {
+            self.saved_game_camera = Some(camera.clone());
+        }
+
+        camera.near_clip = Self::NEAR_CLIP;
+        camera.far_clip = Self::FAR_CLIP;
+
+        match self.mode {
+            EditorCameraMode::Pan2D => {
+                camera.projection = CameraProjection::Orthographic;
+                camera.position = self.position;
+                camera.height = 0.0;
+                camera.rotation = glam::Quat::identity();
+                camera.x_half_extents = self.x_half_extents;
+            }
+            EditorCameraMode::Orbit | EditorCameraMode::Fly => {
+                let viewport_size = viewport.viewport_size_in_pixels();
+                let aspect_ratio = if viewport_size.y() > 0.0 {
+                    viewport_size.x() / viewport_size.y()
+                } else {
+                    1.0
+                };
+
+                let eye = self.eye_position();
+                camera.projection = self.projection;
+                camera.position = eye.truncate();
+                camera.height = eye.z();
+                camera.rotation = self.rotation();
+                camera.fov_y = self.fov_y;
+                camera.x_half_extents = self.half_height_at_pivot() * aspect_ratio;
+            }
+        }
+    }
+
+    // Puts back the game's view from before override_game_camera was called. The active camera
+    // is left as is in case it was changed while the editor was active
+    pub fn restore_game_camera(
+        &mut self,
+        camera: &mut CameraResource,
+    ) {
+        if let Some(saved_game_camera) = self.saved_game_camera.take() {
+            let requested_camera = camera.requested_camera();
+            *camera = saved_game_camera;
+            camera.set_active_camera(requested_camera);
+        }
+    }
+
+    // World units per pixel in the viewport, measured at the pivot for the 3D modes
+    fn world_units_per_pixel(
+        &self,
+        viewport: &ViewportResource,
+    ) -> f32 {
+        let viewport_size = viewport.viewport_size_in_pixels();
+        match self.mode {
+            EditorCameraMode::Pan2D if viewport_size.x() > 0.0 => {
+                (self.x_half_extents * 2.0) / viewport_size.x()
+            }
+            EditorCameraMode::Orbit | EditorCameraMode::Fly if viewport_size.y() > 0.0 => {
+                (self.half_height_at_pivot() * 2.0) / viewport_size.y()
+            }
+            _ => 0.0,
+        }
+    }
+
+    // Moves the camera so that the world follows the mouse. The delta is in pixels
+    pub fn pan(
+        &mut self,
+        viewport_delta: glam::Vec2,
+        viewport: &ViewportResource,
+    ) {
+        // Viewport space has Y pointing down
+        let world_units_per_pixel = self.world_units_per_pixel(viewport);
+        match self.mode {
+            EditorCameraMode::Pan2D => {
+                self.position -= glam::Vec2::new(viewport_delta.x(), -viewport_delta.y())
+                    * world_units_per_pixel;
+            }
+            EditorCameraMode::Orbit | EditorCameraMode::Fly => {
+                self.pivot -= (self.right() * viewport_delta.x() - self.up() * viewport_delta.y())
+                    * world_units_per_pixel;
+            }
+        }
+    }
+
+    // Rotates the camera around the pivot. The delta is in pixels
+    pub fn orbit(
+        &mut self,
+        viewport_delta: glam::Vec2,
+    ) {
+        const RADIANS_PER_PIXEL: f32 = 0.005;
+        self.yaw -= viewport_delta.x() * RADIANS_PER_PIXEL;
+        self.pitch = (self.pitch - viewport_delta.y() * RADIANS_PER_PIXEL)
+            .max(0.0)
+            .min(std::f32::consts::PI);
+    }
+
+    // Rotates the camera around its own position. The delta is in pixels
+    pub fn look(
+        &mut self,
+        viewport_delta: glam::Vec2,
+    ) {
+        let eye = self.eye_position();
+        self.orbit(-viewport_delta);
+        self.pivot = eye + self.forward() * self.distance;
+    }
+
+    // Moves the camera relative to the direction it's facing. x is right, y is up and z is forward
+    pub fn fly(
+        &mut self,
+        direction: glam::Vec3,
+        dt: f32,
+    ) {
+        let offset = self.right() * direction.x()
+            + self.up() * direction.y()
+            + self.forward() * direction.z();
+        self.pivot += offset * self.fly_speed * dt;
+    }
+
+    // Scales the view by zoom_factor (less than 1 zooms in) while keeping the point under the
+    // cursor in the same place on screen. In the 3D modes, this moves toward the pivot instead
+    pub fn zoom_toward(
+        &mut self,
+        viewport_position: glam::Vec2,
+        zoom_factor: f32,
+        viewport: &ViewportResource,
+    ) {
+        match self.mode {
+            EditorCameraMode::Pan2D => {
+                let old_x_half_extents = self.x_half_extents;
+                self.x_half_extents = (self.x_half_extents * zoom_factor)
+                    .max(Self::MIN_X_HALF_EXTENTS)
+                    .min(Self::MAX_X_HALF_EXTENTS);
+
+                let zoom_factor = self.x_half_extents / old_x_half_extents;
+                let cursor = viewport
+                    .viewport_space_to_world_space(viewport_position, 0.0)
+                    .truncate();
+                self.position = cursor + (self.position - cursor) * zoom_factor;
+            }
+            EditorCameraMode::Orbit => {
+                self.distance = (self.distance * zoom_factor)
+                    .max(Self::MIN_DISTANCE)
+                    .min(Self::MAX_DISTANCE);
+            }
+            EditorCameraMode::Fly => {
+                self.fly_speed /= zoom_factor;
+            }
+        }
+    }
+
+    // Centers the camera on the given bounds and zooms so that they fit in the viewport
+    pub fn frame(
+        &mut self,
+        min: glam::Vec3,
+        max: glam::Vec3,
+        viewport: &ViewportResource,
+    ) {
+        let viewport_size = viewport.viewport_size_in_pixels();
+        let aspect_ratio = if viewport_size.y() > 0.0 {
+            viewport_size.x() / viewport_size.y()
+        } else {
+            1.0
+        };
+
+        let half_size = (max - min) * 0.5;
+        let x_half_extents = half_size.x().max(half_size.y() * aspect_ratio) * Self::FRAME_MARGIN;
+        let x_half_extents = x_half_extents
+            .max(Self::MIN_FRAMED_X_HALF_EXTENTS)
+            .min(Self::MAX_X_HALF_EXTENTS);
+
+        match self.mode {
+            EditorCameraMode::Pan2D => {
+                self.position = ((min + max) * 0.5).truncate();
+                self.x_half_extents = x_half_extents;
+            }
+            EditorCameraMode::Orbit | EditorCameraMode::Fly => {
+                // Fit the bounding sphere of the bounds in the vertical field of view
+                let radius =
+                    (half_size.length() * Self::FRAME_MARGIN).max(Self::MIN_FRAMED_X_HALF_EXTENTS);
+                let half_fov = (self.fov_y.to_radians() * 0.5).max(0.01);
+
+                self.pivot = (min + max) * 0.5;
+                self.distance = (radius / half_fov.sin())
+                    .max(Self::MIN_DISTANCE)
+                    .min(Self::MAX_DISTANCE);
+            }
+        }
+    }
+
+    // Snaps the 3D camera to look from the given direction, keeping the pivot and distance
+    pub fn snap_to_view(
+        &mut self,
+        view: EditorCameraView,
+    ) {
+        let (yaw, pitch) = match view {
+            EditorCameraView::Top => (0.0, 0.0),
+            EditorCameraView::Front => (0.0, std::f32::consts::FRAC_PI_2),
+            EditorCameraView::Side => (std::f32::consts::FRAC_PI_2, std::f32::consts::FRAC_PI_2),
+        };
+
+        self.yaw = yaw;
+        self.pitch = pitch;
+    }
+}
@@ -25,6 +25,20 @@ pub enum EditorDraw3DConstraint {
 }
 
 impl EditorDraw3DConstraint {
+    pub fn line(
+        basis: glam::Vec3,
+        dir: glam::Vec3,
+    ) -> Self {
+        EditorDraw3DConstraint::Line(LineConstraint { basis, dir })
+    }
+
+    pub fn plane(
+        basis: glam::Vec3,
+        normal: glam::Vec3,
+    ) -> Self {
+        EditorDraw3DConstraint::Plane(PlaneConstraint { basis, normal })
+    }
+
     pub fn x_line(basis: glam::Vec3) -> Self {
         EditorDraw3DConstraint::Line(LineConstraint {
             basis,
@@ -23,6 +23,9 @@ pub struct EditorSelectionResource {
     // These are entities in the world
     selected_entities: HashSet<Entity>,
 
+    // The first of the selected entities to be selected. Gizmos can use it as the pivot
+    active_entity: Option<Entity>,
+
     pending_selection_ops: Vec<SelectionOp>,
 }
 
@@ -34,6 +37,7 @@ impl EditorSelectionResource {
             registry: Arc::new(registry),
             editor_selection_world,
             selected_entities: Default::default(),
+            active_entity: None,
             pending_selection_ops: Default::default(),
         }
     }
@@ -62,6 +66,12 @@ impl EditorSelectionResource {
         &self.selected_entities
     }
 
+    // The first entity that was selected. If it has since been deselected, one of the other
+    // selected entities takes its place
+    pub fn active_entity(&self) -> Option<Entity> {
+        self.active_entity
+    }
+
     pub fn selected_entity_aabbs(&mut self) -> HashMap<Entity, Option<AABB<f32>>> {
         Self::get_entity_aabbs(&self.selected_entities, &self.editor_selection_world)
     }
@@ -118,6 +128,9 @@ impl EditorSelectionResource {
                     let mut changed = false;
                     for e in entities {
                         changed |= self.selected_entities.insert(e);
+                        if self.active_entity.is_none() {
+                            self.active_entity = Some(e);
+                        }
                     }
 
                     changed
@@ -126,11 +139,15 @@ impl EditorSelectionResource {
                     let mut changed = false;
                     for e in entities {
                         changed |= self.selected_entities.remove(&e);
+                        if self.active_entity == Some(e) {
+                            self.active_entity = self.selected_entities.iter().next().copied();
+                        }
                     }
 
                     changed
                 }
                 SelectionOp::Clear => {
+                    self.active_entity = None;
                     if !self.selected_entities.is_empty() {
                         self.selected_entities.clear();
                         true
@@ -139,6 +156,7 @@ impl EditorSelectionResource {
                     }
                 }
                 SelectionOp::Set(entities) => {
+                    self.active_entity = entities.first().copied();
                     self.selected_entities = entities.iter().copied().collect();
                     true
                 }
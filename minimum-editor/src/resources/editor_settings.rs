@@ -1,28 +1,131 @@
-use minimum_game::input::KeyboardKey;
+use std::path::PathBuf;
 
-pub struct Keybinds {
-    pub selection_add: KeyboardKey,
-    pub selection_subtract: KeyboardKey,
-    pub selection_toggle: KeyboardKey,
+use crate::resources::{Keybinds, KeybindsFileError};
 
-    pub tool_translate: KeyboardKey,
-    pub tool_scale: KeyboardKey,
-    pub tool_rotate: KeyboardKey,
+// Increments that the gizmos move, rotate and scale by while snapping is enabled
+pub struct SnapSettings {
+    pub enabled: bool,
+    // World units. Also the spacing of the grid
+    pub translate_grid_size: f32,
+    // Degrees
+    pub rotate_increment: f32,
+    pub scale_step: f32,
+    pub show_grid: bool,
+}
+
+impl Default for SnapSettings {
+    fn default() -> Self {
+        SnapSettings {
+            enabled: false,
+            translate_grid_size: 1.0,
+            rotate_increment: 15.0,
+            scale_step: 0.1,
+            show_grid: true,
+        }
+    }
+}
+
+impl SnapSettings {
+    // Rounds each component of the position to the nearest grid line
+    pub fn snap_position_to_grid(
+        &self,
+        position: glam::Vec3,
+    ) -> glam::Vec3 {
+        glam::Vec3::new(
+            Self::snap_to_increment(position.x(), self.translate_grid_size),
+            Self::snap_to_increment(position.y(), self.translate_grid_size),
+            Self::snap_to_increment(position.z(), self.translate_grid_size),
+        )
+    }
 
-    pub action_quit: KeyboardKey,
-    pub action_toggle_editor_pause: KeyboardKey,
+    // Rounds the value to the nearest multiple of increment. An increment of 0 or less disables
+    // snapping
+    pub fn snap_to_increment(
+        value: f32,
+        increment: f32,
+    ) -> f32 {
+        if increment > 0.0 {
+            (value / increment).round() * increment
+        } else {
+            value
+        }
+    }
+
+    // Converts a per-frame change into a change that keeps the total so far a multiple of
+    // increment. accumulated is the total change including this frame's previous_frame_delta.
+    // This lets a drag that is applied a frame at a time move in whole increments
+    pub fn snap_frame_delta_to_increment(
+        accumulated: f32,
+        previous_frame_delta: f32,
+        increment: f32,
+    ) -> f32 {
+        if increment > 0.0 {
+            Self::snap_to_increment(accumulated, increment)
+                - Self::snap_to_increment(accumulated - previous_frame_delta, increment)
+        } else {
+            previous_frame_delta
+        }
+    }
 }
 
 pub struct EditorSettingsResource {
     keybinds: Keybinds,
+    // Where keybinds are loaded from and saved to
+    keybinds_path: Option<PathBuf>,
+    snap_settings: SnapSettings,
 }
 
 impl EditorSettingsResource {
     pub fn new(keybinds: Keybinds) -> Self {
-        EditorSettingsResource { keybinds }
+        EditorSettingsResource {
+            keybinds,
+            keybinds_path: None,
+            snap_settings: Default::default(),
+        }
     }
 
     pub fn keybinds(&self) -> &Keybinds {
         &self.keybinds
     }
+
+    pub fn keybinds_mut(&mut self) -> &mut Keybinds {
+        &mut self.keybinds
+    }
+
+    pub fn keybinds_path(&self) -> Option<&PathBuf> {
+        self.keybinds_path.as_ref()
+    }
+
+    // Overrides the current keybinds with the ones in the file, if it exists. Saving writes to
+    // the same file
+    pub fn load_keybinds<P: Into<PathBuf>>(
+        &mut self,
+        path: P,
+    ) -> Result<(), KeybindsFileError> {
+        let path = path.into();
+        let result = if path.exists() {
+            self.keybinds.load(&path)
+        } else {
+            Ok(())
+        };
+
+        self.keybinds_path = Some(path);
+        result
+    }
+
+    pub fn save_keybinds(&self) -> Result<(), KeybindsFileError> {
+        if let Some(path) = &self.keybinds_path {
+            self.keybinds.save(path)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn snap_settings(&self) -> &SnapSettings {
+        &self.snap_settings
+    }
+
+    pub fn snap_settings_mut(&mut self) -> &mut SnapSettings {
+        &mut self.snap_settings
+    }
 }
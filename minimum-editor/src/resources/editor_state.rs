@@ -4,7 +4,8 @@ use legion::*;
 use minimum_kernel::resources::{AssetResource, ComponentRegistryResource};
 use minimum_kernel::pipeline::PrefabAsset;
 use minimum_game::resources::TimeResource;
-use crate::resources::EditorSelectionResource;
+use crate::resources::{EditorSelectionResource, EditorSettingsResource};
+use minimum_transform::components::TransformComponentDef;
 use minimum_game::resources::SimulationTimePauseReason;
 use atelier_assets::core::AssetUuid;
 use legion_prefab::{CookedPrefab, Prefab};
@@ -59,6 +60,15 @@ enum EditorOp {
 
     /// Sets the current editor tool (translate, scale, etc.)
     SetActiveEditorTool(EditorTool),
+
+    /// Moves the selected entities to the nearest grid point (as an undo step)
+    SnapSelectionToGrid,
+
+    /// Copies the selected entities and selects the copies
+    DuplicateSelection,
+
+    /// Deletes the selected entities
+    DeleteSelection,
 }
 
 /// Tracks which windows are open
@@ -68,6 +78,8 @@ pub struct WindowOptions {
     pub show_imgui_demo: bool,
     pub show_entity_list: bool,
     pub show_inspector: bool,
+    pub show_transform_toolbar: bool,
+    pub show_keybinds: bool,
 }
 
 impl WindowOptions {
@@ -78,6 +90,8 @@ impl WindowOptions {
             show_imgui_demo: false,
             show_entity_list: false,
             show_inspector: false,
+            show_transform_toolbar: false,
+            show_keybinds: false,
         }
     }
 
@@ -92,6 +106,7 @@ impl WindowOptions {
         let mut options = Self::new();
         options.show_entity_list = true;
         options.show_inspector = true;
+        options.show_transform_toolbar = true;
         options
     }
 }
@@ -104,6 +119,68 @@ pub enum EditorTool {
     Rotate,
 }
 
+// Which axes the gizmos move, scale and rotate along
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum GizmoSpace {
+    World,
+    // Oriented by the rotation of the entity the gizmo is drawn for
+    Local,
+}
+
+// Where the gizmos are drawn when multiple entities are selected, and the point that rotating and
+// scaling happens around
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum GizmoPivot {
+    // One gizmo at the average position of the selected entities
+    SelectionCenter,
+    // A gizmo for each selected entity. Each entity rotates and scales around its own origin
+    IndividualOrigins,
+    // One gizmo at the active entity (see EditorSelectionResource::active_entity)
+    ActiveEntity,
+}
+
+// Arrow key nudges that haven't been committed yet. Nudges that happen in quick succession are
+// committed together as a single undo step
+pub struct NudgeBurst {
+    pub transaction: EditorTransaction,
+    // The selection when the burst started. If the selection changes, the burst is committed
+    pub selected_entities: HashSet<Entity>,
+    pub last_nudge_instant: std::time::Instant,
+}
+
+// Text typed into the transform toolbar's fields. Fields that aren't being edited are refreshed
+// from the selection every frame
+#[derive(Default)]
+pub struct TransformToolbarState {
+    pub fields: Vec<ImString>,
+    pub editing_field: Option<usize>,
+}
+
+// The entity being renamed in the entity list, if any, and the name typed so far
+#[derive(Default)]
+pub struct EntityListState {
+    pub renaming_entity: Option<Entity>,
+    pub rename_text: ImString,
+    // The text field takes keyboard focus on the first frame it's shown
+    pub focus_rename_field: bool,
+}
+
+impl EntityListState {
+    pub fn start_rename(
+        &mut self,
+        entity: Entity,
+        name: &str,
+    ) {
+        self.renaming_entity = Some(entity);
+        self.rename_text = ImString::new(name);
+        self.focus_rename_field = true;
+    }
+
+    pub fn stop_rename(&mut self) {
+        self.renaming_entity = None;
+    }
+}
+
 #[derive(PartialEq, Debug, Copy, Clone, Eq, Hash)]
 pub enum EditorMode {
     Inactive,
@@ -186,7 +263,11 @@ pub struct EditorStateResource {
     window_options_running: WindowOptions,
     window_options_editing: WindowOptions,
     active_editor_tool: EditorTool,
+    gizmo_space: GizmoSpace,
+    gizmo_pivot: GizmoPivot,
     pub add_component_search_text: ImString,
+    pub transform_toolbar: TransformToolbarState,
+    pub entity_list: EntityListState,
 
     // If a prefab is opened, this holds the state associated with editing it
     opened_prefab: Option<Arc<OpenedPrefabState>>,
@@ -209,6 +290,13 @@ pub struct EditorStateResource {
     // position, rotation, scaling)
     gizmo_transaction: Option<EditorTransaction>,
 
+    // How far the rotate gizmo has turned during the gizmo transaction. This is summed a frame at
+    // a time so that it keeps counting past half a turn
+    gizmo_rotate_angle: f32,
+
+    // Arrow key nudges that will be committed once the user stops nudging
+    nudge_burst: Option<NudgeBurst>,
+
     // If a transaction is in progress, the data required to identify it and commit it is
     // stored here. The ID is used to determine if a transaction provided by downstream code
     // is the same as the one that's currently in progress. If it isn't the same, we commit
@@ -230,7 +318,11 @@ impl EditorStateResource {
             window_options_running: WindowOptions::new_runtime(),
             window_options_editing: WindowOptions::new_editing(),
             active_editor_tool: EditorTool::Translate,
+            gizmo_space: GizmoSpace::World,
+            gizmo_pivot: GizmoPivot::IndividualOrigins,
             add_component_search_text: ImString::with_capacity(255),
+            transform_toolbar: Default::default(),
+            entity_list: Default::default(),
             opened_prefab: None,
             pending_editor_ops: Default::default(),
 
@@ -240,6 +332,8 @@ impl EditorStateResource {
             undo_chain_position: 0,
 
             gizmo_transaction: None,
+            gizmo_rotate_angle: 0.0,
+            nudge_burst: None,
 
             current_transaction_info: None,
         }
@@ -281,6 +375,26 @@ impl EditorStateResource {
         &mut self.gizmo_transaction
     }
 
+    pub fn gizmo_rotate_angle_mut(&mut self) -> &mut f32 {
+        &mut self.gizmo_rotate_angle
+    }
+
+    pub fn nudge_burst_mut(&mut self) -> &mut Option<NudgeBurst> {
+        &mut self.nudge_burst
+    }
+
+    /// Returns true if the transaction has been updated but not committed. Updating or committing
+    /// a different transaction commits the one in progress.
+    pub fn is_transaction_in_progress(
+        &self,
+        transaction: &EditorTransaction,
+    ) -> bool {
+        match &self.current_transaction_info {
+            Some(info) => info.id == transaction.id,
+            None => false,
+        }
+    }
+
     fn play(
         &mut self,
         time_state: &mut TimeResource,
@@ -466,6 +580,43 @@ impl EditorStateResource {
         self.active_editor_tool
     }
 
+    pub fn gizmo_space(&self) -> GizmoSpace {
+        self.gizmo_space
+    }
+
+    pub fn set_gizmo_space(
+        &mut self,
+        gizmo_space: GizmoSpace,
+    ) {
+        self.gizmo_space = gizmo_space;
+    }
+
+    pub fn gizmo_pivot(&self) -> GizmoPivot {
+        self.gizmo_pivot
+    }
+
+    pub fn set_gizmo_pivot(
+        &mut self,
+        gizmo_pivot: GizmoPivot,
+    ) {
+        self.gizmo_pivot = gizmo_pivot;
+    }
+
+    pub fn toggle_gizmo_space(&mut self) {
+        self.gizmo_space = match self.gizmo_space {
+            GizmoSpace::World => GizmoSpace::Local,
+            GizmoSpace::Local => GizmoSpace::World,
+        };
+    }
+
+    pub fn cycle_gizmo_pivot(&mut self) {
+        self.gizmo_pivot = match self.gizmo_pivot {
+            GizmoPivot::IndividualOrigins => GizmoPivot::SelectionCenter,
+            GizmoPivot::SelectionCenter => GizmoPivot::ActiveEntity,
+            GizmoPivot::ActiveEntity => GizmoPivot::IndividualOrigins,
+        };
+    }
+
     pub fn enqueue_save_prefab(&mut self) {
         self.pending_editor_ops.push(EditorOp::SavePrefab);
     }
@@ -502,6 +653,18 @@ impl EditorStateResource {
         self.pending_editor_ops.push(EditorOp::Redo);
     }
 
+    pub fn enqueue_snap_selection_to_grid(&mut self) {
+        self.pending_editor_ops.push(EditorOp::SnapSelectionToGrid);
+    }
+
+    pub fn enqueue_duplicate_selection(&mut self) {
+        self.pending_editor_ops.push(EditorOp::DuplicateSelection);
+    }
+
+    pub fn enqueue_delete_selection(&mut self) {
+        self.pending_editor_ops.push(EditorOp::DeleteSelection);
+    }
+
     pub fn enqueue_set_active_editor_tool(
         &mut self,
         editor_tool: EditorTool,
@@ -573,7 +736,88 @@ impl EditorStateResource {
                 EditorOp::Redo => {
                     Self::redo(world, resources);
                 }
+                EditorOp::SnapSelectionToGrid => {
+                    Self::snap_selection_to_grid(resources);
+                }
+                EditorOp::DuplicateSelection => {
+                    Self::duplicate_selection(resources);
+                }
+                EditorOp::DeleteSelection => {
+                    Self::delete_selection(resources);
+                }
+            }
+        }
+    }
+
+    fn snap_selection_to_grid(resources: &Resources) {
+        let mut editor_state = resources.get_mut::<EditorStateResource>().unwrap();
+        let editor_selection = resources.get::<EditorSelectionResource>().unwrap();
+        let editor_settings = resources.get::<EditorSettingsResource>().unwrap();
+        let component_registry = resources.get::<ComponentRegistryResource>().unwrap();
+        let asset_resource = resources.get::<AssetResource>().unwrap();
+
+        let tx =
+            editor_state.create_transaction_from_selected(&*editor_selection, &*component_registry);
+        if let Some(mut tx) = tx {
+            let mut query = <Write<TransformComponentDef>>::query();
+            for transform in query.iter_mut(tx.world_mut()) {
+                let position = editor_settings
+                    .snap_settings()
+                    .snap_position_to_grid(transform.position());
+                *transform.position_mut() = position;
             }
+
+            tx.commit(
+                &*asset_resource,
+                &mut *editor_state,
+                PostCommitSelection::KeepCurrentSelection,
+                &*component_registry,
+            );
+        }
+    }
+
+    fn duplicate_selection(resources: &Resources) {
+        let mut editor_state = resources.get_mut::<EditorStateResource>().unwrap();
+        let editor_selection = resources.get::<EditorSelectionResource>().unwrap();
+        let component_registry = resources.get::<ComponentRegistryResource>().unwrap();
+        let asset_resource = resources.get::<AssetResource>().unwrap();
+
+        let selected_tx =
+            editor_state.create_transaction_from_selected(&*editor_selection, &*component_registry);
+        let empty_tx = editor_state.create_empty_transaction(&*component_registry);
+        if let (Some(selected_tx), Some(mut tx)) = (selected_tx, empty_tx) {
+            // The copies are the only entities in this transaction, so only they will be selected
+            tx.world_mut().clone_from(
+                selected_tx.world(),
+                &legion::query::any(),
+                &mut component_registry.copy_clone_impl(),
+            );
+
+            tx.commit(
+                &*asset_resource,
+                &mut *editor_state,
+                PostCommitSelection::SelectAllInTransaction,
+                &*component_registry,
+            );
+        }
+    }
+
+    fn delete_selection(resources: &Resources) {
+        let mut editor_state = resources.get_mut::<EditorStateResource>().unwrap();
+        let editor_selection = resources.get::<EditorSelectionResource>().unwrap();
+        let component_registry = resources.get::<ComponentRegistryResource>().unwrap();
+        let asset_resource = resources.get::<AssetResource>().unwrap();
+
+        let tx =
+            editor_state.create_transaction_from_selected(&*editor_selection, &*component_registry);
+        if let Some(mut tx) = tx {
+            tx.world_mut().clear();
+            tx.commit(
+                &*asset_resource,
+                &mut *editor_state,
+                PostCommitSelection::KeepCurrentSelection,
+                &*component_registry,
+            );
         }
     }
 
@@ -0,0 +1,474 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use minimum_game::input::{InputState, KeyboardKey, KeyModifiers};
+use minimum_game::pipeline::{InputMapAction, InputMapButton, InputMapContext};
+use minimum_game::resources::InputMapResource;
+
+// Everything in the editor that can be bound to a key. The names are used in keybind files
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+pub enum EditorAction {
+    // These are checked while clicking to select, so they are usually bound to modifier keys
+    SelectionAdd,
+    SelectionSubtract,
+    SelectionToggle,
+
+    ToolTranslate,
+    ToolScale,
+    ToolRotate,
+
+    Undo,
+    Redo,
+    Save,
+    Duplicate,
+    Delete,
+    FrameSelection,
+    ToggleSnap,
+    ToggleGizmoSpace,
+    CycleGizmoPivot,
+    ToggleEditorPause,
+    Quit,
+
+    NudgeLeft,
+    NudgeRight,
+    NudgeUp,
+    NudgeDown,
+    NudgeRaise,
+    NudgeLower,
+
+    CameraCycleMode,
+    CameraToggleProjection,
+    CameraViewTop,
+    CameraViewFront,
+    CameraViewSide,
+    CameraForward,
+    CameraBack,
+    CameraLeft,
+    CameraRight,
+    CameraUp,
+    CameraDown,
+}
+
+impl EditorAction {
+    pub const ALL: [EditorAction; 34] = [
+        EditorAction::SelectionAdd,
+        EditorAction::SelectionSubtract,
+        EditorAction::SelectionToggle,
+        EditorAction::ToolTranslate,
+        EditorAction::ToolScale,
+        EditorAction::ToolRotate,
+        EditorAction::Undo,
+        EditorAction::Redo,
+        EditorAction::Save,
+        EditorAction::Duplicate,
+        EditorAction::Delete,
+        EditorAction::FrameSelection,
+        EditorAction::ToggleSnap,
+        EditorAction::ToggleGizmoSpace,
+        EditorAction::CycleGizmoPivot,
+        EditorAction::ToggleEditorPause,
+        EditorAction::Quit,
+        EditorAction::NudgeLeft,
+        EditorAction::NudgeRight,
+        EditorAction::NudgeUp,
+        EditorAction::NudgeDown,
+        EditorAction::NudgeRaise,
+        EditorAction::NudgeLower,
+        EditorAction::CameraCycleMode,
+        EditorAction::CameraToggleProjection,
+        EditorAction::CameraViewTop,
+        EditorAction::CameraViewFront,
+        EditorAction::CameraViewSide,
+        EditorAction::CameraForward,
+        EditorAction::CameraBack,
+        EditorAction::CameraLeft,
+        EditorAction::CameraRight,
+        EditorAction::CameraUp,
+        EditorAction::CameraDown,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            EditorAction::SelectionAdd => "selection_add",
+            EditorAction::SelectionSubtract => "selection_subtract",
+            EditorAction::SelectionToggle => "selection_toggle",
+            EditorAction::ToolTranslate => "tool_translate",
+            EditorAction::ToolScale => "tool_scale",
+            EditorAction::ToolRotate => "tool_rotate",
+            EditorAction::Undo => "undo",
+            EditorAction::Redo => "redo",
+            EditorAction::Save => "save",
+            EditorAction::Duplicate => "duplicate",
+            EditorAction::Delete => "delete",
+            EditorAction::FrameSelection => "frame_selection",
+            EditorAction::ToggleSnap => "toggle_snap",
+            EditorAction::ToggleGizmoSpace => "toggle_gizmo_space",
+            EditorAction::CycleGizmoPivot => "cycle_gizmo_pivot",
+            EditorAction::ToggleEditorPause => "toggle_editor_pause",
+            EditorAction::Quit => "quit",
+            EditorAction::NudgeLeft => "nudge_left",
+            EditorAction::NudgeRight => "nudge_right",
+            EditorAction::NudgeUp => "nudge_up",
+            EditorAction::NudgeDown => "nudge_down",
+            EditorAction::NudgeRaise => "nudge_raise",
+            EditorAction::NudgeLower => "nudge_lower",
+            EditorAction::CameraCycleMode => "camera_cycle_mode",
+            EditorAction::CameraToggleProjection => "camera_toggle_projection",
+            EditorAction::CameraViewTop => "camera_view_top",
+            EditorAction::CameraViewFront => "camera_view_front",
+            EditorAction::CameraViewSide => "camera_view_side",
+            EditorAction::CameraForward => "camera_forward",
+            EditorAction::CameraBack => "camera_back",
+            EditorAction::CameraLeft => "camera_left",
+            EditorAction::CameraRight => "camera_right",
+            EditorAction::CameraUp => "camera_up",
+            EditorAction::CameraDown => "camera_down",
+        }
+    }
+
+    // The input map context the action is bound in. Most actions are in the editor context so
+    // they don't get in the way of gameplay input.
+    pub fn input_map_context(self) -> &'static str {
+        match self {
+            EditorAction::ToggleEditorPause | EditorAction::Quit => {
+                InputMapResource::GLOBAL_CONTEXT
+            }
+            _ => InputMapResource::EDITOR_CONTEXT,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<EditorAction> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|action| action.name() == name)
+    }
+}
+
+// A key and the modifiers that must be held with it. Other modifiers must not be held, so
+// ctrl+z and z can be bound to different actions
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct KeyChord {
+    pub key: KeyboardKey,
+    #[serde(default)]
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    pub fn new(key: KeyboardKey) -> Self {
+        KeyChord {
+            key,
+            modifiers: Default::default(),
+        }
+    }
+
+    pub fn with_ctrl(mut self) -> Self {
+        self.modifiers.ctrl = true;
+        self
+    }
+
+    pub fn with_shift(mut self) -> Self {
+        self.modifiers.shift = true;
+        self
+    }
+
+    pub fn with_alt(mut self) -> Self {
+        self.modifiers.alt = true;
+        self
+    }
+
+    pub fn with_logo(mut self) -> Self {
+        self.modifiers.logo = true;
+        self
+    }
+}
+
+impl Into<InputMapButton> for KeyChord {
+    fn into(self) -> InputMapButton {
+        InputMapButton::KeyChord(self.key, self.modifiers)
+    }
+}
+
+impl std::fmt::Display for KeyChord {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        if self.modifiers.ctrl {
+            write!(f, "Ctrl+")?;
+        }
+
+        if self.modifiers.shift {
+            write!(f, "Shift+")?;
+        }
+
+        if self.modifiers.alt {
+            write!(f, "Alt+")?;
+        }
+
+        if self.modifiers.logo {
+            write!(f, "Logo+")?;
+        }
+
+        write!(f, "{:?}", self.key)
+    }
+}
+
+#[derive(Debug)]
+pub enum KeybindsFileError {
+    Io(std::io::Error),
+    Deserialize(ron::de::Error),
+    Serialize(ron::ser::Error),
+}
+
+impl From<std::io::Error> for KeybindsFileError {
+    fn from(error: std::io::Error) -> Self {
+        KeybindsFileError::Io(error)
+    }
+}
+
+impl From<ron::de::Error> for KeybindsFileError {
+    fn from(error: ron::de::Error) -> Self {
+        KeybindsFileError::Deserialize(error)
+    }
+}
+
+impl From<ron::ser::Error> for KeybindsFileError {
+    fn from(error: ron::ser::Error) -> Self {
+        KeybindsFileError::Serialize(error)
+    }
+}
+
+// Maps editor actions to key chords. The chords are bound in the input map (see
+// input_map_contexts()) and actions are read back from it, so they only trigger while their context
+// is active. Actions without a binding only trigger if the input map asset binds them.
+pub struct Keybinds {
+    bindings: HashMap<EditorAction, KeyChord>,
+
+    // If set, the next key pressed is bound to this action instead of triggering anything
+    rebinding_action: Option<EditorAction>,
+
+    // Changes whenever a binding changes so the input map is only updated when needed
+    revision: u64,
+}
+
+// The bindings the editor starts with. Bindings loaded from a file replace these
+impl Default for Keybinds {
+    fn default() -> Self {
+        let key = KeyChord::new;
+        Keybinds::new()
+            .with_binding(EditorAction::SelectionAdd, key(KeyboardKey::LShift))
+            .with_binding(EditorAction::SelectionSubtract, key(KeyboardKey::LAlt))
+            .with_binding(EditorAction::SelectionToggle, key(KeyboardKey::LCtrl))
+            .with_binding(EditorAction::ToolTranslate, key(KeyboardKey::Key1))
+            .with_binding(EditorAction::ToolScale, key(KeyboardKey::Key2))
+            .with_binding(EditorAction::ToolRotate, key(KeyboardKey::Key3))
+            .with_binding(EditorAction::FrameSelection, key(KeyboardKey::F))
+            .with_binding(EditorAction::Undo, key(KeyboardKey::Z).with_ctrl())
+            .with_binding(EditorAction::Redo, key(KeyboardKey::Y).with_ctrl())
+            .with_binding(EditorAction::Save, key(KeyboardKey::S).with_ctrl())
+            .with_binding(EditorAction::Duplicate, key(KeyboardKey::D).with_ctrl())
+            .with_binding(EditorAction::Delete, key(KeyboardKey::Delete))
+            .with_binding(EditorAction::ToggleSnap, key(KeyboardKey::G))
+            .with_binding(EditorAction::ToggleGizmoSpace, key(KeyboardKey::L))
+            .with_binding(EditorAction::CycleGizmoPivot, key(KeyboardKey::V))
+            .with_binding(EditorAction::NudgeLeft, key(KeyboardKey::Left))
+            .with_binding(EditorAction::NudgeRight, key(KeyboardKey::Right))
+            .with_binding(EditorAction::NudgeUp, key(KeyboardKey::Up))
+            .with_binding(EditorAction::NudgeDown, key(KeyboardKey::Down))
+            .with_binding(EditorAction::NudgeRaise, key(KeyboardKey::PageUp))
+            .with_binding(EditorAction::NudgeLower, key(KeyboardKey::PageDown))
+            .with_binding(EditorAction::CameraCycleMode, key(KeyboardKey::C))
+            .with_binding(EditorAction::CameraToggleProjection, key(KeyboardKey::P))
+            .with_binding(EditorAction::CameraViewTop, key(KeyboardKey::Key7))
+            .with_binding(EditorAction::CameraViewFront, key(KeyboardKey::Key8))
+            .with_binding(EditorAction::CameraViewSide, key(KeyboardKey::Key9))
+            .with_binding(EditorAction::CameraForward, key(KeyboardKey::W))
+            .with_binding(EditorAction::CameraBack, key(KeyboardKey::S))
+            .with_binding(EditorAction::CameraLeft, key(KeyboardKey::A))
+            .with_binding(EditorAction::CameraRight, key(KeyboardKey::D))
+            .with_binding(EditorAction::CameraUp, key(KeyboardKey::E))
+            .with_binding(EditorAction::CameraDown, key(KeyboardKey::Q))
+            .with_binding(EditorAction::Quit, key(KeyboardKey::Escape))
+            .with_binding(EditorAction::ToggleEditorPause, key(KeyboardKey::Space))
+    }
+}
+
+impl Keybinds {
+    pub fn new() -> Self {
+        Keybinds {
+            bindings: Default::default(),
+            rebinding_action: None,
+            revision: 0,
+        }
+    }
+
+    pub fn with_binding(
+        mut self,
+        action: EditorAction,
+        chord: KeyChord,
+    ) -> Self {
+        self.set_binding(action, Some(chord));
+        self
+    }
+
+    pub fn binding(
+        &self,
+        action: EditorAction,
+    ) -> Option<KeyChord> {
+        self.bindings.get(&action).copied()
+    }
+
+    pub fn set_binding(
+        &mut self,
+        action: EditorAction,
+        chord: Option<KeyChord>,
+    ) {
+        if let Some(chord) = chord {
+            self.bindings.insert(action, chord);
+        } else {
+            self.bindings.remove(&action);
+        }
+
+        self.revision += 1;
+    }
+
+    // Returns true while the action is held in the input map
+    pub fn is_action_down(
+        &self,
+        input_map: &InputMapResource,
+        action: EditorAction,
+    ) -> bool {
+        self.rebinding_action.is_none() && input_map.is_action_down(action.name())
+    }
+
+    // Returns true if the action started being held in the input map this frame
+    pub fn is_action_just_down(
+        &self,
+        input_map: &InputMapResource,
+        action: EditorAction,
+    ) -> bool {
+        self.rebinding_action.is_none() && input_map.is_action_just_down(action.name())
+    }
+
+    // Returns true if the action started being held this frame or its key repeated while held
+    pub fn is_action_just_down_or_repeated(
+        &self,
+        input_map: &InputMapResource,
+        action: EditorAction,
+    ) -> bool {
+        self.rebinding_action.is_none() && input_map.is_action_just_down_or_repeated(action.name())
+    }
+
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    // The bindings as input map contexts, one per context that actions are bound in. Set these as
+    // runtime contexts on the InputMapResource.
+    pub fn input_map_contexts(&self) -> Vec<InputMapContext> {
+        let context_names = [
+            InputMapResource::EDITOR_CONTEXT,
+            InputMapResource::GLOBAL_CONTEXT,
+        ];
+
+        context_names
+            .iter()
+            .map(|context_name| InputMapContext {
+                name: context_name.to_string(),
+                actions: EditorAction::ALL
+                    .iter()
+                    .filter(|action| action.input_map_context() == *context_name)
+                    .filter_map(|action| {
+                        self.binding(*action).map(|chord| InputMapAction {
+                            name: action.name().to_string(),
+                            bindings: vec![chord.into()],
+                        })
+                    })
+                    .collect(),
+                axes: Vec::new(),
+                axes_2d: Vec::new(),
+            })
+            .collect()
+    }
+
+    pub fn rebinding_action(&self) -> Option<EditorAction> {
+        self.rebinding_action
+    }
+
+    // Starts waiting for a key to bind to the action. No actions trigger until it is bound or
+    // cancelled
+    pub fn begin_rebind(
+        &mut self,
+        action: EditorAction,
+    ) {
+        self.rebinding_action = Some(action);
+    }
+
+    pub fn cancel_rebind(&mut self) {
+        self.rebinding_action = None;
+    }
+
+    // Call every frame while rebinding. The first non-modifier key pressed is bound along with the
+    // held modifiers. A modifier key can be bound by itself by pressing and releasing it.
+    pub fn update_rebind(
+        &mut self,
+        input_state: &InputState,
+    ) {
+        let action = match self.rebinding_action {
+            Some(action) => action,
+            None => return,
+        };
+
+        let key = input_state
+            .keys_just_down()
+            .find(|key| !key.is_modifier())
+            .or_else(|| input_state.keys_just_up().find(|key| key.is_modifier()));
+
+        if let Some(key) = key {
+            let chord = KeyChord {
+                key,
+                modifiers: input_state.modifiers().without_key(key),
+            };
+
+            log::info!("Bound {} to {}", action.name(), chord);
+            self.set_binding(action, Some(chord));
+            self.rebinding_action = None;
+        }
+    }
+
+    // Replaces bindings with the ones in the file. Actions that aren't in the file keep their
+    // current binding and actions set to None in the file are unbound
+    pub fn load<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<(), KeybindsFileError> {
+        let contents = std::fs::read_to_string(path)?;
+        let bindings: BTreeMap<String, Option<KeyChord>> = ron::de::from_str(&contents)?;
+
+        for (name, chord) in bindings {
+            match EditorAction::from_name(&name) {
+                Some(action) => self.set_binding(action, chord),
+                None => log::warn!("Ignoring keybind for unknown action {}", name),
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn save<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<(), KeybindsFileError> {
+        let bindings: BTreeMap<String, Option<KeyChord>> = EditorAction::ALL
+            .iter()
+            .map(|action| (action.name().to_string(), self.binding(*action)))
+            .collect();
+
+        let contents = ron::ser::to_string_pretty(&bindings, ron::ser::PrettyConfig::default())?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+}
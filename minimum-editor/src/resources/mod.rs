@@ -2,10 +2,15 @@ mod editor_state;
 pub use editor_state::PostCommitSelection;
 pub use editor_state::EditorStateResource;
 pub use editor_state::EditorTool;
+pub use editor_state::GizmoSpace;
+pub use editor_state::GizmoPivot;
 pub use editor_state::EditorMode;
 pub use editor_state::EditorTransactionId;
 pub use editor_state::EditorTransaction;
 pub use editor_state::OpenedPrefabState;
+pub use editor_state::NudgeBurst;
+pub use editor_state::TransformToolbarState;
+pub use editor_state::EntityListState;
 
 mod editor_selection;
 pub use editor_selection::EditorSelectionResource;
@@ -24,6 +29,15 @@ pub use editor_draw_3d::EditorDraw3DConstraint;
 mod editor_inspect_registry;
 pub use editor_inspect_registry::EditorInspectRegistryResource;
 
+mod keybinds;
+pub use keybinds::Keybinds;
+pub use keybinds::EditorAction;
+pub use keybinds::KeyChord;
+pub use keybinds::KeybindsFileError;
+
 mod editor_settings;
-pub use editor_settings::Keybinds;
 pub use editor_settings::EditorSettingsResource;
+pub use editor_settings::SnapSettings;
+
+mod editor_camera;
+pub use editor_camera::{EditorCameraResource, EditorCameraMode, EditorCameraView};
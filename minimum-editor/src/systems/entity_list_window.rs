@@ -1,8 +1,9 @@
 use legion::*;
 
-use minimum_game::resources::{InputResource};
+use minimum_game::resources::InputMapResource;
 use crate::resources::{
     EditorStateResource, EditorSelectionResource, PostCommitSelection, EditorSettingsResource,
+    EditorAction,
 };
 use minimum_game::resources::ImguiResource;
 
@@ -18,7 +19,7 @@ pub fn editor_entity_list_window(schedule: &mut legion::systems::Builder) {
             .write_resource::<ImguiResource>()
             .write_resource::<EditorStateResource>()
             .write_resource::<EditorSelectionResource>()
-            .read_resource::<InputResource>()
+            .read_resource::<InputMapResource>()
             .read_resource::<ComponentRegistryResource>()
             .read_resource::<EditorSettingsResource>()
             .read_resource::<AssetResource>()
@@ -30,7 +31,7 @@ pub fn editor_entity_list_window(schedule: &mut legion::systems::Builder) {
                     imgui_manager,
                     editor_ui_state,
                     editor_selection,
-                    input,
+                    input_map,
                     component_registry,
                     editor_settings,
                     asset_resource,
@@ -66,20 +67,7 @@ pub fn editor_entity_list_window(schedule: &mut legion::systems::Builder) {
                                     }
 
                                     if remove_entity {
-                                        if let Some(mut tx) = editor_ui_state
-                                            .create_transaction_from_selected(
-                                                &*editor_selection,
-                                                &*component_registry,
-                                            )
-                                        {
-                                            tx.world_mut().clear();
-                                            tx.commit(
-                                                &*asset_resource,
-                                                &mut *editor_ui_state,
-                                                PostCommitSelection::KeepCurrentSelection,
-                                                &*component_registry,
-                                            );
-                                        }
+                                        editor_ui_state.enqueue_delete_selection();
                                     }
 
                                     let name = im_str!("");
@@ -89,10 +77,48 @@ pub fn editor_entity_list_window(schedule: &mut legion::systems::Builder) {
                                             imgui::sys::ImVec2 { x: -1.0, y: -1.0 },
                                         )
                                     } {
+                                        let mut renamed = None;
                                         for (e, editor_metadata) in all_query.iter(world) {
                                             let is_selected =
                                                 editor_selection.is_entity_selected(*e);
 
+                                            let entity_list = &mut editor_ui_state.entity_list;
+                                            if entity_list.renaming_entity == Some(*e) {
+                                                if entity_list.focus_rename_field {
+                                                    ui.set_keyboard_focus_here(
+                                                        imgui::FocusedWidget::Next,
+                                                    );
+                                                }
+
+                                                ui.set_next_item_width(-1.0);
+                                                let entered = ui
+                                                    .input_text(
+                                                        im_str!("##rename_entity"),
+                                                        &mut entity_list.rename_text,
+                                                    )
+                                                    .resize_buffer(true)
+                                                    .enter_returns_true(true)
+                                                    .build();
+
+                                                // Clicking elsewhere or pressing escape cancels
+                                                if entered {
+                                                    renamed = Some(
+                                                        entity_list
+                                                            .rename_text
+                                                            .to_str()
+                                                            .to_string(),
+                                                    );
+                                                    entity_list.stop_rename();
+                                                } else if !entity_list.focus_rename_field
+                                                    && !ui.is_item_active()
+                                                {
+                                                    entity_list.stop_rename();
+                                                }
+
+                                                entity_list.focus_rename_field = false;
+                                                continue;
+                                            }
+
                                             let s = if let Some(editor_metadata) = editor_metadata {
                                                 im_str!("{:?}", editor_metadata.name)
                                             } else {
@@ -103,11 +129,32 @@ pub fn editor_entity_list_window(schedule: &mut legion::systems::Builder) {
                                                 .selected(is_selected)
                                                 .build(ui);
 
+                                            // imgui handles mouse input over its windows, so the
+                                            // double click comes from imgui rather than
+                                            // InputState. The examples configure imgui with the
+                                            // same MouseClickSettings before each frame.
+                                            if ui.is_item_hovered()
+                                                && ui.is_mouse_double_clicked(
+                                                    imgui::MouseButton::Left,
+                                                )
+                                            {
+                                                let name = editor_metadata
+                                                    .map(|editor_metadata| {
+                                                        editor_metadata.name.as_str()
+                                                    })
+                                                    .unwrap_or("");
+                                                editor_ui_state.entity_list.start_rename(*e, name);
+                                                editor_selection.enqueue_set_selection(vec![*e]);
+                                                continue;
+                                            }
+
                                             if clicked {
                                                 //TODO: Hook up keyboard controls
-                                                let is_control_held = input.is_key_down(
-                                                    editor_settings.keybinds().selection_toggle,
-                                                );
+                                                let is_control_held =
+                                                    editor_settings.keybinds().is_action_down(
+                                                        input_map,
+                                                        EditorAction::SelectionToggle,
+                                                    );
                                                 if is_control_held {
                                                     if !is_selected {
                                                         // Add this entity
@@ -131,6 +178,18 @@ pub fn editor_entity_list_window(schedule: &mut legion::systems::Builder) {
                                         unsafe {
                                             imgui::sys::igListBoxFooter();
                                         }
+
+                                        // The renamed entity was selected when the rename
+                                        // started
+                                        if let Some(name) = renamed {
+                                            rename_selection(
+                                                name,
+                                                &mut *editor_ui_state,
+                                                &*editor_selection,
+                                                &*component_registry,
+                                                &*asset_resource,
+                                            );
+                                        }
                                     }
                                 });
                         }
@@ -139,3 +198,31 @@ pub fn editor_entity_list_window(schedule: &mut legion::systems::Builder) {
             ),
     );
 }
+
+fn rename_selection(
+    name: String,
+    editor_state: &mut EditorStateResource,
+    editor_selection: &EditorSelectionResource,
+    component_registry: &ComponentRegistryResource,
+    asset_resource: &AssetResource,
+) {
+    let tx = editor_state.create_transaction_from_selected(editor_selection, component_registry);
+    if let Some(mut tx) = tx {
+        let entities: Vec<Entity> = <Entity>::query().iter(tx.world()).copied().collect();
+        for entity in entities {
+            if let Some(mut entry) = tx.world_mut().entry(entity) {
+                match entry.get_component_mut::<EditorMetadataComponent>() {
+                    Ok(editor_metadata) => editor_metadata.name = name.clone(),
+                    Err(_) => entry.add_component(EditorMetadataComponent { name: name.clone() }),
+                }
+            }
+        }
+
+        tx.commit(
+            asset_resource,
+            editor_state,
+            PostCommitSelection::KeepCurrentSelection,
+            component_registry,
+        );
+    }
+}
@@ -3,7 +3,8 @@ use legion::*;
 use minimum_game::resources::{InputResource, ViewportResource, DebugDraw3DResource};
 use crate::resources::{
     EditorStateResource, EditorSelectionResource, EditorDraw3DResource, EditorDraw3DConstraint,
-    EditorTransaction, PostCommitSelection,
+    EditorTransaction, PostCommitSelection, EditorSettingsResource, SnapSettings, GizmoSpace,
+    GizmoPivot,
 };
 use crate::resources::EditorTool;
 
@@ -20,6 +21,8 @@ use legion::world::SubWorld;
 
 //TODO: Adapt the size of "hot" area around the editor drawn shapes based on zoom level
 
+const ROTATE_GIZMO_SEGMENTS: u32 = 32;
+
 // The DefaultFilter is EntityFilterTuple<ComponentFilter<TransformComponent>, Passthrough>
 type TransformQuery = Query<
     (Entity, Read<TransformComponent>),
@@ -38,6 +41,7 @@ pub fn editor_gizmos(schedule: &mut legion::systems::Builder) {
             .write_resource::<EditorDraw3DResource>()
             .read_resource::<ComponentRegistryResource>()
             .read_resource::<AssetResource>()
+            .read_resource::<EditorSettingsResource>()
             .with_query(<(Entity, Read<TransformComponent>)>::query())
             .build(
                 |_command_buffer,
@@ -52,28 +56,63 @@ pub fn editor_gizmos(schedule: &mut legion::systems::Builder) {
                     editor_draw,
                     component_registry,
                     asset_resource,
+                    editor_settings,
                 ),
                  transform_query| {
                     let mut gizmo_tx = None;
                     std::mem::swap(&mut gizmo_tx, editor_state.gizmo_transaction_mut());
 
                     if gizmo_tx.is_none() {
+                        // A drag that starts now starts a new transaction
+                        *editor_state.gizmo_rotate_angle_mut() = 0.0;
                         gizmo_tx = editor_state.create_transaction_from_selected(
                             &*editor_selection,
                             &*component_registry,
                         );
                     }
 
+                    let placements = gizmo_placements(
+                        &*editor_state,
+                        &*editor_selection,
+                        subworld,
+                        transform_query,
+                    );
+
+                    // Rotating and scaling around each entity's own origin doesn't need a pivot
+                    let pivot = match editor_state.gizmo_pivot() {
+                        GizmoPivot::IndividualOrigins => None,
+                        GizmoPivot::SelectionCenter | GizmoPivot::ActiveEntity => {
+                            placements.first().copied()
+                        }
+                    };
+
+                    let snap_settings = if editor_settings.snap_settings().enabled {
+                        Some(editor_settings.snap_settings())
+                    } else {
+                        None
+                    };
+
                     if let Some(mut gizmo_tx) = gizmo_tx {
                         let mut result = GizmoResult::NoChange;
                         result = result.max(handle_translate_gizmo_input(
                             &mut *editor_draw,
                             &mut gizmo_tx,
+                            snap_settings,
+                        ));
+                        result = result.max(handle_scale_gizmo_input(
+                            &mut *editor_draw,
+                            &mut gizmo_tx,
+                            snap_settings,
+                            pivot,
+                        ));
+                        result = result.max(handle_rotate_gizmo_input(
+                            &mut *editor_draw,
+                            &mut gizmo_tx,
+                            snap_settings,
+                            editor_state.gizmo_space(),
+                            pivot,
+                            editor_state.gizmo_rotate_angle_mut(),
                         ));
-                        result =
-                            result.max(handle_scale_gizmo_input(&mut *editor_draw, &mut gizmo_tx));
-                        result =
-                            result.max(handle_rotate_gizmo_input(&mut *editor_draw, &mut gizmo_tx));
 
                         match result {
                             GizmoResult::NoChange => {}
@@ -102,25 +141,19 @@ pub fn editor_gizmos(schedule: &mut legion::systems::Builder) {
                             &*viewport_resource,
                             &mut *debug_draw,
                             &mut *editor_draw,
-                            &mut *editor_selection,
-                            subworld,
-                            transform_query,
+                            &placements,
                         ),
                         EditorTool::Scale => draw_scale_gizmo(
                             &*viewport_resource,
                             &mut *debug_draw,
                             &mut *editor_draw,
-                            &mut *editor_selection,
-                            subworld,
-                            transform_query,
+                            &placements,
                         ),
                         EditorTool::Rotate => draw_rotate_gizmo(
                             &*viewport_resource,
                             &mut *debug_draw,
                             &mut *editor_draw,
-                            &mut *editor_selection,
-                            subworld,
-                            transform_query,
+                            &placements,
                         ),
                     }
                 },
@@ -135,9 +168,100 @@ enum GizmoResult {
     Commit,
 }
 
+// Where a gizmo is drawn and how its axes are oriented
+#[derive(Copy, Clone)]
+struct GizmoPlacement {
+    position: glam::Vec3,
+    rotation: glam::Quat,
+}
+
+// Determines where gizmos should be drawn for the selected entities, based on the gizmo space and
+// pivot mode
+fn gizmo_placements(
+    editor_state: &EditorStateResource,
+    editor_selection: &EditorSelectionResource,
+    subworld: &SubWorld,
+    transform_query: &mut TransformQuery,
+) -> Vec<GizmoPlacement> {
+    let gizmo_space = editor_state.gizmo_space();
+    let active_entity = editor_selection.active_entity();
+
+    let mut placements = Vec::new();
+    let mut active_placement = None;
+    for (entity, transform) in transform_query.iter(subworld) {
+        if !editor_selection.is_entity_selected(*entity) {
+            continue;
+        }
+
+        let placement = GizmoPlacement {
+            position: transform.position(),
+            rotation: match gizmo_space {
+                GizmoSpace::World => glam::Quat::identity(),
+                GizmoSpace::Local => transform.rotation(),
+            },
+        };
+
+        if active_entity == Some(*entity) {
+            active_placement = Some(placement);
+        }
+
+        placements.push(placement);
+    }
+
+    let active_placement = active_placement.or_else(|| placements.first().copied());
+
+    match editor_state.gizmo_pivot() {
+        GizmoPivot::IndividualOrigins => placements,
+        GizmoPivot::SelectionCenter => {
+            if placements.is_empty() {
+                return placements;
+            }
+
+            let position_sum = placements
+                .iter()
+                .fold(glam::Vec3::zero(), |sum, placement| {
+                    sum + placement.position
+                });
+
+            // In local space, the gizmo is oriented like the active entity
+            vec![GizmoPlacement {
+                position: position_sum / placements.len() as f32,
+                rotation: active_placement.unwrap().rotation,
+            }]
+        }
+        GizmoPivot::ActiveEntity => active_placement.into_iter().collect(),
+    }
+}
+
+// Applies snap_frame_delta_to_increment to each component
+fn snap_frame_delta_to_increment_3d(
+    accumulated: glam::Vec3,
+    previous_frame_delta: glam::Vec3,
+    increment: f32,
+) -> glam::Vec3 {
+    glam::Vec3::new(
+        SnapSettings::snap_frame_delta_to_increment(
+            accumulated.x(),
+            previous_frame_delta.x(),
+            increment,
+        ),
+        SnapSettings::snap_frame_delta_to_increment(
+            accumulated.y(),
+            previous_frame_delta.y(),
+            increment,
+        ),
+        SnapSettings::snap_frame_delta_to_increment(
+            accumulated.z(),
+            previous_frame_delta.z(),
+            increment,
+        ),
+    )
+}
+
 fn handle_translate_gizmo_input(
     editor_draw: &mut EditorDraw3DResource,
     tx: &mut EditorTransaction,
+    snap_settings: Option<&SnapSettings>,
 ) -> GizmoResult {
     if let Some(drag_in_progress) =
         editor_draw.shape_drag_in_progress_or_just_finished(MouseButton::LEFT)
@@ -169,7 +293,31 @@ fn handle_translate_gizmo_input(
         }
 
         // Determine the drag distance in ui_space
-        let world_space_previous_frame_delta = drag_in_progress.world_space_previous_frame_delta;
+        let mut world_space_previous_frame_delta =
+            drag_in_progress.world_space_previous_frame_delta;
+
+        // Move in whole grid cells, measured from where the drag started
+        if let Some(snap_settings) = snap_settings {
+            world_space_previous_frame_delta = match drag_in_progress.constraint {
+                // Measure along the axis so that snapping still works if it's rotated in local
+                // space
+                EditorDraw3DConstraint::Line(line) => {
+                    let distance = SnapSettings::snap_frame_delta_to_increment(
+                        drag_in_progress
+                            .world_space_accumulated_frame_delta
+                            .dot(line.dir),
+                        world_space_previous_frame_delta.dot(line.dir),
+                        snap_settings.translate_grid_size,
+                    );
+                    line.dir * distance
+                }
+                EditorDraw3DConstraint::Plane(_) => snap_frame_delta_to_increment_3d(
+                    drag_in_progress.world_space_accumulated_frame_delta,
+                    world_space_previous_frame_delta,
+                    snap_settings.translate_grid_size,
+                ),
+            };
+        }
 
         let mut query = <(Entity, Write<TransformComponentDef>)>::query();
 
@@ -197,15 +345,9 @@ fn draw_translate_gizmo(
     viewport: &ViewportResource,
     debug_draw: &mut DebugDraw3DResource,
     editor_draw: &mut EditorDraw3DResource,
-    selection_world: &mut EditorSelectionResource,
-    subworld: &SubWorld,
-    transform_query: &mut TransformQuery,
+    placements: &[GizmoPlacement],
 ) {
-    for (entity, transform) in transform_query.iter(subworld) {
-        if !selection_world.is_entity_selected(*entity) {
-            continue;
-        }
-
+    for placement in placements {
         let x_color = glam::vec4(1.0, 0.0, 0.0, 1.0);
         let y_color = glam::vec4(0.0, 1.0, 0.0, 1.0);
         let z_color = glam::vec4(0.0, 0.0, 1.0, 1.0);
@@ -213,16 +355,24 @@ fn draw_translate_gizmo(
         let xz_color = glam::vec4(1.0, 0.0, 1.0, 1.0);
         let yz_color = glam::vec4(0.0, 1.0, 1.0, 1.0);
 
-        let position = transform.position();
+        let position = placement.position;
         let ui_multiplier = 0.005 * viewport.world_space_ui_multiplier(position);
 
+        let x_axis = placement.rotation * glam::Vec3::unit_x();
+        let y_axis = placement.rotation * glam::Vec3::unit_y();
+        let z_axis = placement.rotation * glam::Vec3::unit_z();
+
+        // Converts an offset in the gizmo's space to world space
+        let to_world =
+            |offset: glam::Vec3| position + placement.rotation * (offset * ui_multiplier);
+
         // x axis line
         editor_draw.add_line(
             "x_axis_translate",
             debug_draw,
             position,
-            position + (glam::vec3(100.0, 0.0, 0.0) * ui_multiplier),
-            EditorDraw3DConstraint::x_line(position),
+            to_world(glam::vec3(100.0, 0.0, 0.0)),
+            EditorDraw3DConstraint::line(position, x_axis),
             x_color,
             DebugDraw3DDepthBehavior::NoDepthTest,
         );
@@ -230,9 +380,9 @@ fn draw_translate_gizmo(
         editor_draw.add_line(
             "x_axis_translate",
             debug_draw,
-            position + (glam::vec3(85.0, 15.0, 0.0) * ui_multiplier),
-            position + (glam::vec3(100.0, 0.0, 0.0) * ui_multiplier),
-            EditorDraw3DConstraint::x_line(position),
+            to_world(glam::vec3(85.0, 15.0, 0.0)),
+            to_world(glam::vec3(100.0, 0.0, 0.0)),
+            EditorDraw3DConstraint::line(position, x_axis),
             x_color,
             DebugDraw3DDepthBehavior::NoDepthTest,
         );
@@ -240,9 +390,9 @@ fn draw_translate_gizmo(
         editor_draw.add_line(
             "x_axis_translate",
             debug_draw,
-            position + (glam::vec3(85.0, -15.0, 0.0) * ui_multiplier),
-            position + (glam::vec3(100.0, 0.0, 0.0) * ui_multiplier),
-            EditorDraw3DConstraint::x_line(position),
+            to_world(glam::vec3(85.0, -15.0, 0.0)),
+            to_world(glam::vec3(100.0, 0.0, 0.0)),
+            EditorDraw3DConstraint::line(position, x_axis),
             x_color,
             DebugDraw3DDepthBehavior::NoDepthTest,
         );
@@ -252,8 +402,8 @@ fn draw_translate_gizmo(
             "y_axis_translate",
             debug_draw,
             position,
-            position + (glam::vec3(0.0, 100.0, 0.0) * ui_multiplier),
-            EditorDraw3DConstraint::y_line(position),
+            to_world(glam::vec3(0.0, 100.0, 0.0)),
+            EditorDraw3DConstraint::line(position, y_axis),
             y_color,
             DebugDraw3DDepthBehavior::NoDepthTest,
         );
@@ -261,9 +411,9 @@ fn draw_translate_gizmo(
         editor_draw.add_line(
             "y_axis_translate",
             debug_draw,
-            position + (glam::vec3(-15.0, 85.0, 0.0) * ui_multiplier),
-            position + (glam::vec3(0.0, 100.0, 0.0) * ui_multiplier),
-            EditorDraw3DConstraint::y_line(position),
+            to_world(glam::vec3(-15.0, 85.0, 0.0)),
+            to_world(glam::vec3(0.0, 100.0, 0.0)),
+            EditorDraw3DConstraint::line(position, y_axis),
             y_color,
             DebugDraw3DDepthBehavior::NoDepthTest,
         );
@@ -271,9 +421,9 @@ fn draw_translate_gizmo(
         editor_draw.add_line(
             "y_axis_translate",
             debug_draw,
-            position + (glam::vec3(15.0, 85.0, 0.0) * ui_multiplier),
-            position + (glam::vec3(0.0, 100.0, 0.0) * ui_multiplier),
-            EditorDraw3DConstraint::y_line(position),
+            to_world(glam::vec3(15.0, 85.0, 0.0)),
+            to_world(glam::vec3(0.0, 100.0, 0.0)),
+            EditorDraw3DConstraint::line(position, y_axis),
             y_color,
             DebugDraw3DDepthBehavior::NoDepthTest,
         );
@@ -283,8 +433,8 @@ fn draw_translate_gizmo(
             "z_axis_translate",
             debug_draw,
             position,
-            position + (glam::vec3(0.0, 0.0, 100.0) * ui_multiplier),
-            EditorDraw3DConstraint::z_line(position),
+            to_world(glam::vec3(0.0, 0.0, 100.0)),
+            EditorDraw3DConstraint::line(position, z_axis),
             z_color,
             DebugDraw3DDepthBehavior::NoDepthTest,
         );
@@ -292,9 +442,9 @@ fn draw_translate_gizmo(
         editor_draw.add_line(
             "z_axis_translate",
             debug_draw,
-            position + (glam::vec3(0.0, -15.0, 85.0) * ui_multiplier),
-            position + (glam::vec3(0.0, 0.0, 100.0) * ui_multiplier),
-            EditorDraw3DConstraint::z_line(position),
+            to_world(glam::vec3(0.0, -15.0, 85.0)),
+            to_world(glam::vec3(0.0, 0.0, 100.0)),
+            EditorDraw3DConstraint::line(position, z_axis),
             z_color,
             DebugDraw3DDepthBehavior::NoDepthTest,
         );
@@ -302,9 +452,9 @@ fn draw_translate_gizmo(
         editor_draw.add_line(
             "z_axis_translate",
             debug_draw,
-            position + (glam::vec3(0.0, 15.0, 85.0) * ui_multiplier),
-            position + (glam::vec3(0.0, 0.0, 100.0) * ui_multiplier),
-            EditorDraw3DConstraint::z_line(position),
+            to_world(glam::vec3(0.0, 15.0, 85.0)),
+            to_world(glam::vec3(0.0, 0.0, 100.0)),
+            EditorDraw3DConstraint::line(position, z_axis),
             z_color,
             DebugDraw3DDepthBehavior::NoDepthTest,
         );
@@ -313,9 +463,9 @@ fn draw_translate_gizmo(
         editor_draw.add_line(
             "xy_axis_translate",
             debug_draw,
-            position + (glam::vec3(0.0, 25.0, 0.0) * ui_multiplier),
-            position + (glam::vec3(25.0, 25.0, 0.0) * ui_multiplier),
-            EditorDraw3DConstraint::xy_plane(position),
+            to_world(glam::vec3(0.0, 25.0, 0.0)),
+            to_world(glam::vec3(25.0, 25.0, 0.0)),
+            EditorDraw3DConstraint::plane(position, z_axis),
             xy_color,
             DebugDraw3DDepthBehavior::NoDepthTest,
         );
@@ -323,9 +473,9 @@ fn draw_translate_gizmo(
         editor_draw.add_line(
             "xy_axis_translate",
             debug_draw,
-            position + (glam::vec3(25.0, 0.0, 0.0) * ui_multiplier),
-            position + (glam::vec3(25.0, 25.0, 0.0) * ui_multiplier),
-            EditorDraw3DConstraint::xy_plane(position),
+            to_world(glam::vec3(25.0, 0.0, 0.0)),
+            to_world(glam::vec3(25.0, 25.0, 0.0)),
+            EditorDraw3DConstraint::plane(position, z_axis),
             xy_color,
             DebugDraw3DDepthBehavior::NoDepthTest,
         );
@@ -334,9 +484,9 @@ fn draw_translate_gizmo(
         editor_draw.add_line(
             "xz_axis_translate",
             debug_draw,
-            position + (glam::vec3(0.0, 0.0, 25.0) * ui_multiplier),
-            position + (glam::vec3(25.0, 0.0, 25.0) * ui_multiplier),
-            EditorDraw3DConstraint::xz_plane(position),
+            to_world(glam::vec3(0.0, 0.0, 25.0)),
+            to_world(glam::vec3(25.0, 0.0, 25.0)),
+            EditorDraw3DConstraint::plane(position, y_axis),
             xz_color,
             DebugDraw3DDepthBehavior::NoDepthTest,
         );
@@ -344,9 +494,9 @@ fn draw_translate_gizmo(
         editor_draw.add_line(
             "xz_axis_translate",
             debug_draw,
-            position + (glam::vec3(25.0, 0.0, 0.0) * ui_multiplier),
-            position + (glam::vec3(25.0, 0.0, 25.0) * ui_multiplier),
-            EditorDraw3DConstraint::xz_plane(position),
+            to_world(glam::vec3(25.0, 0.0, 0.0)),
+            to_world(glam::vec3(25.0, 0.0, 25.0)),
+            EditorDraw3DConstraint::plane(position, y_axis),
             xz_color,
             DebugDraw3DDepthBehavior::NoDepthTest,
         );
@@ -355,9 +505,9 @@ fn draw_translate_gizmo(
         editor_draw.add_line(
             "yz_axis_translate",
             debug_draw,
-            position + (glam::vec3(0.0, 0.0, 25.0) * ui_multiplier),
-            position + (glam::vec3(0.0, 25.0, 25.0) * ui_multiplier),
-            EditorDraw3DConstraint::yz_plane(position),
+            to_world(glam::vec3(0.0, 0.0, 25.0)),
+            to_world(glam::vec3(0.0, 25.0, 25.0)),
+            EditorDraw3DConstraint::plane(position, x_axis),
             yz_color,
             DebugDraw3DDepthBehavior::NoDepthTest,
         );
@@ -365,9 +515,9 @@ fn draw_translate_gizmo(
         editor_draw.add_line(
             "yz_axis_translate",
             debug_draw,
-            position + (glam::vec3(0.0, 25.0, 0.0) * ui_multiplier),
-            position + (glam::vec3(0.0, 25.0, 25.0) * ui_multiplier),
-            EditorDraw3DConstraint::yz_plane(position),
+            to_world(glam::vec3(0.0, 25.0, 0.0)),
+            to_world(glam::vec3(0.0, 25.0, 25.0)),
+            EditorDraw3DConstraint::plane(position, x_axis),
             yz_color,
             DebugDraw3DDepthBehavior::NoDepthTest,
         );
@@ -404,6 +554,8 @@ fn sign_aware_magnitude(v: glam::Vec3) -> f32 {
 fn handle_scale_gizmo_input(
     editor_draw: &mut EditorDraw3DResource,
     tx: &mut EditorTransaction,
+    snap_settings: Option<&SnapSettings>,
+    pivot: Option<GizmoPlacement>,
 ) -> GizmoResult {
     if let Some(drag_in_progress) =
         editor_draw.shape_drag_in_progress_or_just_finished(MouseButton::LEFT)
@@ -428,45 +580,53 @@ fn handle_scale_gizmo_input(
             return GizmoResult::NoChange;
         }
 
-        // Determine the drag distance
-        let mut ui_space_previous_frame_delta = drag_in_progress.world_space_previous_frame_delta;
-
-        if !scale_x && !scale_uniform {
-            ui_space_previous_frame_delta.set_x(0.0);
-        }
-
-        if !scale_y && !scale_uniform {
-            ui_space_previous_frame_delta.set_y(0.0);
-        }
-
-        if !scale_z && !scale_uniform {
-            ui_space_previous_frame_delta.set_z(0.0);
-        }
-
+        // Determine the drag distance along the dragged axis. Uniform scaling can be dragged in any
+        // direction
+        //
         // Pretty sure the sign_aware_magnitude is messing up the FP precision. Probably need to rethink
         // this as a UI-space circle around the basis
-        if scale_uniform {
-            let mag = sign_aware_magnitude(ui_space_previous_frame_delta);
-            ui_space_previous_frame_delta.set_x(mag);
-            ui_space_previous_frame_delta.set_y(mag);
-            ui_space_previous_frame_delta.set_z(mag);
+        let drag_distance = |delta: glam::Vec3| match drag_in_progress.constraint {
+            EditorDraw3DConstraint::Line(line) => delta.dot(line.dir),
+            EditorDraw3DConstraint::Plane(_) => sign_aware_magnitude(delta),
+        };
+
+        let mut ui_space_previous_frame_delta =
+            drag_distance(drag_in_progress.world_space_previous_frame_delta);
+
+        // Change the scale in whole steps, measured from the scale when the drag started
+        if let Some(snap_settings) = snap_settings {
+            ui_space_previous_frame_delta = SnapSettings::snap_frame_delta_to_increment(
+                drag_distance(drag_in_progress.world_space_accumulated_frame_delta),
+                ui_space_previous_frame_delta,
+                snap_settings.scale_step,
+            );
         }
 
-        if scale_uniform {
-            let mut query = <Write<TransformComponentDef>>::query();
-
-            for transform in query.iter_mut(tx.world_mut()) {
-                *transform.uniform_scale_mut() += ui_space_previous_frame_delta.x()
-            }
+        // The change in scale along each of the gizmo's axes
+        let scale_delta = if scale_uniform {
+            glam::Vec3::splat(ui_space_previous_frame_delta)
+        } else if scale_x {
+            glam::Vec3::new(ui_space_previous_frame_delta, 0.0, 0.0)
+        } else if scale_y {
+            glam::Vec3::new(0.0, ui_space_previous_frame_delta, 0.0)
         } else {
-            let mut query = <Write<TransformComponentDef>>::query();
+            glam::Vec3::new(0.0, 0.0, ui_space_previous_frame_delta)
+        };
+
+        let mut query = <Write<TransformComponentDef>>::query();
+        for transform in query.iter_mut(tx.world_mut()) {
+            if scale_uniform {
+                *transform.uniform_scale_mut() += ui_space_previous_frame_delta;
+            } else {
+                *transform.non_uniform_scale_mut() += scale_delta;
+            }
 
-            for non_uniform_scale in query.iter_mut(tx.world_mut()) {
-                *non_uniform_scale.non_uniform_scale += glam::Vec3::new(
-                    ui_space_previous_frame_delta.x(),
-                    ui_space_previous_frame_delta.y(),
-                    ui_space_previous_frame_delta.z(),
-                );
+            // Move the entity toward or away from the pivot, in the same proportion as an entity
+            // with a scale of 1 grows or shrinks
+            if let Some(pivot) = pivot {
+                let offset = pivot.rotation.conjugate() * (transform.position() - pivot.position);
+                let offset = offset * (glam::Vec3::one() + scale_delta);
+                *transform.position_mut() = pivot.position + pivot.rotation * offset;
             }
         }
 
@@ -484,16 +644,10 @@ fn draw_scale_gizmo(
     viewport: &ViewportResource,
     debug_draw: &mut DebugDraw3DResource,
     editor_draw: &mut EditorDraw3DResource,
-    selection_world: &mut EditorSelectionResource,
-    subworld: &SubWorld,
-    transform_query: &mut TransformQuery,
+    placements: &[GizmoPlacement],
 ) {
-    for (entity, transform) in transform_query.iter(subworld) {
-        if !selection_world.is_entity_selected(*entity) {
-            continue;
-        }
-
-        let position = transform.position();
+    for placement in placements {
+        let position = placement.position;
 
         let x_color = glam::Vec4::new(0.0, 1.0, 0.0, 1.0);
         let y_color = glam::Vec4::new(1.0, 0.6, 0.0, 1.0);
@@ -501,13 +655,21 @@ fn draw_scale_gizmo(
 
         let ui_multiplier = 0.005 * viewport.world_space_ui_multiplier(position);
 
+        let x_axis = placement.rotation * glam::Vec3::unit_x();
+        let y_axis = placement.rotation * glam::Vec3::unit_y();
+        let z_axis = placement.rotation * glam::Vec3::unit_z();
+
+        // Converts an offset in the gizmo's space to world space
+        let to_world =
+            |offset: glam::Vec3| position + placement.rotation * (offset * ui_multiplier);
+
         // x axis line
         editor_draw.add_line(
             "x_axis_scale",
             debug_draw,
             position,
-            position + (glam::vec3(100.0, 0.0, 0.0) * ui_multiplier),
-            EditorDraw3DConstraint::x_line(position),
+            to_world(glam::vec3(100.0, 0.0, 0.0)),
+            EditorDraw3DConstraint::line(position, x_axis),
             x_color,
             DebugDraw3DDepthBehavior::NoDepthTest,
         );
@@ -516,9 +678,9 @@ fn draw_scale_gizmo(
         editor_draw.add_line(
             "x_axis_scale",
             debug_draw,
-            position + (glam::vec3(100.0, -20.0, 0.0) * ui_multiplier),
-            position + (glam::vec3(100.0, 20.0, 0.0) * ui_multiplier),
-            EditorDraw3DConstraint::x_line(position),
+            to_world(glam::vec3(100.0, -20.0, 0.0)),
+            to_world(glam::vec3(100.0, 20.0, 0.0)),
+            EditorDraw3DConstraint::line(position, x_axis),
             x_color,
             DebugDraw3DDepthBehavior::NoDepthTest,
         );
@@ -528,8 +690,8 @@ fn draw_scale_gizmo(
             "y_axis_scale",
             debug_draw,
             position,
-            position + (glam::vec3(0.0, 100.0, 0.0) * ui_multiplier),
-            EditorDraw3DConstraint::y_line(position),
+            to_world(glam::vec3(0.0, 100.0, 0.0)),
+            EditorDraw3DConstraint::line(position, y_axis),
             y_color,
             DebugDraw3DDepthBehavior::NoDepthTest,
         );
@@ -538,9 +700,9 @@ fn draw_scale_gizmo(
         editor_draw.add_line(
             "y_axis_scale",
             debug_draw,
-            position + (glam::Vec3::new(-20.0, 100.0, 0.0) * ui_multiplier),
-            position + (glam::Vec3::new(20.0, 100.0, 0.0) * ui_multiplier),
-            EditorDraw3DConstraint::y_line(position),
+            to_world(glam::Vec3::new(-20.0, 100.0, 0.0)),
+            to_world(glam::Vec3::new(20.0, 100.0, 0.0)),
+            EditorDraw3DConstraint::line(position, y_axis),
             y_color,
             DebugDraw3DDepthBehavior::NoDepthTest,
         );
@@ -549,9 +711,9 @@ fn draw_scale_gizmo(
         editor_draw.add_line(
             "uniform_scale",
             debug_draw,
-            position + (glam::Vec3::new(0.0, 0.0, 0.0) * ui_multiplier),
-            position + (glam::Vec3::new(50.0, 50.0, 0.0) * ui_multiplier),
-            EditorDraw3DConstraint::xy_plane(position),
+            to_world(glam::Vec3::new(0.0, 0.0, 0.0)),
+            to_world(glam::Vec3::new(50.0, 50.0, 0.0)),
+            EditorDraw3DConstraint::plane(position, z_axis),
             xy_color,
             DebugDraw3DDepthBehavior::NoDepthTest,
         );
@@ -560,9 +722,9 @@ fn draw_scale_gizmo(
         editor_draw.add_line(
             "uniform_scale",
             debug_draw,
-            position + (glam::Vec3::new(40.0, 60.0, 0.0) * ui_multiplier),
-            position + (glam::Vec3::new(60.0, 40.0, 0.0) * ui_multiplier),
-            EditorDraw3DConstraint::xy_plane(position),
+            to_world(glam::Vec3::new(40.0, 60.0, 0.0)),
+            to_world(glam::Vec3::new(60.0, 40.0, 0.0)),
+            EditorDraw3DConstraint::plane(position, z_axis),
             xy_color,
             DebugDraw3DDepthBehavior::NoDepthTest,
         );
@@ -572,6 +734,10 @@ fn draw_scale_gizmo(
 fn handle_rotate_gizmo_input(
     editor_draw: &mut EditorDraw3DResource,
     tx: &mut EditorTransaction,
+    snap_settings: Option<&SnapSettings>,
+    gizmo_space: GizmoSpace,
+    pivot: Option<GizmoPlacement>,
+    accumulated_angle: &mut f32,
 ) -> GizmoResult {
     if let Some(drag_in_progress) =
         editor_draw.shape_drag_in_progress_or_just_finished(MouseButton::LEFT)
@@ -587,18 +753,48 @@ fn handle_rotate_gizmo_input(
             return GizmoResult::NoChange;
         }
 
-        //TODO: It might be possible to detect the dragged shape's center, compare it to mouse
-        // position, and track a 1:1 rotation with mouse movement
-
-        // Determine the drag distance in ui_space
-        //TODO: I was intending this to use ui space but the values during drag are not lining up
-        // with values on end drag. This is likely an fp precision issue.
-        let ui_space_previous_frame_delta =
-            sign_aware_magnitude(drag_in_progress.world_space_previous_frame_delta);
+        // The ring is constrained to the plane it's drawn in, so the drag can be measured as an
+        // angle around the ring's center. This tracks the mouse 1:1
+        let (center, normal) = match drag_in_progress.constraint {
+            EditorDraw3DConstraint::Plane(plane) => (plane.basis, plane.normal),
+            EditorDraw3DConstraint::Line(_) => return GizmoResult::NoChange,
+        };
+
+        let drag_end = drag_in_progress.world_space_end_position;
+        let drag_previous_end = drag_end - drag_in_progress.world_space_previous_frame_delta;
+
+        // A single frame never turns close to half a turn, so summing the per-frame angles doesn't
+        // wrap around like measuring from where the drag began would
+        let mut previous_frame_angle =
+            angle_around_axis(center, normal, drag_previous_end, drag_end);
+        *accumulated_angle += previous_frame_angle;
+
+        // Rotate in whole increments, measured from the rotation when the drag started
+        if let Some(snap_settings) = snap_settings {
+            previous_frame_angle = SnapSettings::snap_frame_delta_to_increment(
+                *accumulated_angle,
+                previous_frame_angle,
+                snap_settings.rotate_increment.to_radians(),
+            );
+        }
 
-        let mut query = <(Entity, Write<TransformComponentDef>)>::query();
-        for (_entity_handle, rotation) in query.iter_mut(tx.world_mut()) {
-            *rotation.rotation_euler_mut() += glam::Vec3::unit_z() * ui_space_previous_frame_delta
+        let mut query = <Write<TransformComponentDef>>::query();
+        for transform in query.iter_mut(tx.world_mut()) {
+            // With a shared pivot, everything turns around the dragged ring's axis. Otherwise each
+            // entity turns around its own gizmo's axis, which in local space is its own z axis
+            let axis = match (pivot, gizmo_space) {
+                (Some(_), _) | (None, GizmoSpace::World) => normal,
+                (None, GizmoSpace::Local) => transform.rotation_quat() * glam::Vec3::unit_z(),
+            };
+
+            let rotation_delta = glam::Quat::from_axis_angle(axis, previous_frame_angle);
+            transform.set_rotation_quat(rotation_delta * transform.rotation_quat());
+
+            // Swing the entity around the pivot
+            if let Some(pivot) = pivot {
+                let offset = transform.position() - pivot.position;
+                *transform.position_mut() = pivot.position + rotation_delta * offset;
+            }
         }
 
         if editor_draw.is_shape_drag_just_finished(MouseButton::LEFT) {
@@ -611,42 +807,55 @@ fn handle_rotate_gizmo_input(
     }
 }
 
+// Signed angle from one point to another, measured around an axis passing through center
+fn angle_around_axis(
+    center: glam::Vec3,
+    axis: glam::Vec3,
+    from: glam::Vec3,
+    to: glam::Vec3,
+) -> f32 {
+    let from = from - center;
+    let to = to - center;
+    f32::atan2(axis.dot(from.cross(to)), from.dot(to))
+}
+
 fn draw_rotate_gizmo(
-    _viewport: &ViewportResource,
-    _debug_draw: &mut DebugDraw3DResource,
-    _editor_draw: &mut EditorDraw3DResource,
-    selection_world: &mut EditorSelectionResource,
-    subworld: &SubWorld,
-    transform_query: &mut TransformQuery,
+    viewport: &ViewportResource,
+    debug_draw: &mut DebugDraw3DResource,
+    editor_draw: &mut EditorDraw3DResource,
+    placements: &[GizmoPlacement],
 ) {
-    for (entity, _transform) in transform_query.iter(subworld) {
-        if !selection_world.is_entity_selected(*entity) {
-            continue;
-        }
+    for placement in placements {
+        let position = placement.position;
 
-        // let position = transform.position();
-        //
-        // let z_axis_color = glam::Vec4::new(0.0, 1.0, 0.0, 1.0);
-        //
-        // let ui_multiplier = 0.005 * viewport.world_space_ui_multiplier(position);
-
-        // editor_draw.add_sphere(
-        //     "z_axis_rotate",
-        //     debug_draw,
-        //     *position,
-        //     50.0 * ui_multiplier,
-        //     12,
-        //     z_axis_color,
-        //     DebugDraw3DDepthBehavior::NoDepthTest,
-        // );
-        // editor_draw.add_sphere(
-        //     "z_axis_rotate",
-        //     debug_draw,
-        //     *position,
-        //     52.0 * ui_multiplier,
-        //     12,
-        //     z_axis_color,
-        //     DebugDraw3DDepthBehavior::NoDepthTest,
-        // );
+        let z_axis_color = glam::Vec4::new(0.0, 1.0, 0.0, 1.0);
+
+        let ui_multiplier = 0.005 * viewport.world_space_ui_multiplier(position);
+
+        let z_axis = placement.rotation * glam::Vec3::unit_z();
+
+        // The ring is made of line segments so that it can be clicked and dragged like the
+        // other gizmos
+        for &radius in &[50.0, 52.0] {
+            let radius = radius * ui_multiplier;
+            let point_on_ring = |index: u32| {
+                let angle =
+                    (index as f32 / ROTATE_GIZMO_SEGMENTS as f32) * std::f32::consts::PI * 2.0;
+                position
+                    + placement.rotation * glam::Vec3::new(angle.cos(), angle.sin(), 0.0) * radius
+            };
+
+            for index in 0..ROTATE_GIZMO_SEGMENTS {
+                editor_draw.add_line(
+                    "z_axis_rotate",
+                    debug_draw,
+                    point_on_ring(index),
+                    point_on_ring(index + 1),
+                    EditorDraw3DConstraint::plane(position, z_axis),
+                    z_axis_color,
+                    DebugDraw3DDepthBehavior::NoDepthTest,
+                );
+            }
+        }
     }
 }
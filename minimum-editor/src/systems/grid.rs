@@ -0,0 +1,96 @@
+use legion::*;
+
+use minimum_game::resources::{ViewportResource, DebugDraw3DResource, DebugDraw3DDepthBehavior};
+use crate::resources::{EditorStateResource, EditorSettingsResource};
+
+// If drawing every grid line would take more than this many lines on each side of the center,
+// only every 10th (or 100th, etc.) line is drawn
+const MAX_GRID_LINES_PER_SIDE: f32 = 100.0;
+
+// Draws the snapping grid on the XY plane around the point in the middle of the viewport
+pub fn editor_draw_grid(schedule: &mut legion::systems::Builder) {
+    schedule.add_system(
+        SystemBuilder::new("editor_draw_grid")
+            .read_resource::<EditorStateResource>()
+            .read_resource::<EditorSettingsResource>()
+            .read_resource::<ViewportResource>()
+            .write_resource::<DebugDraw3DResource>()
+            .build(
+                |_, _, (editor_state, editor_settings, viewport, debug_draw), _| {
+                    let snap_settings = editor_settings.snap_settings();
+                    if !editor_state.is_editor_active()
+                        || !snap_settings.show_grid
+                        || snap_settings.translate_grid_size <= 0.0
+                    {
+                        return;
+                    }
+
+                    let viewport_size = viewport.viewport_size_in_pixels();
+                    if viewport_size.x() <= 0.0 || viewport_size.y() <= 0.0 {
+                        return;
+                    }
+
+                    // Find where the middle of the viewport meets the XY plane. If the camera is
+                    // looking along or away from the plane, center the grid under the camera
+                    let ray = viewport.viewport_space_to_ray(viewport_size * 0.5);
+                    let t = -ray.origin.z() / ray.dir.z();
+                    let center = if t.is_finite() && t >= 0.0 {
+                        ray.origin + ray.dir * t
+                    } else {
+                        let eye_position = viewport.world_space_eye_position();
+                        glam::Vec3::new(eye_position.x(), eye_position.y(), 0.0)
+                    };
+
+                    // world_space_ui_multiplier is the world space length of about 140 pixels,
+                    // so this covers a bit more than the viewport
+                    let half_extent = viewport.world_space_ui_multiplier(center)
+                        * viewport_size.x().max(viewport_size.y())
+                        / 100.0;
+                    if !half_extent.is_finite() || half_extent <= 0.0 {
+                        return;
+                    }
+
+                    let mut cell_size = snap_settings.translate_grid_size;
+                    while half_extent / cell_size > MAX_GRID_LINES_PER_SIDE {
+                        cell_size *= 10.0;
+                    }
+
+                    let first_x = ((center.x() - half_extent) / cell_size).floor() as i64;
+                    let last_x = ((center.x() + half_extent) / cell_size).ceil() as i64;
+                    let first_y = ((center.y() - half_extent) / cell_size).floor() as i64;
+                    let last_y = ((center.y() + half_extent) / cell_size).ceil() as i64;
+
+                    let min = glam::Vec2::new(first_x as f32, first_y as f32) * cell_size;
+                    let max = glam::Vec2::new(last_x as f32, last_y as f32) * cell_size;
+
+                    let line_color = glam::vec4(0.5, 0.5, 0.5, 0.3);
+                    let x_axis_color = glam::vec4(1.0, 0.0, 0.0, 0.5);
+                    let y_axis_color = glam::vec4(0.0, 1.0, 0.0, 0.5);
+
+                    // Lines parallel to the Y axis
+                    for i in first_x..=last_x {
+                        let x = i as f32 * cell_size;
+                        let color = if i == 0 { y_axis_color } else { line_color };
+                        debug_draw.add_line(
+                            glam::Vec3::new(x, min.y(), 0.0),
+                            glam::Vec3::new(x, max.y(), 0.0),
+                            color,
+                            DebugDraw3DDepthBehavior::Normal,
+                        );
+                    }
+
+                    // Lines parallel to the X axis
+                    for i in first_y..=last_y {
+                        let y = i as f32 * cell_size;
+                        let color = if i == 0 { x_axis_color } else { line_color };
+                        debug_draw.add_line(
+                            glam::Vec3::new(min.x(), y, 0.0),
+                            glam::Vec3::new(max.x(), y, 0.0),
+                            color,
+                            DebugDraw3DDepthBehavior::Normal,
+                        );
+                    }
+                },
+            ),
+    );
+}
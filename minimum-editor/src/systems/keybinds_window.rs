@@ -0,0 +1,79 @@
+use legion::*;
+
+use minimum_game::resources::{ImguiResource, InputResource};
+use crate::resources::{EditorStateResource, EditorSettingsResource, EditorAction};
+
+use imgui::im_str;
+
+// Lists every editor action with its binding. Clicking Rebind waits for the next key (with any
+// held modifiers) and binds it to that action. Save writes the bindings to the keybinds file.
+pub fn editor_keybinds_window(schedule: &mut legion::systems::Builder) {
+    schedule.add_system(
+        SystemBuilder::new("editor_keybinds_window")
+            .write_resource::<ImguiResource>()
+            .write_resource::<EditorStateResource>()
+            .read_resource::<InputResource>()
+            .write_resource::<EditorSettingsResource>()
+            .build(
+                |_, _, (imgui_manager, editor_state, input_state, editor_settings), _| {
+                    editor_settings.keybinds_mut().update_rebind(input_state);
+
+                    imgui_manager.with_ui(|ui: &mut imgui::Ui| {
+                        let window_options = editor_state.window_options_mut();
+                        if !window_options.show_keybinds {
+                            return;
+                        }
+
+                        imgui::Window::new(im_str!("Keybinds"))
+                            .position([360.0, 170.0], imgui::Condition::Once)
+                            .size([420.0, 400.0], imgui::Condition::Once)
+                            .opened(&mut window_options.show_keybinds)
+                            .build(ui, || {
+                                if editor_settings.keybinds_path().is_some()
+                                    && ui.button(im_str!("Save"), [80.0, 0.0])
+                                {
+                                    if let Err(error) = editor_settings.save_keybinds() {
+                                        log::error!("Failed to save keybinds: {:?}", error);
+                                    }
+                                }
+
+                                ui.separator();
+
+                                let keybinds = editor_settings.keybinds_mut();
+                                ui.columns(3, im_str!("keybinds"), false);
+                                for action in EditorAction::ALL.iter().copied() {
+                                    ui.text(action.name());
+                                    ui.next_column();
+
+                                    if keybinds.rebinding_action() == Some(action) {
+                                        ui.text(im_str!("Press a key..."));
+                                    } else if let Some(chord) = keybinds.binding(action) {
+                                        ui.text(&im_str!("{}", chord));
+                                    } else {
+                                        ui.text_disabled(im_str!("Unbound"));
+                                    }
+                                    ui.next_column();
+
+                                    if keybinds.rebinding_action() == Some(action) {
+                                        if ui.small_button(&im_str!("Cancel##{}", action.name())) {
+                                            keybinds.cancel_rebind();
+                                        }
+                                    } else {
+                                        if ui.small_button(&im_str!("Rebind##{}", action.name())) {
+                                            keybinds.begin_rebind(action);
+                                        }
+
+                                        ui.same_line(0.0);
+                                        if ui.small_button(&im_str!("Clear##{}", action.name())) {
+                                            keybinds.set_binding(action, None);
+                                        }
+                                    }
+                                    ui.next_column();
+                                }
+                                ui.columns(1, im_str!("keybinds"), false);
+                            });
+                    });
+                },
+            ),
+    );
+}
@@ -1,11 +1,17 @@
 use legion::*;
 
-use minimum_game::resources::TimeResource;
-use crate::resources::{EditorStateResource};
+use minimum_game::resources::{TimeResource, InputResource};
+use minimum_game::input::{InputState, MouseButton, MouseClickSettings};
+use minimum_game::input_recording::InputRecording;
+use crate::resources::{EditorStateResource, EditorSettingsResource};
 use minimum_game::resources::ImguiResource;
-use crate::resources::EditorTool;
+use crate::resources::{EditorTool, GizmoSpace, GizmoPivot};
 
 use imgui::im_str;
+use std::time::Duration;
+
+// Where input recordings are saved to and played back from, relative to the working directory
+const INPUT_RECORDING_PATH: &str = "input_recording.ron";
 
 fn imgui_menu_tool_button(
     ui: &imgui::Ui,
@@ -28,129 +34,279 @@ fn imgui_menu_tool_button(
     }
 }
 
+// Times are edited in milliseconds
+fn imgui_input_menu(
+    ui: &imgui::Ui,
+    click_settings: &mut MouseClickSettings,
+) {
+    let mut multi_click_interval = click_settings.multi_click_interval.as_millis() as i32;
+    if ui
+        .input_int(im_str!("Double Click Time"), &mut multi_click_interval)
+        .build()
+    {
+        click_settings.multi_click_interval =
+            Duration::from_millis(multi_click_interval.max(0) as u64);
+    }
+
+    ui.input_float(
+        im_str!("Double Click Distance"),
+        &mut click_settings.multi_click_distance,
+    )
+    .build();
+
+    let mut long_press_duration = click_settings.long_press_duration.as_millis() as i32;
+    if ui
+        .input_int(im_str!("Long Press Time"), &mut long_press_duration)
+        .build()
+    {
+        click_settings.long_press_duration =
+            Duration::from_millis(long_press_duration.max(0) as u64);
+    }
+
+    for (button, name) in &[
+        (MouseButton::LEFT, "Left"),
+        (MouseButton::RIGHT, "Right"),
+        (MouseButton::MIDDLE, "Middle"),
+    ] {
+        let mut threshold = click_settings.drag_threshold(*button);
+        if ui
+            .input_float(&im_str!("{} Drag Threshold", name), &mut threshold)
+            .build()
+        {
+            click_settings.set_drag_threshold(*button, threshold);
+        }
+    }
+}
+
+// Recordings are saved when they stop. Playing one back replaces live input until it finishes
+fn imgui_input_recording_menu(
+    ui: &imgui::Ui,
+    input_state: &mut InputState,
+    frame_time: Duration,
+) {
+    if input_state.is_recording() {
+        if imgui::MenuItem::new(im_str!("Stop Recording")).build(ui) {
+            if let Some(recording) = input_state.stop_recording() {
+                match recording.save(INPUT_RECORDING_PATH) {
+                    Ok(()) => log::info!("Saved input recording to {}", INPUT_RECORDING_PATH),
+                    Err(error) => log::warn!("Failed to save input recording: {:?}", error),
+                }
+            }
+        }
+    } else if input_state.is_playing_back() {
+        if imgui::MenuItem::new(im_str!("Stop Playback")).build(ui) {
+            input_state.stop_playback();
+        }
+    } else {
+        if imgui::MenuItem::new(im_str!("Record")).build(ui) {
+            input_state.start_recording(frame_time);
+        }
+
+        if imgui::MenuItem::new(im_str!("Play Recording")).build(ui) {
+            match InputRecording::load(INPUT_RECORDING_PATH) {
+                Ok(recording) => input_state.start_playback(recording),
+                Err(error) => log::warn!("Failed to load input recording: {:?}", error),
+            }
+        }
+    }
+}
+
 pub fn editor_imgui_menu(schedule: &mut legion::systems::Builder) {
     schedule.add_system(
         SystemBuilder::new("editor_imgui_menu")
             .write_resource::<ImguiResource>()
             .write_resource::<EditorStateResource>()
             .read_resource::<TimeResource>()
-            .build(|_command_buffer, _, (imgui, editor_state, time_state), _| {
-                imgui.with_ui(|ui| {
-                    {
-                        let window_settings = editor_state.window_options_mut();
-                        if window_settings.show_imgui_metrics {
-                            ui.show_metrics_window(&mut window_settings.show_imgui_metrics);
-                        }
-
-                        if window_settings.show_imgui_style_editor {
-                            imgui::Window::new(im_str!("Editor")).build(ui, || {
-                                ui.show_default_style_editor();
-                            });
-                        }
-
-                        if window_settings.show_imgui_demo {
-                            ui.show_demo_window(&mut window_settings.show_imgui_demo);
-                        }
-                    }
-
-                    ui.main_menu_bar(|| {
-                        //axis-arrow
-                        imgui_menu_tool_button(
-                            ui,
-                            &mut *editor_state,
-                            EditorTool::Translate,
-                            "\u{fd25}",
-                        );
-                        //resize
-                        imgui_menu_tool_button(
-                            ui,
-                            &mut *editor_state,
-                            EditorTool::Scale,
-                            "\u{fa67}",
-                        );
-                        //rotate-orbit
-                        imgui_menu_tool_button(
-                            ui,
-                            &mut *editor_state,
-                            EditorTool::Rotate,
-                            "\u{fd74}",
-                        );
-
-                        ui.menu(imgui::im_str!("File"), true, || {
-                            if imgui::MenuItem::new(imgui::im_str!("Open")).build(ui) {
-                                if let Some(opened_prefab) = editor_state.opened_prefab() {
-                                    // temporarily get the recently opened prefab uuid from editor state
-                                    let uuid = opened_prefab.uuid();
-                                    editor_state.enqueue_open_prefab(*uuid);
-                                }
+            .write_resource::<EditorSettingsResource>()
+            .write_resource::<InputResource>()
+            .build(
+                |_, _, (imgui, editor_state, time_state, settings, input), _| {
+                    imgui.with_ui(|ui| {
+                        {
+                            let window_settings = editor_state.window_options_mut();
+                            if window_settings.show_imgui_metrics {
+                                ui.show_metrics_window(&mut window_settings.show_imgui_metrics);
                             }
 
-                            if imgui::MenuItem::new(im_str!("Save")).build(ui) {
-                                editor_state.enqueue_save_prefab();
+                            if window_settings.show_imgui_style_editor {
+                                imgui::Window::new(im_str!("Editor")).build(ui, || {
+                                    ui.show_default_style_editor();
+                                });
                             }
-                        });
 
-                        ui.menu(imgui::im_str!("Edit"), true, || {
-                            if imgui::MenuItem::new(im_str!("Undo")).build(ui) {
-                                editor_state.enqueue_undo();
+                            if window_settings.show_imgui_demo {
+                                ui.show_demo_window(&mut window_settings.show_imgui_demo);
                             }
+                        }
 
-                            if imgui::MenuItem::new(im_str!("Redo")).build(ui) {
-                                editor_state.enqueue_redo();
-                            }
-                        });
-
-                        let window_settings = editor_state.window_options_mut();
-                        ui.menu(im_str!("Windows"), true, || {
-                            ui.checkbox(
-                                im_str!("ImGui Metrics"),
-                                &mut window_settings.show_imgui_metrics,
+                        ui.main_menu_bar(|| {
+                            //axis-arrow
+                            imgui_menu_tool_button(
+                                ui,
+                                &mut *editor_state,
+                                EditorTool::Translate,
+                                "\u{fd25}",
                             );
-                            ui.checkbox(
-                                im_str!("ImGui Style Editor"),
-                                &mut window_settings.show_imgui_style_editor,
+                            //resize
+                            imgui_menu_tool_button(
+                                ui,
+                                &mut *editor_state,
+                                EditorTool::Scale,
+                                "\u{fa67}",
                             );
-                            ui.checkbox(
-                                im_str!("ImGui Demo"),
-                                &mut window_settings.show_imgui_demo,
+                            //rotate-orbit
+                            imgui_menu_tool_button(
+                                ui,
+                                &mut *editor_state,
+                                EditorTool::Rotate,
+                                "\u{fd74}",
                             );
-                            ui.checkbox(
-                                im_str!("Entity List"),
-                                &mut window_settings.show_entity_list,
-                            );
-                            ui.checkbox(im_str!("Inspector"), &mut window_settings.show_inspector);
-                        });
 
-                        ui.separator();
-
-                        if editor_state.is_editor_active() {
-                            if imgui::MenuItem::new(im_str!("\u{e8c4} Reset")).build(ui) {
-                                editor_state.enqueue_reset();
+                            let gizmo_space_label = match editor_state.gizmo_space() {
+                                GizmoSpace::World => im_str!("World"),
+                                GizmoSpace::Local => im_str!("Local"),
+                            };
+                            if imgui::MenuItem::new(gizmo_space_label).build(ui) {
+                                editor_state.toggle_gizmo_space();
                             }
 
-                            if imgui::MenuItem::new(im_str!("\u{f40a} Play")).build(ui) {
-                                editor_state.enqueue_play();
-                            }
-                        } else {
-                            if imgui::MenuItem::new(im_str!("\u{e8c4} Reset")).build(ui) {
-                                editor_state.enqueue_reset();
-                            }
+                            ui.menu(im_str!("Pivot"), true, || {
+                                let pivots = [
+                                    (GizmoPivot::SelectionCenter, im_str!("Selection Center")),
+                                    (GizmoPivot::IndividualOrigins, im_str!("Individual Origins")),
+                                    (GizmoPivot::ActiveEntity, im_str!("Active Entity")),
+                                ];
+
+                                for (gizmo_pivot, label) in pivots.iter() {
+                                    let selected = editor_state.gizmo_pivot() == *gizmo_pivot;
+                                    if imgui::MenuItem::new(*label).selected(selected).build(ui) {
+                                        editor_state.set_gizmo_pivot(*gizmo_pivot);
+                                    }
+                                }
+                            });
+
+                            ui.menu(imgui::im_str!("File"), true, || {
+                                if imgui::MenuItem::new(imgui::im_str!("Open")).build(ui) {
+                                    if let Some(opened_prefab) = editor_state.opened_prefab() {
+                                        // temporarily get the recently opened prefab uuid from editor state
+                                        let uuid = opened_prefab.uuid();
+                                        editor_state.enqueue_open_prefab(*uuid);
+                                    }
+                                }
+
+                                if imgui::MenuItem::new(im_str!("Save")).build(ui) {
+                                    editor_state.enqueue_save_prefab();
+                                }
+                            });
 
-                            if imgui::MenuItem::new(im_str!("\u{f3e4} Pause")).build(ui) {
-                                editor_state.enqueue_pause();
+                            ui.menu(imgui::im_str!("Edit"), true, || {
+                                if imgui::MenuItem::new(im_str!("Undo")).build(ui) {
+                                    editor_state.enqueue_undo();
+                                }
+
+                                if imgui::MenuItem::new(im_str!("Redo")).build(ui) {
+                                    editor_state.enqueue_redo();
+                                }
+
+                                if imgui::MenuItem::new(im_str!("Snap to Grid")).build(ui) {
+                                    editor_state.enqueue_snap_selection_to_grid();
+                                }
+                            });
+
+                            let snap_settings = settings.snap_settings_mut();
+                            ui.menu(im_str!("Snap"), true, || {
+                                ui.checkbox(im_str!("Enabled"), &mut snap_settings.enabled);
+                                ui.checkbox(im_str!("Show Grid"), &mut snap_settings.show_grid);
+                                ui.input_float(
+                                    im_str!("Grid Size"),
+                                    &mut snap_settings.translate_grid_size,
+                                )
+                                .build();
+                                ui.input_float(
+                                    im_str!("Rotation Increment"),
+                                    &mut snap_settings.rotate_increment,
+                                )
+                                .build();
+                                ui.input_float(
+                                    im_str!("Scale Step"),
+                                    &mut snap_settings.scale_step,
+                                )
+                                .build();
+                            });
+
+                            ui.menu(im_str!("Input"), true, || {
+                                imgui_input_menu(ui, input.mouse_click_settings_mut());
+                                ui.separator();
+                                imgui_input_recording_menu(
+                                    ui,
+                                    input.input_state_mut(),
+                                    time_state.fixed_step_time(),
+                                );
+                            });
+
+                            let window_settings = editor_state.window_options_mut();
+                            ui.menu(im_str!("Windows"), true, || {
+                                ui.checkbox(
+                                    im_str!("ImGui Metrics"),
+                                    &mut window_settings.show_imgui_metrics,
+                                );
+                                ui.checkbox(
+                                    im_str!("ImGui Style Editor"),
+                                    &mut window_settings.show_imgui_style_editor,
+                                );
+                                ui.checkbox(
+                                    im_str!("ImGui Demo"),
+                                    &mut window_settings.show_imgui_demo,
+                                );
+                                ui.checkbox(
+                                    im_str!("Entity List"),
+                                    &mut window_settings.show_entity_list,
+                                );
+                                ui.checkbox(
+                                    im_str!("Inspector"),
+                                    &mut window_settings.show_inspector,
+                                );
+                                ui.checkbox(
+                                    im_str!("Transform"),
+                                    &mut window_settings.show_transform_toolbar,
+                                );
+                                ui.checkbox(
+                                    im_str!("Keybinds"),
+                                    &mut window_settings.show_keybinds,
+                                );
+                            });
+
+                            ui.separator();
+
+                            if editor_state.is_editor_active() {
+                                if imgui::MenuItem::new(im_str!("\u{e8c4} Reset")).build(ui) {
+                                    editor_state.enqueue_reset();
+                                }
+
+                                if imgui::MenuItem::new(im_str!("\u{f40a} Play")).build(ui) {
+                                    editor_state.enqueue_play();
+                                }
+                            } else {
+                                if imgui::MenuItem::new(im_str!("\u{e8c4} Reset")).build(ui) {
+                                    editor_state.enqueue_reset();
+                                }
+
+                                if imgui::MenuItem::new(im_str!("\u{f3e4} Pause")).build(ui) {
+                                    editor_state.enqueue_pause();
+                                }
                             }
-                        }
 
-                        ui.text(im_str!(
-                            "FPS: {:.1}",
-                            time_state.system_time().updates_per_second_smoothed()
-                        ));
+                            ui.text(im_str!(
+                                "FPS: {:.1}",
+                                time_state.system_time().updates_per_second_smoothed()
+                            ));
 
-                        if time_state.is_simulation_paused() {
-                            ui.text(im_str!("SIMULATION PAUSED"));
-                        }
+                            if time_state.is_simulation_paused() {
+                                ui.text(im_str!("SIMULATION PAUSED"));
+                            }
+                        });
                     });
-                });
-            }),
+                },
+            ),
     );
 }
@@ -1,10 +1,15 @@
 use legion::*;
 
-use minimum_game::resources::{InputResource, ViewportResource, DebugDraw3DResource, CameraResource};
+use minimum_game::resources::{
+    InputResource, ViewportResource, DebugDraw3DResource, CameraResource, TimeResource,
+    InputMapResource,
+};
 use crate::resources::{
     EditorStateResource, EditorSelectionResource, EditorDraw3DResource, EditorSettingsResource,
+    EditorCameraResource, EditorCameraMode, EditorCameraView, EditorAction,
 };
 use crate::resources::EditorTool;
+use minimum_game::input::MouseButton;
 
 mod main_menu;
 pub use main_menu::editor_imgui_menu;
@@ -25,6 +30,16 @@ pub use selection::editor_handle_selection;
 mod gizmos_3d;
 pub use gizmos_3d::editor_gizmos;
 
+mod grid;
+pub use grid::editor_draw_grid;
+
+mod transform_toolbar;
+pub use transform_toolbar::editor_transform_toolbar_window;
+pub use transform_toolbar::editor_nudge_selection;
+
+mod keybinds_window;
+pub use keybinds_window::editor_keybinds_window;
+
 pub fn editor_refresh_selection_world(
     world: &mut World,
     resources: &mut Resources,
@@ -69,22 +84,49 @@ pub fn editor_process_editor_ops(
     EditorStateResource::process_editor_ops(world, resources);
 }
 
+// Activates the editor input context while editing and the gameplay context while playing, and
+// binds the editor's keybinds in the input map. Editor actions other than pausing and quitting are
+// in the editor context, so they are ignored while playing.
+pub fn editor_update_input_contexts(schedule: &mut legion::systems::Builder) {
+    let mut bound_keybinds_revision = None;
+    schedule.add_system(
+        SystemBuilder::new("editor_update_input_contexts")
+            .read_resource::<EditorStateResource>()
+            .write_resource::<InputMapResource>()
+            .read_resource::<EditorSettingsResource>()
+            .build(move |_, _, (editor_state, input_map, editor_settings), _| {
+                let editing = editor_state.is_editor_active();
+                input_map.set_context_active(InputMapResource::EDITOR_CONTEXT, editing);
+                input_map.set_context_active(InputMapResource::GAMEPLAY_CONTEXT, !editing);
+
+                let keybinds = editor_settings.keybinds();
+                if bound_keybinds_revision != Some(keybinds.revision()) {
+                    for context in keybinds.input_map_contexts() {
+                        input_map.set_runtime_context(context);
+                    }
+
+                    bound_keybinds_revision = Some(keybinds.revision());
+                }
+            }),
+    );
+}
+
 pub fn editor_keybinds(schedule: &mut legion::systems::Builder) {
     schedule.add_system(
         SystemBuilder::new("editor_input")
             .write_resource::<EditorStateResource>()
-            .read_resource::<InputResource>()
+            .read_resource::<InputMapResource>()
             .read_resource::<ViewportResource>()
             .write_resource::<EditorSelectionResource>()
             .write_resource::<DebugDraw3DResource>()
             .write_resource::<EditorDraw3DResource>()
-            .read_resource::<EditorSettingsResource>()
+            .write_resource::<EditorSettingsResource>()
             .build(
                 |_command_buffer,
                  _subworld,
                  (
                     editor_state,
-                    input_state,
+                    input_map,
                     _viewport,
                     _editor_selection,
                     _debug_draw,
@@ -92,58 +134,248 @@ pub fn editor_keybinds(schedule: &mut legion::systems::Builder) {
                     editor_settings,
                 ),
                  _| {
-                    if input_state.is_key_just_down(editor_settings.keybinds().tool_translate) {
+                    let keybinds = editor_settings.keybinds();
+                    let is_just_down =
+                        |action: EditorAction| keybinds.is_action_just_down(input_map, action);
+
+                    if is_just_down(EditorAction::ToolTranslate) {
                         editor_state.enqueue_set_active_editor_tool(EditorTool::Translate);
                     }
 
-                    if input_state.is_key_just_down(editor_settings.keybinds().tool_scale) {
+                    if is_just_down(EditorAction::ToolScale) {
                         editor_state.enqueue_set_active_editor_tool(EditorTool::Scale);
                     }
 
-                    if input_state.is_key_just_down(editor_settings.keybinds().tool_rotate) {
+                    if is_just_down(EditorAction::ToolRotate) {
                         editor_state.enqueue_set_active_editor_tool(EditorTool::Rotate);
                     }
 
-                    if input_state
-                        .is_key_just_down(editor_settings.keybinds().action_toggle_editor_pause)
-                    {
+                    if is_just_down(EditorAction::ToggleEditorPause) {
                         editor_state.enqueue_toggle_pause();
                     }
+
+                    if is_just_down(EditorAction::ToggleGizmoSpace) {
+                        editor_state.toggle_gizmo_space();
+                    }
+
+                    if is_just_down(EditorAction::CycleGizmoPivot) {
+                        editor_state.cycle_gizmo_pivot();
+                    }
+
+                    if editor_state.is_editor_active() {
+                        if is_just_down(EditorAction::Undo) {
+                            editor_state.enqueue_undo();
+                        }
+
+                        if is_just_down(EditorAction::Redo) {
+                            editor_state.enqueue_redo();
+                        }
+
+                        if is_just_down(EditorAction::Save) {
+                            editor_state.enqueue_save_prefab();
+                        }
+
+                        if is_just_down(EditorAction::Duplicate) {
+                            editor_state.enqueue_duplicate_selection();
+                        }
+
+                        if is_just_down(EditorAction::Delete) {
+                            editor_state.enqueue_delete_selection();
+                        }
+                    }
+
+                    if is_just_down(EditorAction::ToggleSnap) {
+                        let snap_settings = editor_settings.snap_settings_mut();
+                        snap_settings.enabled = !snap_settings.enabled;
+                    }
                 },
             ),
     );
 }
 
+// Navigation for the editor camera. While the editor is active, the editor camera replaces the
+// game's camera. The game's camera is restored when it becomes inactive.
+//
+// In the 2D mode, middle or right drag pans and scrolling zooms toward the cursor. In orbit mode,
+// right drag orbits around the selection (or the pivot if nothing is selected), middle drag pans
+// and scrolling moves toward the pivot. In fly mode, right drag looks around, the movement keys
+// move the camera and scrolling changes the movement speed. In all modes, the frame selection key
+// fits the selection in the view
 pub fn editor_mouse_input(schedule: &mut legion::systems::Builder) {
     schedule.add_system(
-        SystemBuilder::new("editor_input")
+        SystemBuilder::new("editor_mouse_input")
+            .read_resource::<EditorStateResource>()
             .read_resource::<InputResource>()
+            .read_resource::<InputMapResource>()
+            .read_resource::<TimeResource>()
+            .read_resource::<EditorSettingsResource>()
+            .write_resource::<EditorSelectionResource>()
+            .write_resource::<EditorCameraResource>()
             .write_resource::<CameraResource>()
-            .read_resource::<ViewportResource>()
+            .write_resource::<ViewportResource>()
             .build(
                 |_command_buffer,
                  _subworld,
-                 (input_state, camera_resource, _viewport_resource),
+                 (
+                    editor_state,
+                    input_state,
+                    input_map,
+                    time,
+                    editor_settings,
+                    editor_selection,
+                    editor_camera,
+                    camera_resource,
+                    viewport,
+                ),
                  _| {
-                    // Right click drag pans the viewport
-                    // if let Some(mouse_drag) = input_state.mouse_drag_in_progress(MouseButton::RIGHT) {
-                    //     //let mut delta = mouse_drag.world_scale_previous_frame_delta;
-                    //     let mut delta = mouse_drag.ui_
-                    //     delta *= glam::Vec2::new(-1.0, -1.0);
-                    //     camera_resource.position += delta;
-                    // }
-
-                    // Right click drag pans the viewport
+                    if !editor_state.is_editor_active() {
+                        if editor_camera.is_overriding_game_camera() {
+                            editor_camera.restore_game_camera(camera_resource);
+                            camera_resource.update_viewport(viewport);
+                        }
+
+                        return;
+                    }
+
+                    // Make sure the viewport matches the editor camera before using it to convert
+                    // mouse positions to world space
+                    editor_camera.override_game_camera(camera_resource, viewport);
+                    camera_resource.update_viewport(viewport);
+
+                    let keybinds = editor_settings.keybinds();
+                    if keybinds.is_action_just_down(input_map, EditorAction::CameraCycleMode) {
+                        editor_camera.cycle_mode();
+                    }
+
+                    if keybinds.is_action_just_down(input_map, EditorAction::CameraToggleProjection)
+                    {
+                        editor_camera.toggle_projection();
+                    }
+
+                    for &(action, view) in &[
+                        (EditorAction::CameraViewTop, EditorCameraView::Top),
+                        (EditorAction::CameraViewFront, EditorCameraView::Front),
+                        (EditorAction::CameraViewSide, EditorCameraView::Side),
+                    ] {
+                        if keybinds.is_action_just_down(input_map, action) {
+                            editor_camera.snap_to_view(view);
+                        }
+                    }
+
+                    match editor_camera.mode {
+                        EditorCameraMode::Pan2D => {
+                            for &button in &[MouseButton::MIDDLE, MouseButton::RIGHT] {
+                                if let Some(mouse_drag) = input_state.mouse_drag_in_progress(button)
+                                {
+                                    editor_camera.pan(mouse_drag.previous_frame_delta, viewport);
+                                }
+                            }
+                        }
+                        EditorCameraMode::Orbit => {
+                            // Orbit around the selection, keeping the current distance
+                            if input_state.is_mouse_just_down(MouseButton::RIGHT) {
+                                if let Some((min, max)) = selection_bounds(editor_selection) {
+                                    editor_camera.pivot = (min + max) * 0.5;
+                                }
+                            }
+
+                            if let Some(mouse_drag) =
+                                input_state.mouse_drag_in_progress(MouseButton::RIGHT)
+                            {
+                                editor_camera.orbit(mouse_drag.previous_frame_delta);
+                            }
+
+                            if let Some(mouse_drag) =
+                                input_state.mouse_drag_in_progress(MouseButton::MIDDLE)
+                            {
+                                editor_camera.pan(mouse_drag.previous_frame_delta, viewport);
+                            }
+                        }
+                        EditorCameraMode::Fly => {
+                            if let Some(mouse_drag) =
+                                input_state.mouse_drag_in_progress(MouseButton::RIGHT)
+                            {
+                                editor_camera.look(mouse_drag.previous_frame_delta);
+                            }
+
+                            if let Some(mouse_drag) =
+                                input_state.mouse_drag_in_progress(MouseButton::MIDDLE)
+                            {
+                                editor_camera.pan(mouse_drag.previous_frame_delta, viewport);
+                            }
+
+                            // x is right, y is up and z is forward
+                            let mut direction = glam::Vec3::zero();
+                            for &(action, offset) in &[
+                                (EditorAction::CameraRight, glam::Vec3::new(1.0, 0.0, 0.0)),
+                                (EditorAction::CameraLeft, glam::Vec3::new(-1.0, 0.0, 0.0)),
+                                (EditorAction::CameraUp, glam::Vec3::new(0.0, 1.0, 0.0)),
+                                (EditorAction::CameraDown, glam::Vec3::new(0.0, -1.0, 0.0)),
+                                (EditorAction::CameraForward, glam::Vec3::new(0.0, 0.0, 1.0)),
+                                (EditorAction::CameraBack, glam::Vec3::new(0.0, 0.0, -1.0)),
+                            ] {
+                                if keybinds.is_action_down(input_map, action) {
+                                    direction += offset;
+                                }
+                            }
+
+                            // Use system time so that the camera can move while the game is paused
+                            if direction != glam::Vec3::zero() {
+                                editor_camera
+                                    .fly(direction, time.system_time().previous_update_dt());
+                            }
+                        }
+                    }
+
                     let mouse_scroll = input_state.mouse_wheel_delta();
-                    let delta = mouse_scroll.y as f32;
+                    if mouse_scroll.y != 0.0 {
+                        let zoom_factor = 1.05_f32.powf(-mouse_scroll.y);
+                        editor_camera.zoom_toward(
+                            input_state.mouse_position(),
+                            zoom_factor,
+                            viewport,
+                        );
+                    }
+
+                    // Double clicking an entity selects it with the first click and frames it with
+                    // the second
+                    if keybinds.is_action_just_down(input_map, EditorAction::FrameSelection)
+                        || input_state.is_mouse_button_just_double_clicked(MouseButton::LEFT)
+                    {
+                        if let Some((min, max)) = selection_bounds(editor_selection) {
+                            editor_camera.frame(min, max, viewport);
+                        }
+                    }
 
-                    let delta = 1.05_f32.powf(-delta);
-                    camera_resource.x_half_extents *= delta;
+                    editor_camera.override_game_camera(camera_resource, viewport);
+                    camera_resource.update_viewport(viewport);
                 },
             ),
     );
 }
 
+// The union of the bounds of all selected entities
+fn selection_bounds(
+    editor_selection: &mut EditorSelectionResource
+) -> Option<(glam::Vec3, glam::Vec3)> {
+    let mut min = glam::Vec3::new(std::f32::MAX, std::f32::MAX, std::f32::MAX);
+    let mut max = glam::Vec3::new(std::f32::MIN, std::f32::MIN, std::f32::MIN);
+    for aabb in editor_selection.selected_entity_aabbs().values() {
+        if let Some(aabb) = aabb {
+            let mins = aabb.mins();
+            let maxs = aabb.maxs();
+            min = min.min(glam::Vec3::new(mins.x, mins.y, mins.z));
+            max = max.max(glam::Vec3::new(maxs.x, maxs.y, maxs.z));
+        }
+    }
+
+    if min.x() <= max.x() && min.y() <= max.y() && min.z() <= max.z() {
+        Some((min, max))
+    } else {
+        None
+    }
+}
+
 pub fn editor_update_editor_draw(schedule: &mut legion::systems::Builder) {
     schedule.add_system(
         SystemBuilder::new("editor_input")
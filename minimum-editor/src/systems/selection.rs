@@ -1,11 +1,12 @@
 use legion::*;
 
 use minimum_game::resources::{
-    InputResource, ViewportResource, DebugDraw2DResource, DebugDraw3DResource,
+    InputResource, InputMapResource, ViewportResource, DebugDraw2DResource, DebugDraw3DResource,
     DebugDraw3DDepthBehavior,
 };
 use crate::resources::{
     EditorStateResource, EditorSelectionResource, EditorDraw3DResource, EditorSettingsResource,
+    EditorAction,
 };
 
 use minimum_game::input::MouseButton;
@@ -18,6 +19,7 @@ use minimum_math::na_convert::vec3_glam_to_glm;
 fn handle_selection(
     editor_draw: &EditorDraw3DResource,
     input_state: &InputResource,
+    input_map: &InputMapResource,
     viewport: &ViewportResource,
     editor_selection: &mut EditorSelectionResource,
     debug_draw_2d: &mut DebugDraw2DResource,
@@ -122,10 +124,11 @@ fn handle_selection(
     }
 
     if let Some(intersecting_entities) = intersecting_entities {
-        let add_to_selection = input_state.is_key_down(editor_settings.keybinds().selection_add);
+        let keybinds = editor_settings.keybinds();
+        let add_to_selection = keybinds.is_action_down(input_map, EditorAction::SelectionAdd);
         let subtract_from_selection =
-            input_state.is_key_down(editor_settings.keybinds().selection_subtract);
-        let toggle_selection = input_state.is_key_down(editor_settings.keybinds().selection_toggle);
+            keybinds.is_action_down(input_map, EditorAction::SelectionSubtract);
+        let toggle_selection = keybinds.is_action_down(input_map, EditorAction::SelectionToggle);
 
         let mut any_not_selected = false;
         for e in &intersecting_entities {
@@ -161,6 +164,7 @@ pub fn editor_handle_selection(schedule: &mut legion::systems::Builder) {
         SystemBuilder::new("editor_input")
             .write_resource::<EditorStateResource>()
             .read_resource::<InputResource>()
+            .read_resource::<InputMapResource>()
             .read_resource::<ViewportResource>()
             .write_resource::<EditorSelectionResource>()
             .write_resource::<DebugDraw2DResource>()
@@ -172,6 +176,7 @@ pub fn editor_handle_selection(schedule: &mut legion::systems::Builder) {
                  (
                     _editor_state,
                     input_state,
+                    input_map,
                     viewport,
                     editor_selection,
                     debug_draw_2d,
@@ -182,6 +187,7 @@ pub fn editor_handle_selection(schedule: &mut legion::systems::Builder) {
                     handle_selection(
                         &*editor_draw,
                         &*input_state,
+                        &*input_map,
                         &*viewport,
                         &mut *editor_selection,
                         &mut *debug_draw_2d,
@@ -0,0 +1,348 @@
+use legion::*;
+
+use minimum_game::resources::{ImguiResource, InputMapResource, TimeResource};
+use crate::resources::{
+    EditorStateResource, EditorSelectionResource, EditorSettingsResource, PostCommitSelection,
+    NudgeBurst, EditorAction,
+};
+
+use imgui::{im_str, ImString};
+
+use minimum_kernel::resources::ComponentRegistryResource;
+use minimum_kernel::resources::AssetResource;
+use minimum_transform::components::TransformComponentDef;
+
+// Nudges that happen within this long of each other are committed as a single undo step
+const NUDGE_BURST_TIMEOUT_SECONDS: f32 = 0.75;
+
+// The toolbar has an x, y and z field for each of position, rotation and scale
+const TRANSFORM_TOOLBAR_ROWS: [&str; 3] = ["Position", "Rotation", "Scale"];
+const TRANSFORM_TOOLBAR_FIELD_COUNT: usize = 9;
+
+// Applies text typed into a toolbar field to the current value. "1.5" replaces the value, "+0.5"
+// or "+=0.5" adds to it and "-=0.5" subtracts from it. Returns None if the text can't be parsed.
+fn apply_transform_entry(
+    text: &str,
+    value: f32,
+) -> Option<f32> {
+    let text = text.trim();
+    if let Some(delta) = text.strip_prefix("-=") {
+        delta.trim().parse::<f32>().ok().map(|delta| value - delta)
+    } else if let Some(delta) = text.strip_prefix("+=").or_else(|| text.strip_prefix('+')) {
+        delta.trim().parse::<f32>().ok().map(|delta| value + delta)
+    } else {
+        text.parse::<f32>().ok()
+    }
+}
+
+// Fields are ordered position xyz, rotation xyz (in degrees) and then scale xyz
+fn transform_field(
+    transform: &TransformComponentDef,
+    field: usize,
+) -> f32 {
+    let values = match field / 3 {
+        0 => transform.position(),
+        1 => {
+            let rotation = transform.rotation_euler();
+            glam::Vec3::new(
+                rotation.x().to_degrees(),
+                rotation.y().to_degrees(),
+                rotation.z().to_degrees(),
+            )
+        }
+        _ => transform.scale(),
+    };
+
+    match field % 3 {
+        0 => values.x(),
+        1 => values.y(),
+        _ => values.z(),
+    }
+}
+
+fn set_transform_field(
+    transform: &mut TransformComponentDef,
+    field: usize,
+    value: f32,
+) {
+    let uniform_scale = transform.uniform_scale();
+    let (values, value) = match field / 3 {
+        0 => (transform.position_mut(), value),
+        1 => (transform.rotation_euler_mut(), value.to_radians()),
+        _ => {
+            // The displayed scale includes the uniform scale, so only the non-uniform part is
+            // changed to reach the typed value
+            if uniform_scale.abs() < std::f32::EPSILON {
+                return;
+            }
+
+            (transform.non_uniform_scale_mut(), value / uniform_scale)
+        }
+    };
+
+    match field % 3 {
+        0 => values.set_x(value),
+        1 => values.set_y(value),
+        _ => values.set_z(value),
+    }
+}
+
+// Shows the position, rotation and scale of the selection. Typing into a field and pressing
+// enter changes that value on all selected entities, as a single undo step.
+pub fn editor_transform_toolbar_window(schedule: &mut legion::systems::Builder) {
+    schedule.add_system(
+        SystemBuilder::new("editor_transform_toolbar_window")
+            .write_resource::<ImguiResource>()
+            .write_resource::<EditorStateResource>()
+            .read_resource::<EditorSelectionResource>()
+            .read_resource::<ComponentRegistryResource>()
+            .read_resource::<AssetResource>()
+            .build(
+                |_,
+                 _,
+                 (
+                    imgui_manager,
+                    editor_state,
+                    editor_selection,
+                    component_registry,
+                    asset_resource,
+                ),
+                 _| {
+                    imgui_manager.with_ui(|ui: &mut imgui::Ui| {
+                        if !editor_state.window_options().show_transform_toolbar {
+                            return;
+                        }
+
+                        imgui::Window::new(im_str!("Transform"))
+                            .position([360.0, 50.0], imgui::Condition::Once)
+                            .size([420.0, 110.0], imgui::Condition::Once)
+                            .build(ui, || {
+                                let opened_prefab = match editor_state.opened_prefab() {
+                                    Some(opened_prefab)
+                                        if !editor_selection.selected_entities().is_empty() =>
+                                    {
+                                        opened_prefab
+                                    }
+                                    _ => {
+                                        ui.text(im_str!("Nothing selected"));
+                                        return;
+                                    }
+                                };
+
+                                // When several entities are selected, the first one's values are
+                                // shown. They are read from the opened prefab so that a
+                                // transaction is only started once a value is entered.
+                                let prefab_world = &opened_prefab.cooked_prefab().world;
+                                let transform = editor_selection
+                                    .selected_entities()
+                                    .iter()
+                                    .filter_map(|world_entity| {
+                                        opened_prefab.world_to_prefab_mappings().get(world_entity)
+                                    })
+                                    .filter_map(|prefab_entity| {
+                                        prefab_world.entry_ref(*prefab_entity).ok()
+                                    })
+                                    .find_map(|entity_ref| {
+                                        entity_ref
+                                            .get_component::<TransformComponentDef>()
+                                            .ok()
+                                            .cloned()
+                                    });
+
+                                let values = match transform {
+                                    Some(transform) => {
+                                        let mut values = [0.0; TRANSFORM_TOOLBAR_FIELD_COUNT];
+                                        for (field, value) in values.iter_mut().enumerate() {
+                                            *value = transform_field(&transform, field);
+                                        }
+                                        values
+                                    }
+                                    None => {
+                                        ui.text(im_str!("The selection has no transform"));
+                                        return;
+                                    }
+                                };
+
+                                let toolbar = &mut editor_state.transform_toolbar;
+                                toolbar
+                                    .fields
+                                    .resize(TRANSFORM_TOOLBAR_FIELD_COUNT, ImString::default());
+
+                                let mut entered_field = None;
+                                for (row, row_name) in TRANSFORM_TOOLBAR_ROWS.iter().enumerate() {
+                                    ui.text(&im_str!("{}", row_name));
+                                    for field in (row * 3)..(row * 3 + 3) {
+                                        let text = &mut toolbar.fields[field];
+                                        if toolbar.editing_field != Some(field) {
+                                            *text = im_str!("{:.3}", values[field]);
+                                        }
+
+                                        ui.same_line(if field == row * 3 { 80.0 } else { 0.0 });
+                                        ui.set_next_item_width(100.0);
+                                        if ui
+                                            .input_text(&im_str!("##transform_{}", field), text)
+                                            .resize_buffer(true)
+                                            .enter_returns_true(true)
+                                            .build()
+                                        {
+                                            entered_field = Some(field);
+                                        }
+
+                                        if ui.is_item_active() {
+                                            toolbar.editing_field = Some(field);
+                                        } else if toolbar.editing_field == Some(field) {
+                                            toolbar.editing_field = None;
+                                        }
+                                    }
+                                }
+
+                                let entered_field = entered_field.map(|field| {
+                                    (field, toolbar.fields[field].to_str().to_string())
+                                });
+                                let entered_field = entered_field.and_then(|(field, text)| {
+                                    editor_state
+                                        .create_transaction_from_selected(
+                                            &*editor_selection,
+                                            &*component_registry,
+                                        )
+                                        .map(|tx| (field, text, tx))
+                                });
+
+                                if let Some((field, text, mut tx)) = entered_field {
+                                    // Relative entries are applied to each entity's own value
+                                    let mut changed = false;
+                                    let mut query = <Write<TransformComponentDef>>::query();
+                                    for transform in query.iter_mut(tx.world_mut()) {
+                                        let value = transform_field(transform, field);
+                                        if let Some(value) = apply_transform_entry(&text, value) {
+                                            set_transform_field(transform, field, value);
+                                            changed = true;
+                                        }
+                                    }
+
+                                    if changed {
+                                        tx.commit(
+                                            &*asset_resource,
+                                            &mut *editor_state,
+                                            PostCommitSelection::KeepCurrentSelection,
+                                            &*component_registry,
+                                        );
+                                    }
+                                }
+                            });
+                    });
+                },
+            ),
+    );
+}
+
+// Moves the selection by the grid size when a nudge key is pressed. Nudges in quick succession
+// are committed as a single undo step.
+pub fn editor_nudge_selection(schedule: &mut legion::systems::Builder) {
+    schedule.add_system(
+        SystemBuilder::new("editor_nudge_selection")
+            .write_resource::<EditorStateResource>()
+            .read_resource::<EditorSelectionResource>()
+            .read_resource::<InputMapResource>()
+            .read_resource::<TimeResource>()
+            .read_resource::<EditorSettingsResource>()
+            .read_resource::<ComponentRegistryResource>()
+            .read_resource::<AssetResource>()
+            .build(
+                |_,
+                 _,
+                 (
+                    editor_state,
+                    editor_selection,
+                    input_map,
+                    time_state,
+                    editor_settings,
+                    component_registry,
+                    asset_resource,
+                ),
+                 _| {
+                    let nudges = [
+                        (EditorAction::NudgeLeft, -glam::Vec3::unit_x()),
+                        (EditorAction::NudgeRight, glam::Vec3::unit_x()),
+                        (EditorAction::NudgeUp, glam::Vec3::unit_y()),
+                        (EditorAction::NudgeDown, -glam::Vec3::unit_y()),
+                        (EditorAction::NudgeRaise, glam::Vec3::unit_z()),
+                        (EditorAction::NudgeLower, -glam::Vec3::unit_z()),
+                    ];
+
+                    let mut direction = glam::Vec3::zero();
+                    if editor_state.is_editor_active() {
+                        let keybinds = editor_settings.keybinds();
+                        for (action, nudge_direction) in nudges.iter() {
+                            // Holding a nudge key keeps nudging at the platform's key repeat rate
+                            if keybinds.is_action_just_down_or_repeated(input_map, *action) {
+                                direction += *nudge_direction;
+                            }
+                        }
+                    }
+
+                    let now = time_state.system_time().current_instant();
+
+                    let mut nudge_burst = editor_state.nudge_burst_mut().take();
+                    if let Some(burst) = &nudge_burst {
+                        // Starting another transaction commits this one, so there's nothing left
+                        // to do
+                        if !editor_state.is_transaction_in_progress(&burst.transaction) {
+                            nudge_burst = None;
+                        }
+                    }
+
+                    if let Some(burst) = &nudge_burst {
+                        let selection_changed =
+                            burst.selected_entities != *editor_selection.selected_entities();
+                        let timed_out = (now - burst.last_nudge_instant).as_secs_f32()
+                            > NUDGE_BURST_TIMEOUT_SECONDS;
+
+                        if selection_changed || (timed_out && direction == glam::Vec3::zero()) {
+                            nudge_burst.take().unwrap().transaction.commit(
+                                &*asset_resource,
+                                &mut *editor_state,
+                                PostCommitSelection::KeepCurrentSelection,
+                                &*component_registry,
+                            );
+                        }
+                    }
+
+                    if direction != glam::Vec3::zero() {
+                        if nudge_burst.is_none() {
+                            nudge_burst = editor_state
+                                .create_transaction_from_selected(
+                                    &*editor_selection,
+                                    &*component_registry,
+                                )
+                                .map(|transaction| NudgeBurst {
+                                    transaction,
+                                    selected_entities: editor_selection.selected_entities().clone(),
+                                    last_nudge_instant: now,
+                                });
+                        }
+
+                        if let Some(burst) = &mut nudge_burst {
+                            let offset =
+                                direction * editor_settings.snap_settings().translate_grid_size;
+
+                            let mut query = <Write<TransformComponentDef>>::query();
+                            for transform in query.iter_mut(burst.transaction.world_mut()) {
+                                *transform.position_mut() += offset;
+                            }
+
+                            burst.transaction.update(
+                                &*asset_resource,
+                                &mut *editor_state,
+                                PostCommitSelection::KeepCurrentSelection,
+                                &*component_registry,
+                            );
+                            burst.last_nudge_instant = now;
+                        }
+                    }
+
+                    *editor_state.nudge_burst_mut() = nudge_burst;
+                },
+            ),
+    );
+}
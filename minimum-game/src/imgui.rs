@@ -5,6 +5,8 @@ use imgui::sys as imgui_sys;
 pub use imgui;
 use imgui::{DrawCmdParams, DrawCmd};
 
+use crate::input::MouseClickSettings;
+
 pub struct ImguiFontAtlas {
     pub width: u32,
     pub height: u32,
@@ -240,6 +242,19 @@ impl ImguiManager {
         (f)(&mut inner.context);
     }
 
+    // Makes imgui's double click detection agree with InputState's multi-click settings. Call this
+    // before begin_frame(), it takes effect on the next frame
+    pub fn set_mouse_click_settings(
+        &self,
+        settings: &MouseClickSettings,
+    ) {
+        self.with_context(|context| {
+            let io = context.io_mut();
+            io.mouse_double_click_time = settings.multi_click_interval.as_secs_f32();
+            io.mouse_double_click_max_dist = settings.multi_click_distance;
+        });
+    }
+
     // Allows access to the ui without the caller needing to be aware of locking. A frame must be started
     pub fn with_ui<F>(
         &self,
@@ -1,9 +1,295 @@
-// End-users should provide their own layer to translate from these general values to something
-// appropriate to their platform or windowing system
-#[derive(Copy, Clone)]
-pub struct KeyboardKey(pub u8);
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
-#[derive(Copy, Clone)]
+use crate::input_recording::{InputEvent, InputPlayer, InputRecorder, InputRecording};
+
+// Keys as labelled on a US keyboard, independent of the windowing system. Backends translate their
+// own key codes into these, so a binding means the same key on every platform. Keys that aren't
+// listed here are ignored.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum KeyboardKey {
+    // Letters
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    I,
+    J,
+    K,
+    L,
+    M,
+    N,
+    O,
+    P,
+    Q,
+    R,
+    S,
+    T,
+    U,
+    V,
+    W,
+    X,
+    Y,
+    Z,
+
+    // Number row
+    Key0,
+    Key1,
+    Key2,
+    Key3,
+    Key4,
+    Key5,
+    Key6,
+    Key7,
+    Key8,
+    Key9,
+
+    // Function keys
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+
+    // Editing and navigation
+    Escape,
+    Tab,
+    CapsLock,
+    Space,
+    Enter,
+    Backspace,
+    Delete,
+    Insert,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Left,
+    Right,
+    Up,
+    Down,
+    Menu,
+
+    // Modifiers
+    LShift,
+    RShift,
+    LCtrl,
+    RCtrl,
+    LAlt,
+    RAlt,
+    LLogo,
+    RLogo,
+
+    // Punctuation
+    Minus,
+    Equals,
+    LBracket,
+    RBracket,
+    Backslash,
+    Semicolon,
+    Apostrophe,
+    Grave,
+    Comma,
+    Period,
+    Slash,
+
+    // Numpad and locks
+    Numpad0,
+    Numpad1,
+    Numpad2,
+    Numpad3,
+    Numpad4,
+    Numpad5,
+    Numpad6,
+    Numpad7,
+    Numpad8,
+    Numpad9,
+    NumpadAdd,
+    NumpadSubtract,
+    NumpadMultiply,
+    NumpadDivide,
+    NumpadDecimal,
+    NumpadEnter,
+    NumLock,
+    PrintScreen,
+    ScrollLock,
+    Pause,
+}
+
+impl KeyboardKey {
+    pub const COUNT: usize = 104;
+
+    pub const ALL: [KeyboardKey; Self::COUNT] = [
+        KeyboardKey::A,
+        KeyboardKey::B,
+        KeyboardKey::C,
+        KeyboardKey::D,
+        KeyboardKey::E,
+        KeyboardKey::F,
+        KeyboardKey::G,
+        KeyboardKey::H,
+        KeyboardKey::I,
+        KeyboardKey::J,
+        KeyboardKey::K,
+        KeyboardKey::L,
+        KeyboardKey::M,
+        KeyboardKey::N,
+        KeyboardKey::O,
+        KeyboardKey::P,
+        KeyboardKey::Q,
+        KeyboardKey::R,
+        KeyboardKey::S,
+        KeyboardKey::T,
+        KeyboardKey::U,
+        KeyboardKey::V,
+        KeyboardKey::W,
+        KeyboardKey::X,
+        KeyboardKey::Y,
+        KeyboardKey::Z,
+        KeyboardKey::Key0,
+        KeyboardKey::Key1,
+        KeyboardKey::Key2,
+        KeyboardKey::Key3,
+        KeyboardKey::Key4,
+        KeyboardKey::Key5,
+        KeyboardKey::Key6,
+        KeyboardKey::Key7,
+        KeyboardKey::Key8,
+        KeyboardKey::Key9,
+        KeyboardKey::F1,
+        KeyboardKey::F2,
+        KeyboardKey::F3,
+        KeyboardKey::F4,
+        KeyboardKey::F5,
+        KeyboardKey::F6,
+        KeyboardKey::F7,
+        KeyboardKey::F8,
+        KeyboardKey::F9,
+        KeyboardKey::F10,
+        KeyboardKey::F11,
+        KeyboardKey::F12,
+        KeyboardKey::Escape,
+        KeyboardKey::Tab,
+        KeyboardKey::CapsLock,
+        KeyboardKey::Space,
+        KeyboardKey::Enter,
+        KeyboardKey::Backspace,
+        KeyboardKey::Delete,
+        KeyboardKey::Insert,
+        KeyboardKey::Home,
+        KeyboardKey::End,
+        KeyboardKey::PageUp,
+        KeyboardKey::PageDown,
+        KeyboardKey::Left,
+        KeyboardKey::Right,
+        KeyboardKey::Up,
+        KeyboardKey::Down,
+        KeyboardKey::Menu,
+        KeyboardKey::LShift,
+        KeyboardKey::RShift,
+        KeyboardKey::LCtrl,
+        KeyboardKey::RCtrl,
+        KeyboardKey::LAlt,
+        KeyboardKey::RAlt,
+        KeyboardKey::LLogo,
+        KeyboardKey::RLogo,
+        KeyboardKey::Minus,
+        KeyboardKey::Equals,
+        KeyboardKey::LBracket,
+        KeyboardKey::RBracket,
+        KeyboardKey::Backslash,
+        KeyboardKey::Semicolon,
+        KeyboardKey::Apostrophe,
+        KeyboardKey::Grave,
+        KeyboardKey::Comma,
+        KeyboardKey::Period,
+        KeyboardKey::Slash,
+        KeyboardKey::Numpad0,
+        KeyboardKey::Numpad1,
+        KeyboardKey::Numpad2,
+        KeyboardKey::Numpad3,
+        KeyboardKey::Numpad4,
+        KeyboardKey::Numpad5,
+        KeyboardKey::Numpad6,
+        KeyboardKey::Numpad7,
+        KeyboardKey::Numpad8,
+        KeyboardKey::Numpad9,
+        KeyboardKey::NumpadAdd,
+        KeyboardKey::NumpadSubtract,
+        KeyboardKey::NumpadMultiply,
+        KeyboardKey::NumpadDivide,
+        KeyboardKey::NumpadDecimal,
+        KeyboardKey::NumpadEnter,
+        KeyboardKey::NumLock,
+        KeyboardKey::PrintScreen,
+        KeyboardKey::ScrollLock,
+        KeyboardKey::Pause,
+    ];
+
+    /// Returns true for shift, ctrl, alt and logo keys
+    pub fn is_modifier(self) -> bool {
+        KeyModifiers::default().with_key(self) != KeyModifiers::default()
+    }
+}
+
+/// Which modifier keys are held. Left and right keys are not distinguished.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyModifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub logo: bool,
+}
+
+impl KeyModifiers {
+    /// Returns these modifiers with the one the given key represents set. Non-modifier keys leave
+    /// them unchanged.
+    pub fn with_key(
+        self,
+        key: KeyboardKey,
+    ) -> Self {
+        self.set_key(key, true)
+    }
+
+    /// Returns these modifiers with the one the given key represents cleared. Non-modifier keys
+    /// leave them unchanged.
+    pub fn without_key(
+        self,
+        key: KeyboardKey,
+    ) -> Self {
+        self.set_key(key, false)
+    }
+
+    fn set_key(
+        mut self,
+        key: KeyboardKey,
+        value: bool,
+    ) -> Self {
+        match key {
+            KeyboardKey::LShift | KeyboardKey::RShift => self.shift = value,
+            KeyboardKey::LCtrl | KeyboardKey::RCtrl => self.ctrl = value,
+            KeyboardKey::LAlt | KeyboardKey::RAlt => self.alt = value,
+            KeyboardKey::LLogo | KeyboardKey::RLogo => self.logo = value,
+            _ => {}
+        }
+
+        self
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct MouseButton(pub u8);
 
 impl MouseButton {
@@ -12,7 +298,71 @@ impl MouseButton {
     pub const MIDDLE: MouseButton = MouseButton(2);
 }
 
-#[derive(Copy, Clone)]
+/// Identifies a connected gamepad. Backends pick the values, they only need to be unique among the
+/// gamepads that are connected at the same time.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct GamepadId(pub u32);
+
+// Buttons are named by position on an xbox style controller, so A is the bottom face button
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum GamepadButton {
+    A,
+    B,
+    X,
+    Y,
+    Back,
+    Guide,
+    Start,
+    LeftStick,
+    RightStick,
+    LeftShoulder,
+    RightShoulder,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+impl GamepadButton {
+    pub const COUNT: usize = 15;
+}
+
+// Stick axes go from -1 to 1, with right and up being positive. Triggers go from 0 to 1.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum GamepadAxis {
+    LeftX,
+    LeftY,
+    RightX,
+    RightY,
+    LeftTrigger,
+    RightTrigger,
+}
+
+impl GamepadAxis {
+    pub const COUNT: usize = 6;
+}
+
+struct GamepadState {
+    id: GamepadId,
+    button_is_down: [bool; GamepadButton::COUNT],
+    button_just_down: [bool; GamepadButton::COUNT],
+    button_just_up: [bool; GamepadButton::COUNT],
+    axes: [f32; GamepadAxis::COUNT],
+}
+
+impl GamepadState {
+    fn new(id: GamepadId) -> Self {
+        GamepadState {
+            id,
+            button_is_down: [false; GamepadButton::COUNT],
+            button_just_down: [false; GamepadButton::COUNT],
+            button_just_up: [false; GamepadButton::COUNT],
+            axes: [0.0; GamepadAxis::COUNT],
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct MouseScrollDelta {
     pub x: f32,
     pub y: f32,
@@ -27,12 +377,66 @@ impl MouseScrollDelta {
     }
 }
 
-#[derive(Copy, Clone, PartialEq)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub enum ButtonState {
     Released,
     Pressed,
 }
 
+/// Tuning for how mouse button presses are turned into clicks, multi-clicks, long presses and drags
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct MouseClickSettings {
+    /// A click counts toward a double or triple click if it follows the previous one within this
+    /// time and distance
+    pub multi_click_interval: Duration,
+    pub multi_click_distance: f32,
+    /// How long a button has to be held without dragging to count as a long press
+    pub long_press_duration: Duration,
+    /// Distance the mouse has to move while a button is held before it's a drag rather than a
+    /// click, indexed by button
+    pub drag_thresholds: [f32; InputState::MOUSE_BUTTON_COUNT as usize],
+}
+
+impl Default for MouseClickSettings {
+    fn default() -> Self {
+        MouseClickSettings {
+            multi_click_interval: Duration::from_millis(500),
+            multi_click_distance: 4.0,
+            long_press_duration: Duration::from_millis(500),
+            drag_thresholds: [2.0; InputState::MOUSE_BUTTON_COUNT as usize],
+        }
+    }
+}
+
+impl MouseClickSettings {
+    pub fn drag_threshold(
+        &self,
+        mouse_button: MouseButton,
+    ) -> f32 {
+        InputState::mouse_button_to_index(mouse_button)
+            .map(|index| self.drag_thresholds[index])
+            .unwrap_or(0.0)
+    }
+
+    pub fn set_drag_threshold(
+        &mut self,
+        mouse_button: MouseButton,
+        threshold: f32,
+    ) {
+        if let Some(index) = InputState::mouse_button_to_index(mouse_button) {
+            self.drag_thresholds[index] = threshold.max(0.0);
+        }
+    }
+}
+
+// The most recent click of a button, used to detect multi-clicks
+#[derive(Copy, Clone)]
+struct MouseClick {
+    time: Duration,
+    position: glam::Vec2,
+    count: u32,
+}
+
 /// Encapsulates the state of a mouse drag
 #[derive(Copy, Clone, Debug)]
 pub struct MouseDragState {
@@ -47,6 +451,8 @@ pub struct InputState {
     key_is_down: [bool; Self::KEYBOARD_BUTTON_COUNT],
     key_just_down: [bool; Self::KEYBOARD_BUTTON_COUNT],
     key_just_up: [bool; Self::KEYBOARD_BUTTON_COUNT],
+    key_just_repeated: [bool; Self::KEYBOARD_BUTTON_COUNT],
+    modifiers: KeyModifiers,
 
     mouse_position: glam::Vec2,
     mouse_wheel_delta: MouseScrollDelta,
@@ -55,25 +461,47 @@ pub struct InputState {
     mouse_button_just_up: [Option<glam::Vec2>; Self::MOUSE_BUTTON_COUNT as usize],
 
     mouse_button_just_clicked: [Option<glam::Vec2>; Self::MOUSE_BUTTON_COUNT as usize],
+    mouse_button_last_click: [Option<MouseClick>; Self::MOUSE_BUTTON_COUNT as usize],
+    mouse_button_just_clicked_count: [u32; Self::MOUSE_BUTTON_COUNT as usize],
+
+    mouse_button_went_down_time: [Option<Duration>; Self::MOUSE_BUTTON_COUNT as usize],
+    mouse_button_just_long_pressed: [Option<glam::Vec2>; Self::MOUSE_BUTTON_COUNT as usize],
+    mouse_button_long_pressed: [bool; Self::MOUSE_BUTTON_COUNT as usize],
 
     mouse_button_went_down_position: [Option<glam::Vec2>; Self::MOUSE_BUTTON_COUNT as usize],
     mouse_button_went_up_position: [Option<glam::Vec2>; Self::MOUSE_BUTTON_COUNT as usize],
 
     mouse_drag_in_progress: [Option<MouseDragState>; Self::MOUSE_BUTTON_COUNT as usize],
     mouse_drag_just_finished: [Option<MouseDragState>; Self::MOUSE_BUTTON_COUNT as usize],
+
+    gamepads: Vec<GamepadState>,
+    gamepads_just_connected: Vec<GamepadId>,
+    gamepads_just_disconnected: Vec<GamepadId>,
+    gamepad_deadzone: f32,
+
+    mouse_click_settings: MouseClickSettings,
+    // Time as of the last call to update_time. Events are stamped with this
+    time: Duration,
+
+    text_input: String,
+    text_input_active: bool,
+
+    recorder: Option<InputRecorder>,
+    player: Option<InputPlayer>,
 }
 
 impl InputState {
-    /// Number of keyboard buttons we will track. Any button with a higher virtual key code will be
-    /// ignored
-    pub const KEYBOARD_BUTTON_COUNT: usize = 255;
+    /// Number of keyboard buttons we will track
+    pub const KEYBOARD_BUTTON_COUNT: usize = KeyboardKey::COUNT;
 
     /// Number of mouse buttons we will track. Any button with a higher index will be ignored.
     pub const MOUSE_BUTTON_COUNT: u8 = 7;
 
-    /// Distance in LogicalPosition units that the mouse has to be dragged to be considered a drag
-    /// rather than a click
-    const MIN_DRAG_DISTANCE: f32 = 2.0;
+    /// Clicks after a triple click start counting from one again
+    const MAX_CLICK_COUNT: u32 = 3;
+
+    /// Default for how far a gamepad axis has to move from rest before it reads as non-zero
+    pub const DEFAULT_GAMEPAD_DEADZONE: f32 = 0.15;
 }
 
 impl InputState {
@@ -83,16 +511,33 @@ impl InputState {
             key_is_down: [false; Self::KEYBOARD_BUTTON_COUNT],
             key_just_down: [false; Self::KEYBOARD_BUTTON_COUNT],
             key_just_up: [false; Self::KEYBOARD_BUTTON_COUNT],
+            key_just_repeated: [false; Self::KEYBOARD_BUTTON_COUNT],
+            modifiers: KeyModifiers::default(),
             mouse_position: glam::Vec2::zero(),
             mouse_wheel_delta: MouseScrollDelta { x: 0.0, y: 0.0 },
             mouse_button_is_down: [false; Self::MOUSE_BUTTON_COUNT as usize],
             mouse_button_just_down: [None; Self::MOUSE_BUTTON_COUNT as usize],
             mouse_button_just_up: [None; Self::MOUSE_BUTTON_COUNT as usize],
             mouse_button_just_clicked: [None; Self::MOUSE_BUTTON_COUNT as usize],
+            mouse_button_last_click: [None; Self::MOUSE_BUTTON_COUNT as usize],
+            mouse_button_just_clicked_count: [0; Self::MOUSE_BUTTON_COUNT as usize],
+            mouse_button_went_down_time: [None; Self::MOUSE_BUTTON_COUNT as usize],
+            mouse_button_just_long_pressed: [None; Self::MOUSE_BUTTON_COUNT as usize],
+            mouse_button_long_pressed: [false; Self::MOUSE_BUTTON_COUNT as usize],
             mouse_button_went_down_position: [None; Self::MOUSE_BUTTON_COUNT as usize],
             mouse_button_went_up_position: [None; Self::MOUSE_BUTTON_COUNT as usize],
             mouse_drag_in_progress: [None; Self::MOUSE_BUTTON_COUNT as usize],
             mouse_drag_just_finished: [None; Self::MOUSE_BUTTON_COUNT as usize],
+            gamepads: Default::default(),
+            gamepads_just_connected: Default::default(),
+            gamepads_just_disconnected: Default::default(),
+            gamepad_deadzone: Self::DEFAULT_GAMEPAD_DEADZONE,
+            mouse_click_settings: Default::default(),
+            time: Duration::default(),
+            text_input: Default::default(),
+            text_input_active: false,
+            recorder: None,
+            player: None,
         }
     }
 
@@ -101,11 +546,7 @@ impl InputState {
         &self,
         key: KeyboardKey,
     ) -> bool {
-        if let Some(index) = Self::keyboard_button_to_index(key) {
-            self.key_is_down[index]
-        } else {
-            false
-        }
+        self.key_is_down[Self::keyboard_button_to_index(key)]
     }
 
     /// Returns true if the key went down during this frame
@@ -113,11 +554,7 @@ impl InputState {
         &self,
         key: KeyboardKey,
     ) -> bool {
-        if let Some(index) = Self::keyboard_button_to_index(key) {
-            self.key_just_down[index]
-        } else {
-            false
-        }
+        self.key_just_down[Self::keyboard_button_to_index(key)]
     }
 
     /// Returns true if the key went up during this frame
@@ -125,11 +562,48 @@ impl InputState {
         &self,
         key: KeyboardKey,
     ) -> bool {
-        if let Some(index) = Self::keyboard_button_to_index(key) {
-            self.key_just_up[index]
-        } else {
-            false
-        }
+        self.key_just_up[Self::keyboard_button_to_index(key)]
+    }
+
+    /// Returns true if the key was held long enough for the platform to repeat it during this
+    /// frame. This does not include the first press.
+    pub fn is_key_just_repeated(
+        &self,
+        key: KeyboardKey,
+    ) -> bool {
+        self.key_just_repeated[Self::keyboard_button_to_index(key)]
+    }
+
+    /// Returns true if the key went down or repeated during this frame. Useful for things like
+    /// text navigation that should keep happening while a key is held.
+    pub fn is_key_just_down_or_repeated(
+        &self,
+        key: KeyboardKey,
+    ) -> bool {
+        self.is_key_just_down(key) || self.is_key_just_repeated(key)
+    }
+
+    /// Returns the modifier keys that are currently held, as reported by the platform
+    pub fn modifiers(&self) -> KeyModifiers {
+        self.modifiers
+    }
+
+    /// Returns the keys that went down during this frame
+    pub fn keys_just_down(&self) -> impl Iterator<Item = KeyboardKey> + '_ {
+        self.key_just_down
+            .iter()
+            .enumerate()
+            .filter(|(_, just_down)| **just_down)
+            .map(|(index, _)| KeyboardKey::ALL[index])
+    }
+
+    /// Returns the keys that went up during this frame
+    pub fn keys_just_up(&self) -> impl Iterator<Item = KeyboardKey> + '_ {
+        self.key_just_up
+            .iter()
+            .enumerate()
+            .filter(|(_, just_up)| **just_up)
+            .map(|(index, _)| KeyboardKey::ALL[index])
     }
 
     /// Get the current mouse position
@@ -229,6 +703,67 @@ impl InputState {
         }
     }
 
+    /// Returns how many clicks in a row the button was just clicked as part of, 0 if it wasn't just
+    /// clicked. This goes up to 3, after which it starts from 1 again.
+    pub fn mouse_button_just_clicked_count(
+        &self,
+        mouse_button: MouseButton,
+    ) -> u32 {
+        if let Some(index) = Self::mouse_button_to_index(mouse_button) {
+            self.mouse_button_just_clicked_count[index]
+        } else {
+            0
+        }
+    }
+
+    /// Returns true if the button was just clicked for the second time in a row. See
+    /// MouseClickSettings for how close together the clicks need to be.
+    pub fn is_mouse_button_just_double_clicked(
+        &self,
+        mouse_button: MouseButton,
+    ) -> bool {
+        self.mouse_button_just_clicked_count(mouse_button) == 2
+    }
+
+    /// Returns true if the button was just clicked for the third time in a row
+    pub fn is_mouse_button_just_triple_clicked(
+        &self,
+        mouse_button: MouseButton,
+    ) -> bool {
+        self.mouse_button_just_clicked_count(mouse_button) == 3
+    }
+
+    /// Returns true if the button has just been held long enough, without dragging, to be a long
+    /// press. This happens once per press, while the button is still down. Releasing the button
+    /// afterward is still reported as a click.
+    pub fn is_mouse_button_just_long_pressed(
+        &self,
+        mouse_button: MouseButton,
+    ) -> bool {
+        self.mouse_button_just_long_pressed_position(mouse_button)
+            .is_some()
+    }
+
+    /// Returns the position the button went down at if it just became a long press, otherwise None
+    pub fn mouse_button_just_long_pressed_position(
+        &self,
+        mouse_button: MouseButton,
+    ) -> Option<glam::Vec2> {
+        if let Some(index) = Self::mouse_button_to_index(mouse_button) {
+            self.mouse_button_just_long_pressed[index]
+        } else {
+            None
+        }
+    }
+
+    pub fn mouse_click_settings(&self) -> &MouseClickSettings {
+        &self.mouse_click_settings
+    }
+
+    pub fn mouse_click_settings_mut(&mut self) -> &mut MouseClickSettings {
+        &mut self.mouse_click_settings
+    }
+
     /// Returns the position the button went down at previously. This could have been some time ago.
     pub fn mouse_button_went_down_position(
         &self,
@@ -302,6 +837,265 @@ impl InputState {
         }
     }
 
+    /// Returns the gamepads that are currently connected, in the order they were connected
+    pub fn connected_gamepads(&self) -> impl Iterator<Item = GamepadId> + '_ {
+        self.gamepads.iter().map(|gamepad| gamepad.id)
+    }
+
+    /// Returns the gamepads that were connected during this frame
+    pub fn gamepads_just_connected(&self) -> &[GamepadId] {
+        &self.gamepads_just_connected
+    }
+
+    /// Returns the gamepads that were disconnected during this frame
+    pub fn gamepads_just_disconnected(&self) -> &[GamepadId] {
+        &self.gamepads_just_disconnected
+    }
+
+    /// Returns true if the given button is down. Always false for disconnected gamepads.
+    pub fn is_gamepad_button_down(
+        &self,
+        gamepad: GamepadId,
+        button: GamepadButton,
+    ) -> bool {
+        self.gamepad(gamepad)
+            .map(|gamepad| gamepad.button_is_down[button as usize])
+            .unwrap_or(false)
+    }
+
+    /// Returns true if the button went down during this frame
+    pub fn is_gamepad_button_just_down(
+        &self,
+        gamepad: GamepadId,
+        button: GamepadButton,
+    ) -> bool {
+        self.gamepad(gamepad)
+            .map(|gamepad| gamepad.button_just_down[button as usize])
+            .unwrap_or(false)
+    }
+
+    /// Returns true if the button went up during this frame
+    pub fn is_gamepad_button_just_up(
+        &self,
+        gamepad: GamepadId,
+        button: GamepadButton,
+    ) -> bool {
+        self.gamepad(gamepad)
+            .map(|gamepad| gamepad.button_just_up[button as usize])
+            .unwrap_or(false)
+    }
+
+    /// Returns true if the button is down on any connected gamepad
+    pub fn is_any_gamepad_button_down(
+        &self,
+        button: GamepadButton,
+    ) -> bool {
+        self.gamepads
+            .iter()
+            .any(|gamepad| gamepad.button_is_down[button as usize])
+    }
+
+    /// Returns the axis value with the deadzone applied. Values inside the deadzone read as zero
+    /// and the rest of the range is rescaled so the axis still reaches 1.
+    pub fn gamepad_axis(
+        &self,
+        gamepad: GamepadId,
+        axis: GamepadAxis,
+    ) -> f32 {
+        Self::apply_deadzone(self.gamepad_axis_raw(gamepad, axis), self.gamepad_deadzone)
+    }
+
+    /// Returns the axis value as reported by the gamepad, without a deadzone
+    pub fn gamepad_axis_raw(
+        &self,
+        gamepad: GamepadId,
+        axis: GamepadAxis,
+    ) -> f32 {
+        self.gamepad(gamepad)
+            .map(|gamepad| gamepad.axes[axis as usize])
+            .unwrap_or(0.0)
+    }
+
+    /// Returns the axis value furthest from zero among all connected gamepads, with the deadzone
+    /// applied
+    pub fn any_gamepad_axis(
+        &self,
+        axis: GamepadAxis,
+    ) -> f32 {
+        self.gamepads
+            .iter()
+            .map(|gamepad| self.gamepad_axis(gamepad.id, axis))
+            .fold(0.0, |value: f32, axis_value| {
+                if axis_value.abs() > value.abs() {
+                    axis_value
+                } else {
+                    value
+                }
+            })
+    }
+
+    pub fn gamepad_deadzone(&self) -> f32 {
+        self.gamepad_deadzone
+    }
+
+    pub fn set_gamepad_deadzone(
+        &mut self,
+        deadzone: f32,
+    ) {
+        self.gamepad_deadzone = deadzone.max(0.0).min(0.99);
+    }
+
+    fn gamepad(
+        &self,
+        gamepad: GamepadId,
+    ) -> Option<&GamepadState> {
+        self.gamepads.iter().find(|state| state.id == gamepad)
+    }
+
+    // Events for a gamepad we haven't heard of yet (for example one that was connected before the
+    // window opened) connect it first. That connection isn't recorded, since replaying the event
+    // that caused it connects the gamepad the same way
+    fn gamepad_mut(
+        &mut self,
+        gamepad: GamepadId,
+    ) -> &mut GamepadState {
+        self.connect_gamepad(gamepad);
+
+        self.gamepads
+            .iter_mut()
+            .find(|state| state.id == gamepad)
+            .unwrap()
+    }
+
+    fn apply_deadzone(
+        value: f32,
+        deadzone: f32,
+    ) -> f32 {
+        if value.abs() <= deadzone {
+            0.0
+        } else {
+            value.signum() * (value.abs() - deadzone) / (1.0 - deadzone)
+        }
+    }
+
+    /// Text typed this frame, including characters composed with an IME. Empty unless text input
+    /// has been started.
+    pub fn text_input(&self) -> &str {
+        &self.text_input
+    }
+
+    pub fn is_text_input_active(&self) -> bool {
+        self.text_input_active
+    }
+
+    /// Start collecting typed text, for example when a text field gains focus. Backends use this
+    /// to decide whether to enable the IME.
+    pub fn start_text_input(&mut self) {
+        self.text_input_active = true;
+    }
+
+    pub fn stop_text_input(&mut self) {
+        self.text_input_active = false;
+        self.text_input.clear();
+    }
+
+    /// Call once a frame with the current time. Multi-clicks and long presses are measured with
+    /// this rather than the wall clock so that they behave the same when input is played back.
+    pub fn update_time(
+        &mut self,
+        time: Duration,
+    ) {
+        self.time = time;
+
+        for index in 0..Self::MOUSE_BUTTON_COUNT as usize {
+            if let Some(went_down_time) = self.mouse_button_went_down_time[index] {
+                let is_long_press = !self.mouse_button_long_pressed[index]
+                    && self.mouse_drag_in_progress[index].is_none()
+                    && time >= went_down_time + self.mouse_click_settings.long_press_duration;
+                if is_long_press {
+                    self.mouse_button_long_pressed[index] = true;
+                    self.mouse_button_just_long_pressed[index] =
+                        self.mouse_button_went_down_position[index];
+                }
+            }
+        }
+    }
+
+    //
+    // Recording and playback
+    //
+
+    /// Start capturing every event passed into the handlers below, replacing any recording that
+    /// was already in progress. Frames are counted by calls to end_frame.
+    pub fn start_recording(
+        &mut self,
+        frame_time: Duration,
+    ) {
+        self.recorder = Some(InputRecorder::new(frame_time));
+    }
+
+    pub fn stop_recording(&mut self) -> Option<InputRecording> {
+        self.recorder.take().map(|recorder| recorder.finish())
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
+    }
+
+    /// Replay a recording starting with the next call to play_frame. Events from the backend are
+    /// ignored until it finishes or stop_playback is called.
+    pub fn start_playback(
+        &mut self,
+        recording: InputRecording,
+    ) {
+        self.player = Some(InputPlayer::new(recording));
+    }
+
+    pub fn stop_playback(&mut self) -> Option<InputRecording> {
+        self.player.take().map(|player| player.finish())
+    }
+
+    pub fn is_playing_back(&self) -> bool {
+        self.player.is_some()
+    }
+
+    /// The timestep that time should advance by while recording or playing back
+    pub fn fixed_frame_time(&self) -> Option<Duration> {
+        if let Some(player) = &self.player {
+            Some(player.frame_time())
+        } else {
+            self.recorder.as_ref().map(|recorder| recorder.frame_time())
+        }
+    }
+
+    /// Call at the start of every frame. Injects the events recorded for this frame if a recording
+    /// is playing.
+    pub fn play_frame(&mut self) {
+        // Take the player out so the injected events aren't rejected as live input
+        if let Some(mut player) = self.player.take() {
+            for recorded in player.frame_events() {
+                recorded.event.clone().apply(self);
+            }
+            self.player = Some(player);
+        }
+    }
+
+    // Returns false if the event should be dropped because a recording is playing
+    fn accept_event(
+        &mut self,
+        event: InputEvent,
+    ) -> bool {
+        if self.player.is_some() {
+            return false;
+        }
+
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record(event);
+        }
+
+        true
+    }
+
     //
     // Handlers for significant events
     //
@@ -318,6 +1112,10 @@ impl InputState {
             *value = false;
         }
 
+        for value in self.key_just_repeated.iter_mut() {
+            *value = false;
+        }
+
         for value in self.mouse_button_just_down.iter_mut() {
             *value = None;
         }
@@ -330,6 +1128,14 @@ impl InputState {
             *value = None;
         }
 
+        for value in self.mouse_button_just_clicked_count.iter_mut() {
+            *value = 0;
+        }
+
+        for value in self.mouse_button_just_long_pressed.iter_mut() {
+            *value = None;
+        }
+
         for value in self.mouse_drag_just_finished.iter_mut() {
             *value = None;
         }
@@ -340,28 +1146,68 @@ impl InputState {
                 //v.world_space_previous_frame_delta = glam::Vec2::zero()
             }
         }
+
+        for gamepad in &mut self.gamepads {
+            gamepad.button_just_down = [false; GamepadButton::COUNT];
+            gamepad.button_just_up = [false; GamepadButton::COUNT];
+        }
+
+        self.gamepads_just_connected.clear();
+        self.gamepads_just_disconnected.clear();
+
+        self.text_input.clear();
+
+        if let Some(recorder) = &mut self.recorder {
+            recorder.end_frame();
+        }
+
+        if let Some(player) = &mut self.player {
+            player.end_frame();
+            if player.is_finished() {
+                log::info!("Finished playing back input recording");
+                self.player = None;
+            }
+        }
     }
 
-    /// Call when a key event occurs
+    /// Call when a key event occurs. A press for a key that is already down is treated as a repeat.
     pub fn handle_keyboard_event(
         &mut self,
         keyboard_button: KeyboardKey,
         button_state: ButtonState,
     ) {
-        if let Some(kc) = Self::keyboard_button_to_index(keyboard_button) {
-            // Assign true if key is down, or false if key is up
-            if button_state == ButtonState::Pressed {
-                if !self.key_is_down[kc] {
-                    self.key_just_down[kc] = true;
-                }
-                self.key_is_down[kc] = true
+        if !self.accept_event(InputEvent::Keyboard(keyboard_button, button_state)) {
+            return;
+        }
+
+        let kc = Self::keyboard_button_to_index(keyboard_button);
+
+        // Assign true if key is down, or false if key is up
+        if button_state == ButtonState::Pressed {
+            if self.key_is_down[kc] {
+                self.key_just_repeated[kc] = true;
             } else {
-                if self.key_is_down[kc] {
-                    self.key_just_up[kc] = true;
-                }
-                self.key_is_down[kc] = false
+                self.key_just_down[kc] = true;
             }
+            self.key_is_down[kc] = true
+        } else {
+            if self.key_is_down[kc] {
+                self.key_just_up[kc] = true;
+            }
+            self.key_is_down[kc] = false
+        }
+    }
+
+    /// Call when the held modifier keys change
+    pub fn handle_modifiers_event(
+        &mut self,
+        modifiers: KeyModifiers,
+    ) {
+        if !self.accept_event(InputEvent::Modifiers(modifiers)) {
+            return;
         }
+
+        self.modifiers = modifiers;
     }
 
     /// Call when a mouse button event occurs
@@ -370,6 +1216,10 @@ impl InputState {
         button: MouseButton,
         button_event: ButtonState,
     ) {
+        if !self.accept_event(InputEvent::MouseButton(button, button_event)) {
+            return;
+        }
+
         if let Some(button_index) = Self::mouse_button_to_index(button) {
             assert!(button_index < InputState::MOUSE_BUTTON_COUNT as usize);
 
@@ -380,6 +1230,8 @@ impl InputState {
                     self.mouse_button_is_down[button_index] = true;
 
                     self.mouse_button_went_down_position[button_index] = Some(self.mouse_position);
+                    self.mouse_button_went_down_time[button_index] = Some(self.time);
+                    self.mouse_button_long_pressed[button_index] = false;
                 }
                 ButtonState::Released => {
                     self.mouse_button_just_up[button_index] = Some(self.mouse_position);
@@ -402,21 +1254,54 @@ impl InputState {
                             });
                         }
                         None => {
-                            self.mouse_button_just_clicked[button_index] = Some(self.mouse_position)
+                            self.mouse_button_just_clicked[button_index] =
+                                Some(self.mouse_position);
+                            self.handle_click(button_index);
                         }
                     }
 
                     self.mouse_drag_in_progress[button_index] = None;
+                    self.mouse_button_went_down_time[button_index] = None;
                 }
             }
         }
     }
 
+    // Counts the click toward a multi-click if it's close enough to the previous one
+    fn handle_click(
+        &mut self,
+        button_index: usize,
+    ) {
+        let settings = &self.mouse_click_settings;
+        let count = match self.mouse_button_last_click[button_index] {
+            Some(last_click)
+                if last_click.count < Self::MAX_CLICK_COUNT
+                    && self.time <= last_click.time + settings.multi_click_interval
+                    && glam::Vec2::length(last_click.position - self.mouse_position)
+                        <= settings.multi_click_distance =>
+            {
+                last_click.count + 1
+            }
+            _ => 1,
+        };
+
+        self.mouse_button_last_click[button_index] = Some(MouseClick {
+            time: self.time,
+            position: self.mouse_position,
+            count,
+        });
+        self.mouse_button_just_clicked_count[button_index] = count;
+    }
+
     /// Call when a mouse move occurs
     pub fn handle_mouse_move_event(
         &mut self,
         position: glam::Vec2,
     ) {
+        if !self.accept_event(InputEvent::MouseMove(position)) {
+            return;
+        }
+
         // Update mouse position
         self.mouse_position = position;
 
@@ -430,7 +1315,7 @@ impl InputState {
                             Some(went_down_position) => {
                                 let min_drag_distance_met =
                                     glam::Vec2::length(went_down_position - self.mouse_position)
-                                        > Self::MIN_DRAG_DISTANCE;
+                                        > self.mouse_click_settings.drag_thresholds[i];
                                 if min_drag_distance_met {
                                     let delta = self.mouse_position - went_down_position;
 
@@ -474,10 +1359,106 @@ impl InputState {
         &mut self,
         delta: MouseScrollDelta,
     ) {
+        if !self.accept_event(InputEvent::MouseWheel(delta)) {
+            return;
+        }
+
         self.mouse_wheel_delta.x += delta.x;
         self.mouse_wheel_delta.y += delta.y;
     }
 
+    /// Call when a gamepad is plugged in
+    pub fn handle_gamepad_connected(
+        &mut self,
+        gamepad: GamepadId,
+    ) {
+        if !self.accept_event(InputEvent::GamepadConnected(gamepad)) {
+            return;
+        }
+
+        self.connect_gamepad(gamepad);
+    }
+
+    fn connect_gamepad(
+        &mut self,
+        gamepad: GamepadId,
+    ) {
+        if self.gamepad(gamepad).is_none() {
+            self.gamepads.push(GamepadState::new(gamepad));
+            self.gamepads_just_connected.push(gamepad);
+        }
+    }
+
+    /// Call when a gamepad is unplugged. Its buttons and axes read as released from then on.
+    pub fn handle_gamepad_disconnected(
+        &mut self,
+        gamepad: GamepadId,
+    ) {
+        if !self.accept_event(InputEvent::GamepadDisconnected(gamepad)) {
+            return;
+        }
+
+        let count = self.gamepads.len();
+        self.gamepads.retain(|state| state.id != gamepad);
+        if self.gamepads.len() != count {
+            self.gamepads_just_disconnected.push(gamepad);
+        }
+    }
+
+    /// Call when a gamepad button is pressed or released
+    pub fn handle_gamepad_button_event(
+        &mut self,
+        gamepad: GamepadId,
+        button: GamepadButton,
+        button_state: ButtonState,
+    ) {
+        if !self.accept_event(InputEvent::GamepadButton(gamepad, button, button_state)) {
+            return;
+        }
+
+        let gamepad = self.gamepad_mut(gamepad);
+        let index = button as usize;
+        if button_state == ButtonState::Pressed {
+            if !gamepad.button_is_down[index] {
+                gamepad.button_just_down[index] = true;
+            }
+            gamepad.button_is_down[index] = true;
+        } else {
+            if gamepad.button_is_down[index] {
+                gamepad.button_just_up[index] = true;
+            }
+            gamepad.button_is_down[index] = false;
+        }
+    }
+
+    /// Call when a gamepad axis moves. See GamepadAxis for the expected range.
+    pub fn handle_gamepad_axis_event(
+        &mut self,
+        gamepad: GamepadId,
+        axis: GamepadAxis,
+        value: f32,
+    ) {
+        if !self.accept_event(InputEvent::GamepadAxis(gamepad, axis, value)) {
+            return;
+        }
+
+        self.gamepad_mut(gamepad).axes[axis as usize] = value.max(-1.0).min(1.0);
+    }
+
+    /// Call when the backend produces text. Ignored unless text input has been started.
+    pub fn handle_text_input_event(
+        &mut self,
+        text: &str,
+    ) {
+        if !self.accept_event(InputEvent::TextInput(text.to_string())) {
+            return;
+        }
+
+        if self.text_input_active {
+            self.text_input.push_str(text);
+        }
+    }
+
     /// Convert the winit mouse button enum into a numerical index
     pub fn mouse_button_to_index(button: MouseButton) -> Option<usize> {
         if button.0 >= Self::MOUSE_BUTTON_COUNT {
@@ -496,12 +1477,147 @@ impl InputState {
         }
     }
 
-    /// Convert the winit virtual key code into a numerical index
-    pub fn keyboard_button_to_index(button: KeyboardKey) -> Option<usize> {
-        if button.0 as usize >= Self::KEYBOARD_BUTTON_COUNT {
-            None
-        } else {
-            Some(button.0 as usize)
+    /// Convert the key into a numerical index
+    pub fn keyboard_button_to_index(button: KeyboardKey) -> usize {
+        button as usize
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // InputState maps indices back to keys with KeyboardKey::ALL, so it must be in declaration order
+    #[test]
+    fn all_keys_are_in_index_order() {
+        for (index, key) in KeyboardKey::ALL.iter().enumerate() {
+            assert_eq!(InputState::keyboard_button_to_index(*key), index);
         }
     }
+
+    #[test]
+    fn gamepad_buttons_and_axes() {
+        let gamepad = GamepadId(3);
+        let mut input_state = InputState::new();
+        input_state.handle_gamepad_connected(gamepad);
+        assert_eq!(input_state.gamepads_just_connected(), &[gamepad]);
+
+        input_state.handle_gamepad_button_event(gamepad, GamepadButton::A, ButtonState::Pressed);
+        input_state.handle_gamepad_axis_event(gamepad, GamepadAxis::LeftX, 0.1);
+        input_state.handle_gamepad_axis_event(gamepad, GamepadAxis::LeftY, -1.0);
+        assert!(input_state.is_gamepad_button_just_down(gamepad, GamepadButton::A));
+        assert!(input_state.is_any_gamepad_button_down(GamepadButton::A));
+        assert_eq!(input_state.gamepad_axis(gamepad, GamepadAxis::LeftX), 0.0);
+        assert_eq!(
+            input_state.gamepad_axis_raw(gamepad, GamepadAxis::LeftX),
+            0.1
+        );
+        assert_eq!(input_state.gamepad_axis(gamepad, GamepadAxis::LeftY), -1.0);
+
+        input_state.end_frame();
+        assert!(input_state.gamepads_just_connected().is_empty());
+        assert!(!input_state.is_gamepad_button_just_down(gamepad, GamepadButton::A));
+        assert!(input_state.is_gamepad_button_down(gamepad, GamepadButton::A));
+
+        input_state.handle_gamepad_disconnected(gamepad);
+        assert_eq!(input_state.gamepads_just_disconnected(), &[gamepad]);
+        assert!(!input_state.is_gamepad_button_down(gamepad, GamepadButton::A));
+        assert_eq!(input_state.connected_gamepads().count(), 0);
+    }
+
+    #[test]
+    fn pressing_a_held_key_again_is_a_repeat() {
+        let mut input_state = InputState::new();
+        input_state.handle_keyboard_event(KeyboardKey::Left, ButtonState::Pressed);
+        assert!(input_state.is_key_just_down(KeyboardKey::Left));
+        assert!(!input_state.is_key_just_repeated(KeyboardKey::Left));
+
+        input_state.end_frame();
+        input_state.handle_keyboard_event(KeyboardKey::Left, ButtonState::Pressed);
+        assert!(!input_state.is_key_just_down(KeyboardKey::Left));
+        assert!(input_state.is_key_just_repeated(KeyboardKey::Left));
+        assert!(input_state.is_key_down(KeyboardKey::Left));
+    }
+
+    #[test]
+    fn clicks_in_quick_succession_are_multi_clicks() {
+        let mut input_state = InputState::new();
+        let click = |input_state: &mut InputState, millis| {
+            input_state.update_time(Duration::from_millis(millis));
+            input_state.handle_mouse_button_event(MouseButton::LEFT, ButtonState::Pressed);
+            input_state.handle_mouse_button_event(MouseButton::LEFT, ButtonState::Released);
+        };
+
+        click(&mut input_state, 0);
+        assert_eq!(
+            input_state.mouse_button_just_clicked_count(MouseButton::LEFT),
+            1
+        );
+        input_state.end_frame();
+        assert_eq!(
+            input_state.mouse_button_just_clicked_count(MouseButton::LEFT),
+            0
+        );
+
+        click(&mut input_state, 200);
+        assert!(input_state.is_mouse_button_just_double_clicked(MouseButton::LEFT));
+        input_state.end_frame();
+
+        click(&mut input_state, 400);
+        assert!(input_state.is_mouse_button_just_triple_clicked(MouseButton::LEFT));
+        input_state.end_frame();
+
+        // Too late to continue the sequence
+        click(&mut input_state, 2000);
+        assert_eq!(
+            input_state.mouse_button_just_clicked_count(MouseButton::LEFT),
+            1
+        );
+    }
+
+    #[test]
+    fn holding_a_button_is_a_long_press() {
+        let mut input_state = InputState::new();
+        input_state.handle_mouse_button_event(MouseButton::RIGHT, ButtonState::Pressed);
+        input_state.update_time(Duration::from_millis(100));
+        assert!(!input_state.is_mouse_button_just_long_pressed(MouseButton::RIGHT));
+
+        input_state.update_time(Duration::from_millis(600));
+        assert!(input_state.is_mouse_button_just_long_pressed(MouseButton::RIGHT));
+        input_state.end_frame();
+
+        // Only reported once per press
+        input_state.update_time(Duration::from_millis(1200));
+        assert!(!input_state.is_mouse_button_just_long_pressed(MouseButton::RIGHT));
+    }
+
+    #[test]
+    fn drag_threshold_is_per_button() {
+        let mut input_state = InputState::new();
+        input_state
+            .mouse_click_settings_mut()
+            .set_drag_threshold(MouseButton::LEFT, 10.0);
+
+        input_state.handle_mouse_button_event(MouseButton::LEFT, ButtonState::Pressed);
+        input_state.handle_mouse_button_event(MouseButton::RIGHT, ButtonState::Pressed);
+        input_state.handle_mouse_move_event(glam::Vec2::new(5.0, 0.0));
+        assert!(!input_state.is_mouse_drag_in_progress(MouseButton::LEFT));
+        assert!(input_state.is_mouse_drag_in_progress(MouseButton::RIGHT));
+    }
+
+    #[test]
+    fn text_input_is_collected_while_started() {
+        let mut input_state = InputState::new();
+        input_state.handle_text_input_event("a");
+        assert_eq!(input_state.text_input(), "");
+
+        input_state.start_text_input();
+        input_state.handle_text_input_event("h");
+        input_state.handle_text_input_event("é");
+        assert_eq!(input_state.text_input(), "hé");
+
+        input_state.end_frame();
+        assert_eq!(input_state.text_input(), "");
+        assert!(input_state.is_text_input_active());
+    }
 }
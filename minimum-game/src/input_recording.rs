@@ -0,0 +1,288 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Duration;
+
+use crate::input::{
+    ButtonState, GamepadAxis, GamepadButton, GamepadId, InputState, KeyModifiers, KeyboardKey,
+    MouseButton, MouseScrollDelta,
+};
+
+/// One event as it was passed into InputState
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum InputEvent {
+    Keyboard(KeyboardKey, ButtonState),
+    Modifiers(KeyModifiers),
+    MouseButton(MouseButton, ButtonState),
+    MouseMove(glam::Vec2),
+    MouseWheel(MouseScrollDelta),
+    GamepadConnected(GamepadId),
+    GamepadDisconnected(GamepadId),
+    GamepadButton(GamepadId, GamepadButton, ButtonState),
+    GamepadAxis(GamepadId, GamepadAxis, f32),
+    TextInput(String),
+}
+
+impl InputEvent {
+    /// Feed the event into the input state, the same way the backend originally did
+    pub fn apply(
+        self,
+        input_state: &mut InputState,
+    ) {
+        match self {
+            InputEvent::Keyboard(key, state) => input_state.handle_keyboard_event(key, state),
+            InputEvent::Modifiers(modifiers) => input_state.handle_modifiers_event(modifiers),
+            InputEvent::MouseButton(button, state) => {
+                input_state.handle_mouse_button_event(button, state)
+            }
+            InputEvent::MouseMove(position) => input_state.handle_mouse_move_event(position),
+            InputEvent::MouseWheel(delta) => input_state.handle_mouse_wheel_event(delta),
+            InputEvent::GamepadConnected(gamepad) => input_state.handle_gamepad_connected(gamepad),
+            InputEvent::GamepadDisconnected(gamepad) => {
+                input_state.handle_gamepad_disconnected(gamepad)
+            }
+            InputEvent::GamepadButton(gamepad, button, state) => {
+                input_state.handle_gamepad_button_event(gamepad, button, state)
+            }
+            InputEvent::GamepadAxis(gamepad, axis, value) => {
+                input_state.handle_gamepad_axis_event(gamepad, axis, value)
+            }
+            InputEvent::TextInput(text) => input_state.handle_text_input_event(&text),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct RecordedInputEvent {
+    /// Number of frames since the recording started
+    pub frame: u64,
+    pub event: InputEvent,
+}
+
+#[derive(Debug)]
+pub enum InputRecordingFileError {
+    Io(std::io::Error),
+    Deserialize(ron::de::Error),
+    Serialize(ron::ser::Error),
+}
+
+impl From<std::io::Error> for InputRecordingFileError {
+    fn from(error: std::io::Error) -> Self {
+        InputRecordingFileError::Io(error)
+    }
+}
+
+impl From<ron::de::Error> for InputRecordingFileError {
+    fn from(error: ron::de::Error) -> Self {
+        InputRecordingFileError::Deserialize(error)
+    }
+}
+
+impl From<ron::ser::Error> for InputRecordingFileError {
+    fn from(error: ron::ser::Error) -> Self {
+        InputRecordingFileError::Serialize(error)
+    }
+}
+
+/// Every input event over a number of frames. Both recording and playback advance time by exactly
+/// frame_time each frame, so a recording replays the same way it was played as long as it starts
+/// from the same state (for example right after a level is loaded)
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct InputRecording {
+    pub frame_time: Duration,
+    pub frame_count: u64,
+    pub events: Vec<RecordedInputEvent>,
+}
+
+impl InputRecording {
+    pub fn new(frame_time: Duration) -> Self {
+        InputRecording {
+            frame_time,
+            frame_count: 0,
+            events: Default::default(),
+        }
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, InputRecordingFileError> {
+        let contents = std::fs::read_to_string(path)?;
+        Ok(ron::de::from_str(&contents)?)
+    }
+
+    pub fn save<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<(), InputRecordingFileError> {
+        let contents = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+}
+
+/// Appends events to a recording. InputState owns this while recording.
+pub struct InputRecorder {
+    recording: InputRecording,
+}
+
+impl InputRecorder {
+    pub fn new(frame_time: Duration) -> Self {
+        InputRecorder {
+            recording: InputRecording::new(frame_time),
+        }
+    }
+
+    pub fn frame_time(&self) -> Duration {
+        self.recording.frame_time
+    }
+
+    pub fn record(
+        &mut self,
+        event: InputEvent,
+    ) {
+        self.recording.events.push(RecordedInputEvent {
+            frame: self.recording.frame_count,
+            event,
+        });
+    }
+
+    pub fn end_frame(&mut self) {
+        self.recording.frame_count += 1;
+    }
+
+    pub fn finish(self) -> InputRecording {
+        self.recording
+    }
+}
+
+/// Re-injects a recording one frame at a time. InputState owns this while playing back.
+pub struct InputPlayer {
+    recording: InputRecording,
+    frame: u64,
+    next_event: usize,
+}
+
+impl InputPlayer {
+    pub fn new(recording: InputRecording) -> Self {
+        InputPlayer {
+            recording,
+            frame: 0,
+            next_event: 0,
+        }
+    }
+
+    pub fn frame_time(&self) -> Duration {
+        self.recording.frame_time
+    }
+
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.frame >= self.recording.frame_count
+    }
+
+    /// Returns the events that were recorded for the current frame
+    pub fn frame_events(&mut self) -> &[RecordedInputEvent] {
+        let first = self.next_event;
+        let events = &self.recording.events[first..];
+        let count = events
+            .iter()
+            .take_while(|event| event.frame <= self.frame)
+            .count();
+        self.next_event += count;
+        &events[..count]
+    }
+
+    pub fn end_frame(&mut self) {
+        self.frame += 1;
+    }
+
+    pub fn finish(self) -> InputRecording {
+        self.recording
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn playback_reproduces_recorded_frames() {
+        let frame_time = Duration::from_micros(16_667);
+        let mut input_state = InputState::new();
+        input_state.start_recording(frame_time);
+
+        input_state.handle_mouse_move_event(glam::Vec2::new(10.0, 10.0));
+        input_state.handle_keyboard_event(KeyboardKey::W, ButtonState::Pressed);
+        input_state.end_frame();
+        input_state.end_frame();
+        input_state.handle_mouse_button_event(MouseButton::LEFT, ButtonState::Pressed);
+        input_state.handle_keyboard_event(KeyboardKey::W, ButtonState::Released);
+        input_state.end_frame();
+
+        let recording = input_state.stop_recording().unwrap();
+        assert_eq!(recording.frame_count, 3);
+        assert_eq!(recording.events.len(), 4);
+
+        let contents =
+            ron::ser::to_string_pretty(&recording, ron::ser::PrettyConfig::default()).unwrap();
+        let recording: InputRecording = ron::de::from_str(&contents).unwrap();
+
+        let mut input_state = InputState::new();
+        input_state.start_playback(recording);
+        assert_eq!(input_state.fixed_frame_time(), Some(frame_time));
+
+        input_state.play_frame();
+        // Live events are ignored while a recording is playing
+        input_state.handle_keyboard_event(KeyboardKey::S, ButtonState::Pressed);
+        assert!(input_state.is_key_just_down(KeyboardKey::W));
+        assert!(!input_state.is_key_down(KeyboardKey::S));
+        assert_eq!(input_state.mouse_position(), glam::Vec2::new(10.0, 10.0));
+        input_state.end_frame();
+
+        input_state.play_frame();
+        assert!(input_state.is_key_down(KeyboardKey::W));
+        assert!(!input_state.is_mouse_down(MouseButton::LEFT));
+        input_state.end_frame();
+
+        input_state.play_frame();
+        assert!(input_state.is_key_just_up(KeyboardKey::W));
+        assert!(input_state.is_mouse_just_down(MouseButton::LEFT));
+        input_state.end_frame();
+
+        assert!(!input_state.is_playing_back());
+        assert_eq!(input_state.fixed_frame_time(), None);
+    }
+
+    #[test]
+    fn implicit_gamepad_connect_is_not_recorded() {
+        let mut input_state = InputState::new();
+        input_state.start_recording(Duration::from_micros(16_667));
+
+        // The gamepad was never reported as connected, so the button event connects it
+        input_state.handle_gamepad_button_event(
+            GamepadId(0),
+            GamepadButton::A,
+            ButtonState::Pressed,
+        );
+        input_state.end_frame();
+
+        let recording = input_state.stop_recording().unwrap();
+        assert_eq!(
+            recording.events,
+            vec![RecordedInputEvent {
+                frame: 0,
+                event: InputEvent::GamepadButton(
+                    GamepadId(0),
+                    GamepadButton::A,
+                    ButtonState::Pressed
+                ),
+            }]
+        );
+
+        let mut input_state = InputState::new();
+        input_state.start_playback(recording);
+        input_state.play_frame();
+        assert_eq!(input_state.gamepads_just_connected(), &[GamepadId(0)]);
+        assert!(input_state.is_gamepad_button_just_down(GamepadId(0), GamepadButton::A));
+    }
+}
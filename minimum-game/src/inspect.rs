@@ -0,0 +1,53 @@
+use std::fmt::Debug;
+use imgui::{ImStr, ImString};
+
+// A fieldless enum that is edited with a combo box. Each variant is listed by its Debug name.
+pub trait InspectEnum: Copy + PartialEq + Debug + 'static {
+    const VARIANTS: &'static [Self];
+}
+
+// Call from InspectRenderDefault::render() to show the enum as read-only text
+pub fn render_enum<T: InspectEnum>(
+    data: &[&T],
+    label: &'static str,
+    ui: &imgui::Ui,
+) {
+    if data.is_empty() {
+        return;
+    }
+
+    ui.text(&imgui::im_str!("{}: {:?}", label, data[0]));
+}
+
+// Call from InspectRenderDefault::render_mut() to pick a variant from a combo box. The picked
+// variant is written to every selected value.
+pub fn render_enum_mut<T: InspectEnum>(
+    data: &mut [&mut T],
+    label: &'static str,
+    ui: &imgui::Ui,
+) -> bool {
+    if data.is_empty() {
+        return false;
+    }
+
+    let names: Vec<ImString> = T::VARIANTS
+        .iter()
+        .map(|variant| imgui::im_str!("{:?}", variant))
+        .collect();
+    let names: Vec<&ImStr> = names.iter().map(|name| &**name).collect();
+    let mut index = T::VARIANTS
+        .iter()
+        .position(|variant| *variant == *data[0])
+        .unwrap_or(0);
+
+    let changed = imgui::ComboBox::new(&imgui::im_str!("{}", label))
+        .build_simple_string(ui, &mut index, &names);
+
+    if changed {
+        for d in data {
+            **d = T::VARIANTS[index];
+        }
+    }
+
+    changed
+}
@@ -4,8 +4,11 @@ extern crate log;
 
 pub mod resources;
 pub mod systems;
+pub mod pipeline;
 
 pub mod input;
+pub mod input_recording;
 
 pub mod imgui;
+pub mod inspect;
 pub use crate::imgui::ImguiManager;
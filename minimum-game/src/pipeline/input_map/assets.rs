@@ -0,0 +1,158 @@
+use serde::{Deserialize, Serialize};
+use type_uuid::TypeUuid;
+
+use crate::input::{InputState, KeyboardKey, KeyModifiers, MouseButton, GamepadButton, GamepadAxis};
+
+// A single input that is either held or not
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum InputMapButton {
+    Key(KeyboardKey),
+    // Held while the key and exactly these modifiers are held. The key itself doesn't count as a
+    // held modifier, so a modifier key can be bound on its own
+    KeyChord(KeyboardKey, KeyModifiers),
+    // Index of the button, 0 is left, 1 is right and 2 is middle
+    MouseButton(u8),
+    // Held on any connected gamepad
+    GamepadButton(GamepadButton),
+}
+
+impl InputMapButton {
+    pub fn is_down(
+        &self,
+        input_state: &InputState,
+    ) -> bool {
+        match self {
+            InputMapButton::Key(key) => input_state.is_key_down(*key),
+            InputMapButton::KeyChord(key, modifiers) => {
+                input_state.is_key_down(*key)
+                    && input_state.modifiers().without_key(*key) == *modifiers
+            }
+            InputMapButton::MouseButton(index) => input_state.is_mouse_down(MouseButton(*index)),
+            InputMapButton::GamepadButton(button) => {
+                input_state.is_any_gamepad_button_down(*button)
+            }
+        }
+    }
+
+    // Returns true if the platform repeated a held key this frame. Only keys repeat
+    pub fn is_repeated(
+        &self,
+        input_state: &InputState,
+    ) -> bool {
+        match self {
+            InputMapButton::Key(key) => input_state.is_key_just_repeated(*key),
+            InputMapButton::KeyChord(key, modifiers) => {
+                input_state.is_key_just_repeated(*key)
+                    && input_state.modifiers().without_key(*key) == *modifiers
+            }
+            InputMapButton::MouseButton(_) | InputMapButton::GamepadButton(_) => false,
+        }
+    }
+}
+
+// Something that produces a value for an axis
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum InputMapAxisBinding {
+    // -1 while negative is held, 1 while positive is held and 0 if both or neither are held
+    Buttons {
+        negative: InputMapButton,
+        positive: InputMapButton,
+    },
+    // Scroll distance during this frame
+    MouseWheelX,
+    MouseWheelY,
+    // The axis on whichever connected gamepad is furthest from rest, with the deadzone applied
+    GamepadAxis(GamepadAxis),
+}
+
+impl InputMapAxisBinding {
+    pub fn value(
+        &self,
+        input_state: &InputState,
+    ) -> f32 {
+        match self {
+            InputMapAxisBinding::Buttons { negative, positive } => {
+                let mut value = 0.0;
+                if negative.is_down(input_state) {
+                    value -= 1.0;
+                }
+                if positive.is_down(input_state) {
+                    value += 1.0;
+                }
+                value
+            }
+            InputMapAxisBinding::MouseWheelX => input_state.mouse_wheel_delta().x,
+            InputMapAxisBinding::MouseWheelY => input_state.mouse_wheel_delta().y,
+            InputMapAxisBinding::GamepadAxis(axis) => input_state.any_gamepad_axis(*axis),
+        }
+    }
+
+    // When several bindings are active, the one furthest from zero is used. This way holding both
+    // W and Up doesn't move twice as fast.
+    pub fn combined_value(
+        bindings: &[InputMapAxisBinding],
+        input_state: &InputState,
+    ) -> f32 {
+        bindings
+            .iter()
+            .map(|binding| binding.value(input_state))
+            .fold(0.0, |value: f32, binding_value| {
+                if binding_value.abs() > value.abs() {
+                    binding_value
+                } else {
+                    value
+                }
+            })
+    }
+}
+
+// A named action that is down while any of its bindings are held
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct InputMapAction {
+    pub name: String,
+    pub bindings: Vec<InputMapButton>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct InputMapAxis {
+    pub name: String,
+    pub bindings: Vec<InputMapAxisBinding>,
+}
+
+// Two axes read together, for example movement. The result is clamped to a length of 1 so that
+// moving diagonally isn't faster.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct InputMapAxis2D {
+    pub name: String,
+    pub x: Vec<InputMapAxisBinding>,
+    pub y: Vec<InputMapAxisBinding>,
+}
+
+// A set of bindings that are only used while the context is active, for example "gameplay" or
+// "editor"
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct InputMapContext {
+    pub name: String,
+    #[serde(default)]
+    pub actions: Vec<InputMapAction>,
+    #[serde(default)]
+    pub axes: Vec<InputMapAxis>,
+    #[serde(default)]
+    pub axes_2d: Vec<InputMapAxis2D>,
+}
+
+// Binds named actions and axes to inputs. See InputMapResource for reading them.
+#[derive(TypeUuid, Serialize, Deserialize, Debug, Clone)]
+#[uuid = "4f47f2a1-28c5-4b49-8113-f197aa2f2581"]
+pub struct InputMapAsset {
+    pub contexts: Vec<InputMapContext>,
+}
+
+impl InputMapAsset {
+    pub fn context(
+        &self,
+        name: &str,
+    ) -> Option<&InputMapContext> {
+        self.contexts.iter().find(|context| context.name == name)
+    }
+}
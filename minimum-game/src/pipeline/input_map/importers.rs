@@ -0,0 +1,89 @@
+use atelier_assets::importer::{ImportedAsset, Importer, ImporterValue};
+use atelier_assets::core::AssetUuid;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use type_uuid::TypeUuid;
+
+use super::InputMapAsset;
+
+use atelier_assets::importer as atelier_importer;
+
+#[derive(Default, Deserialize, Serialize, TypeUuid, Clone, Copy)]
+#[uuid = "d471e186-6c8e-42ad-83af-d70da30d184d"]
+pub struct InputMapImporterOptions {}
+
+/// A simple state for Importer to retain the same UUID between imports
+/// for all single-asset source files
+#[derive(Default, Deserialize, Serialize, TypeUuid)]
+#[uuid = "d45f12b2-5115-46ab-9e39-1a51ea325415"]
+pub struct InputMapImporterState {
+    pub id: Option<AssetUuid>,
+}
+
+/// Reads an InputMapAsset from a RON file, for example:
+///
+/// ```ron
+/// (
+///     contexts: [
+///         (
+///             name: "gameplay",
+///             actions: [
+///                 (name: "jump", bindings: [Key(Space), MouseButton(0)]),
+///             ],
+///             axes_2d: [
+///                 (
+///                     name: "move",
+///                     x: [Buttons(negative: Key(A), positive: Key(D))],
+///                     y: [Buttons(negative: Key(S), positive: Key(W))],
+///                 ),
+///             ],
+///         ),
+///     ],
+/// )
+/// ```
+#[derive(Default, TypeUuid)]
+#[uuid = "34baa6c3-83b2-49f6-ba6d-a5e6617b6c21"]
+pub struct InputMapImporter {}
+
+impl Importer for InputMapImporter {
+    type State = InputMapImporterState;
+    type Options = InputMapImporterOptions;
+
+    fn version_static() -> u32 {
+        1
+    }
+
+    fn version(&self) -> u32 {
+        Self::version_static()
+    }
+
+    fn import(
+        &self,
+        source: &mut dyn Read,
+        _: &Self::Options,
+        state: &mut Self::State,
+    ) -> atelier_importer::Result<ImporterValue> {
+        // Read in the data
+        let mut bytes = Vec::new();
+        source.read_to_end(&mut bytes)?;
+
+        let input_map_asset = ron::de::from_bytes::<InputMapAsset>(&bytes)?;
+
+        // Reuse the ID from previous imports so that the input map resource keeps pointing at it
+        // when the file is edited
+        let id = *state
+            .id
+            .get_or_insert_with(|| AssetUuid(*uuid::Uuid::new_v4().as_bytes()));
+
+        Ok(ImporterValue {
+            assets: vec![ImportedAsset {
+                id,
+                search_tags: Vec::new(),
+                build_deps: Vec::new(),
+                load_deps: Vec::new(),
+                asset_data: Box::new(input_map_asset),
+                build_pipeline: None,
+            }],
+        })
+    }
+}
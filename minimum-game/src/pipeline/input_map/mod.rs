@@ -0,0 +1,11 @@
+mod importers;
+pub use importers::InputMapImporter;
+
+mod assets;
+pub use assets::InputMapAsset;
+pub use assets::InputMapContext;
+pub use assets::InputMapAction;
+pub use assets::InputMapAxis;
+pub use assets::InputMapAxis2D;
+pub use assets::InputMapButton;
+pub use assets::InputMapAxisBinding;
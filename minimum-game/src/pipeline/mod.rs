@@ -0,0 +1,9 @@
+mod input_map;
+pub use input_map::InputMapImporter;
+pub use input_map::InputMapAsset;
+pub use input_map::InputMapContext;
+pub use input_map::InputMapAction;
+pub use input_map::InputMapAxis;
+pub use input_map::InputMapAxis2D;
+pub use input_map::InputMapButton;
+pub use input_map::InputMapAxisBinding;
@@ -1,6 +1,48 @@
+use legion::Entity;
+use serde::{Deserialize, Serialize};
+
+use crate::inspect::InspectEnum;
+use super::ViewportResource;
+
+// How a camera maps the world onto the viewport
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
+pub enum CameraProjection {
+    Orthographic,
+    Perspective,
+}
+
+impl Default for CameraProjection {
+    fn default() -> Self {
+        CameraProjection::Orthographic
+    }
+}
+
+impl InspectEnum for CameraProjection {
+    const VARIANTS: &'static [Self] = &[
+        CameraProjection::Orthographic,
+        CameraProjection::Perspective,
+    ];
+}
+
+// The view that the world is drawn with. When the world contains CameraComponents, update_cameras
+// copies the active camera into this every frame. Otherwise it can be set directly.
+#[derive(Clone)]
 pub struct CameraResource {
     pub position: glam::Vec2,
     pub x_half_extents: f32,
+    pub projection: CameraProjection,
+    // Z position of the camera
+    pub height: f32,
+    pub rotation: glam::Quat,
+    // Orthographic cameras ignore fov_y and near_clip
+    pub fov_y: f32,
+    pub near_clip: f32,
+    pub far_clip: f32,
+
+    // The camera that was explicitly made active
+    requested_camera: Option<Entity>,
+    // The camera that was last copied into this resource
+    active_camera: Option<Entity>,
 }
 
 impl CameraResource {
@@ -11,6 +53,108 @@ impl CameraResource {
         CameraResource {
             position,
             x_half_extents,
+            projection: CameraProjection::Orthographic,
+            height: 0.0,
+            rotation: glam::Quat::identity(),
+            fov_y: 60.0,
+            near_clip: 0.1,
+            far_clip: 1000.0,
+            requested_camera: None,
+            active_camera: None,
         }
     }
+
+    // Use the given camera entity instead of the camera with the highest priority. Pass None to go
+    // back to using priority
+    pub fn set_active_camera(
+        &mut self,
+        camera: Option<Entity>,
+    ) {
+        self.requested_camera = camera;
+    }
+
+    pub fn requested_camera(&self) -> Option<Entity> {
+        self.requested_camera
+    }
+
+    // The camera entity currently being used, if any
+    pub fn active_camera(&self) -> Option<Entity> {
+        self.active_camera
+    }
+
+    pub(crate) fn set_current_camera(
+        &mut self,
+        camera: Option<Entity>,
+    ) {
+        self.active_camera = camera;
+    }
+
+    // Half the size of an orthographic view in world units
+    pub fn view_half_extents(
+        &self,
+        viewport_size_in_pixels: glam::Vec2,
+    ) -> glam::Vec2 {
+        let y_half_extents =
+            self.x_half_extents / (viewport_size_in_pixels.x() / viewport_size_in_pixels.y());
+
+        glam::Vec2::new(self.x_half_extents, y_half_extents)
+    }
+
+    pub fn world_space_eye_position(&self) -> glam::Vec3 {
+        self.position.extend(self.height)
+    }
+
+    // Cameras look down -Z with +Y up before the rotation is applied
+    pub fn world_space_view_matrix(&self) -> glam::Mat4 {
+        glam::Mat4::from_rotation_translation(self.rotation, self.world_space_eye_position())
+            .inverse()
+    }
+
+    pub fn world_space_proj_matrix(
+        &self,
+        viewport_size_in_pixels: glam::Vec2,
+    ) -> glam::Mat4 {
+        // Viewport space has Y pointing down, so both projections flip Y
+        match self.projection {
+            CameraProjection::Orthographic => {
+                let view_half_extents = self.view_half_extents(viewport_size_in_pixels);
+                glam::Mat4::orthographic_rh(
+                    -view_half_extents.x(),
+                    view_half_extents.x(),
+                    view_half_extents.y(),
+                    -view_half_extents.y(),
+                    -self.far_clip,
+                    self.far_clip,
+                )
+            }
+            CameraProjection::Perspective => {
+                let aspect_ratio = viewport_size_in_pixels.x() / viewport_size_in_pixels.y();
+                let projection = glam::Mat4::perspective_rh(
+                    self.fov_y.to_radians(),
+                    aspect_ratio,
+                    self.near_clip,
+                    self.far_clip,
+                );
+
+                glam::Mat4::from_scale(glam::Vec3::new(1.0, -1.0, 1.0)) * projection
+            }
+        }
+    }
+
+    // Sets the world space view of the viewport to match this camera
+    pub fn update_viewport(
+        &self,
+        viewport: &mut ViewportResource,
+    ) {
+        let viewport_size_in_pixels = viewport.viewport_size_in_pixels();
+        viewport.set_world_space_view(
+            self.world_space_proj_matrix(viewport_size_in_pixels),
+            self.world_space_view_matrix(),
+            self.world_space_eye_position(),
+            self.projection,
+            self.fov_y,
+            self.near_clip,
+            self.far_clip,
+        );
+    }
 }
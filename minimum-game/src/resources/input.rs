@@ -1,16 +1,43 @@
 use std::ops::{Deref, DerefMut};
 
 use crate::input::InputState;
+use crate::resources::TimeResource;
+use std::time::Duration;
 
 // For now just wrap the input helper that skulpin provides
 pub struct InputResource {
     input_state: InputState,
+
+    // The fixed update time that was set before recording or playback took over
+    previous_fixed_update_time: Option<Option<Duration>>,
 }
 
 impl InputResource {
     pub fn new() -> Self {
         InputResource {
             input_state: InputState::new(),
+            previous_fixed_update_time: None,
+        }
+    }
+
+    /// Recording and playback advance time by the recording's frame time. Whatever was set
+    /// before is restored once they end.
+    pub fn update_fixed_update_time(
+        &mut self,
+        time_resource: &mut TimeResource,
+    ) {
+        match self.input_state.fixed_frame_time() {
+            Some(frame_time) => {
+                if self.previous_fixed_update_time.is_none() {
+                    self.previous_fixed_update_time = Some(time_resource.fixed_update_time());
+                }
+                time_resource.set_fixed_update_time(Some(frame_time));
+            }
+            None => {
+                if let Some(previous) = self.previous_fixed_update_time.take() {
+                    time_resource.set_fixed_update_time(previous);
+                }
+            }
         }
     }
 
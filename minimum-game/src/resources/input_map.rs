@@ -0,0 +1,219 @@
+use std::collections::{HashMap, HashSet};
+
+use atelier_assets::core::AssetUuid;
+use atelier_assets::loader::handle::{AssetHandle, Handle};
+use minimum_kernel::resources::AssetResource;
+
+use crate::input::InputState;
+use crate::pipeline::{InputMapAsset, InputMapAxisBinding, InputMapContext};
+
+// Reads named actions and axes from an InputMapAsset. Only bindings in active contexts are used,
+// so for example editor bindings don't fire while the game is playing. Edits to the asset file are
+// picked up as soon as the asset reloads.
+//
+// Code can also provide contexts (see set_runtime_context), for example the editor's keybinds are
+// the editor context. These are used together with the asset's context of the same name.
+pub struct InputMapResource {
+    input_map: Option<Handle<InputMapAsset>>,
+    runtime_contexts: Vec<InputMapContext>,
+    active_contexts: Vec<String>,
+
+    actions_down: HashSet<String>,
+    previous_actions_down: HashSet<String>,
+    actions_repeated: HashSet<String>,
+    axes: HashMap<String, f32>,
+    axes_2d: HashMap<String, glam::Vec2>,
+}
+
+impl InputMapResource {
+    pub const GAMEPLAY_CONTEXT: &'static str = "gameplay";
+    pub const EDITOR_CONTEXT: &'static str = "editor";
+    // For bindings that work in any context, like quitting
+    pub const GLOBAL_CONTEXT: &'static str = "global";
+
+    // Starts with the gameplay and global contexts active
+    pub fn new() -> Self {
+        InputMapResource {
+            input_map: None,
+            runtime_contexts: Default::default(),
+            active_contexts: vec![
+                Self::GAMEPLAY_CONTEXT.to_string(),
+                Self::GLOBAL_CONTEXT.to_string(),
+            ],
+            actions_down: Default::default(),
+            previous_actions_down: Default::default(),
+            actions_repeated: Default::default(),
+            axes: Default::default(),
+            axes_2d: Default::default(),
+        }
+    }
+
+    // Starts loading the input map with the given UUID, replacing the current one
+    pub fn load(
+        &mut self,
+        asset_resource: &AssetResource,
+        input_map_uuid: AssetUuid,
+    ) {
+        let load_handle = asset_resource.loader().add_ref(input_map_uuid);
+        self.input_map = Some(Handle::<InputMapAsset>::new(
+            asset_resource.tx().clone(),
+            load_handle,
+        ));
+    }
+
+    // Adds a context that isn't in the asset, replacing any previously set context with the same
+    // name. Whether it is used still depends on whether the context is active.
+    pub fn set_runtime_context(
+        &mut self,
+        context: InputMapContext,
+    ) {
+        self.runtime_contexts
+            .retain(|runtime_context| runtime_context.name != context.name);
+        self.runtime_contexts.push(context);
+    }
+
+    pub fn is_context_active(
+        &self,
+        context: &str,
+    ) -> bool {
+        self.active_contexts.iter().any(|active| active == context)
+    }
+
+    pub fn set_context_active(
+        &mut self,
+        context: &str,
+        active: bool,
+    ) {
+        if active && !self.is_context_active(context) {
+            self.active_contexts.push(context.to_string());
+        } else if !active {
+            self.active_contexts.retain(|active| active != context);
+        }
+    }
+
+    // Returns true while any binding of the action is held
+    pub fn is_action_down(
+        &self,
+        action: &str,
+    ) -> bool {
+        self.actions_down.contains(action)
+    }
+
+    // Returns true if the action started being held this frame
+    pub fn is_action_just_down(
+        &self,
+        action: &str,
+    ) -> bool {
+        self.actions_down.contains(action) && !self.previous_actions_down.contains(action)
+    }
+
+    // Returns true if the action started being held this frame, or a key bound to it repeated
+    // while held. Useful for actions that step something, like moving a selection
+    pub fn is_action_just_down_or_repeated(
+        &self,
+        action: &str,
+    ) -> bool {
+        self.is_action_just_down(action) || self.actions_repeated.contains(action)
+    }
+
+    // Returns true if the action stopped being held this frame
+    pub fn is_action_just_up(
+        &self,
+        action: &str,
+    ) -> bool {
+        !self.actions_down.contains(action) && self.previous_actions_down.contains(action)
+    }
+
+    // Returns the value of the axis, 0 if it isn't bound in an active context
+    pub fn axis(
+        &self,
+        axis: &str,
+    ) -> f32 {
+        self.axes.get(axis).copied().unwrap_or(0.0)
+    }
+
+    // Returns the value of the 2D axis, zero if it isn't bound in an active context
+    pub fn axis_2d(
+        &self,
+        axis: &str,
+    ) -> glam::Vec2 {
+        self.axes_2d
+            .get(axis)
+            .copied()
+            .unwrap_or_else(glam::Vec2::zero)
+    }
+
+    // Call once per frame after input events have been handled
+    pub fn update(
+        &mut self,
+        input_state: &InputState,
+        asset_resource: &AssetResource,
+    ) {
+        self.previous_actions_down = std::mem::take(&mut self.actions_down);
+        self.actions_repeated.clear();
+        self.axes.clear();
+        self.axes_2d.clear();
+
+        // Only runtime contexts are bound until the asset finishes loading
+        let storage = asset_resource.storage();
+        let asset_contexts = self
+            .input_map
+            .as_ref()
+            .and_then(|h| h.asset(storage))
+            .map(|input_map| input_map.contexts.as_slice())
+            .unwrap_or(&[]);
+
+        let active_contexts = &self.active_contexts;
+        let contexts = asset_contexts
+            .iter()
+            .chain(self.runtime_contexts.iter())
+            .filter(|context| active_contexts.iter().any(|active| *active == context.name));
+
+        for context in contexts {
+            for action in &context.actions {
+                if action
+                    .bindings
+                    .iter()
+                    .any(|binding| binding.is_down(input_state))
+                {
+                    self.actions_down.insert(action.name.clone());
+                }
+
+                if action
+                    .bindings
+                    .iter()
+                    .any(|binding| binding.is_repeated(input_state))
+                {
+                    self.actions_repeated.insert(action.name.clone());
+                }
+            }
+
+            for axis in &context.axes {
+                let value = InputMapAxisBinding::combined_value(&axis.bindings, input_state);
+                let entry = self.axes.entry(axis.name.clone()).or_insert(0.0);
+                if value.abs() > entry.abs() {
+                    *entry = value;
+                }
+            }
+
+            for axis in &context.axes_2d {
+                let mut value = glam::Vec2::new(
+                    InputMapAxisBinding::combined_value(&axis.x, input_state),
+                    InputMapAxisBinding::combined_value(&axis.y, input_state),
+                );
+
+                if value.length_squared() > 1.0 {
+                    value = value.normalize();
+                }
+
+                let entry = self
+                    .axes_2d
+                    .entry(axis.name.clone())
+                    .or_insert_with(glam::Vec2::zero);
+                if value.length_squared() > entry.length_squared() {
+                    *entry = value;
+                }
+            }
+        }
+    }
+}
@@ -21,8 +21,12 @@ pub use viewport::ViewportResource;
 mod input;
 pub use input::InputResource;
 
+mod input_map;
+pub use input_map::InputMapResource;
+
 mod camera;
 pub use camera::CameraResource;
+pub use camera::CameraProjection;
 
 mod imgui;
 pub use crate::resources::imgui::ImguiResource;
@@ -17,7 +17,25 @@ pub struct TimeResource {
     pub simulation_time: TimeContext,
     pub log_fps_event: PeriodicEvent,
     pub simulation_pause_flags: u8, // No flags set means simulation is not paused
+    fixed_update_time: Option<time::Duration>,
     pending_time_ops: Vec<TimeOp>,
+
+    // Simulation time is consumed in fixed steps. Whatever is left over carries into the next
+    // update
+    fixed_step_time: time::Duration,
+    max_fixed_steps_per_update: u32,
+    fixed_step_accumulator: time::Duration,
+    fixed_steps_this_update: u32,
+    fixed_simulation_time: TimeContext,
+}
+
+impl TimeResource {
+    /// 60 steps per second
+    pub const DEFAULT_FIXED_STEP_TIME: time::Duration = time::Duration::from_nanos(16_666_667);
+
+    /// If an update is so long that more steps than this are due, the extra time is dropped. This
+    /// keeps a slow frame from causing more steps, which cause an even slower frame.
+    pub const DEFAULT_MAX_FIXED_STEPS_PER_UPDATE: u32 = 5;
 }
 
 impl TimeResource {
@@ -29,7 +47,13 @@ impl TimeResource {
             simulation_time: TimeContext::new(),
             log_fps_event: Default::default(),
             simulation_pause_flags: 0,
+            fixed_update_time: None,
             pending_time_ops: Default::default(),
+            fixed_step_time: Self::DEFAULT_FIXED_STEP_TIME,
+            max_fixed_steps_per_update: Self::DEFAULT_MAX_FIXED_STEPS_PER_UPDATE,
+            fixed_step_accumulator: time::Duration::default(),
+            fixed_steps_this_update: 0,
+            fixed_simulation_time: TimeContext::new(),
         }
     }
 
@@ -41,6 +65,12 @@ impl TimeResource {
         &self.simulation_time
     }
 
+    /// Simulation time as seen by systems on the fixed tick. It advances by exactly the fixed step
+    /// time for each step.
+    pub fn fixed_game_time(&self) -> &TimeContext {
+        &self.fixed_simulation_time
+    }
+
     pub fn set_simulation_time_paused(
         &mut self,
         paused: bool,
@@ -60,6 +90,8 @@ impl TimeResource {
 
     pub fn reset_simulation_time(&mut self) {
         self.simulation_time = TimeContext::new();
+        self.fixed_simulation_time = TimeContext::new();
+        self.fixed_step_accumulator = time::Duration::default();
         log::trace!("Simulation time reset");
     }
 
@@ -67,11 +99,98 @@ impl TimeResource {
         self.simulation_pause_flags != 0
     }
 
+    /// When set, every update advances time by exactly this amount instead of measuring the wall
+    /// clock. This makes updates deterministic, which is useful for tests and offline rendering
+    pub fn set_fixed_update_time(
+        &mut self,
+        fixed_update_time: Option<time::Duration>,
+    ) {
+        self.fixed_update_time = fixed_update_time;
+    }
+
+    pub fn fixed_update_time(&self) -> Option<time::Duration> {
+        self.fixed_update_time
+    }
+
+    pub fn fixed_step_time(&self) -> time::Duration {
+        self.fixed_step_time
+    }
+
+    /// Length of a fixed step in f32 seconds
+    pub fn fixed_step_dt(&self) -> f32 {
+        self.fixed_step_time.as_secs_f32()
+    }
+
+    pub fn set_fixed_step_time(
+        &mut self,
+        fixed_step_time: time::Duration,
+    ) {
+        // A zero length step would never consume the accumulated time
+        self.fixed_step_time = fixed_step_time.max(time::Duration::from_micros(100));
+    }
+
+    pub fn set_fixed_steps_per_second(
+        &mut self,
+        steps_per_second: f32,
+    ) {
+        self.set_fixed_step_time(time::Duration::from_secs_f32(
+            1.0 / steps_per_second.max(1.0),
+        ));
+    }
+
+    pub fn max_fixed_steps_per_update(&self) -> u32 {
+        self.max_fixed_steps_per_update
+    }
+
+    pub fn set_max_fixed_steps_per_update(
+        &mut self,
+        max_fixed_steps_per_update: u32,
+    ) {
+        self.max_fixed_steps_per_update = max_fixed_steps_per_update.max(1);
+    }
+
+    /// Number of fixed steps that are due during this update. Zero while the simulation is paused.
+    pub fn fixed_steps_this_update(&self) -> u32 {
+        self.fixed_steps_this_update
+    }
+
+    /// How far the simulation is between the last fixed step and the next one, from 0 to 1. Rendering
+    /// can blend the previous and current step's state by this to move smoothly when the fixed
+    /// step rate doesn't match the frame rate.
+    pub fn fixed_step_interpolation_alpha(&self) -> f32 {
+        (self.fixed_step_accumulator.as_secs_f64() / self.fixed_step_time.as_secs_f64()) as f32
+    }
+
+    /// Called before each fixed step runs
+    pub fn advance_fixed_step(&mut self) {
+        self.fixed_simulation_time.update(self.fixed_step_time);
+    }
+
     pub fn advance_time(&mut self) {
-        self.time_state.update();
+        match self.fixed_update_time {
+            Some(elapsed) => self.time_state.update_by(elapsed),
+            None => self.time_state.update(),
+        }
+
+        self.fixed_steps_this_update = 0;
         if !self.is_simulation_paused() {
-            self.simulation_time
-                .update(self.time_state.previous_update_time());
+            let elapsed = self.time_state.previous_update_time();
+            self.simulation_time.update(elapsed);
+
+            self.fixed_step_accumulator += elapsed;
+            while self.fixed_step_accumulator >= self.fixed_step_time {
+                if self.fixed_steps_this_update == self.max_fixed_steps_per_update {
+                    log::trace!("Dropping simulation time, too many fixed steps are due");
+                    self.fixed_step_accumulator = time::Duration::from_nanos(
+                        (self.fixed_step_accumulator.as_nanos() % self.fixed_step_time.as_nanos())
+                            as u64,
+                    );
+                    break;
+                }
+
+                self.fixed_step_accumulator -= self.fixed_step_time;
+                self.fixed_steps_this_update += 1;
+            }
         }
     }
 
@@ -143,6 +262,15 @@ impl TimeState {
         self.app_time_context.update(elapsed);
     }
 
+    /// Advance time by the given amount rather than by measuring how much time has passed
+    pub fn update_by(
+        &mut self,
+        elapsed: time::Duration,
+    ) {
+        self.previous_update_instant += elapsed;
+        self.app_time_context.update(elapsed);
+    }
+
     /// System time that the application started
     pub fn app_start_system_time(&self) -> &time::SystemTime {
         &self.app_start_system_time
@@ -286,3 +414,34 @@ impl TimeContext {
         self.update_count
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn fixed_steps_accumulate_leftover_time() {
+        let mut time_resource = TimeResource::new();
+        time_resource.set_fixed_step_time(time::Duration::from_millis(10));
+        time_resource.set_fixed_update_time(Some(time::Duration::from_millis(25)));
+
+        time_resource.advance_time();
+        assert_eq!(time_resource.fixed_steps_this_update(), 2);
+        assert!((time_resource.fixed_step_interpolation_alpha() - 0.5).abs() < 0.001);
+
+        time_resource.advance_time();
+        assert_eq!(time_resource.fixed_steps_this_update(), 3);
+        assert!(time_resource.fixed_step_interpolation_alpha() < 0.001);
+
+        // Time beyond the max steps is dropped
+        time_resource.set_max_fixed_steps_per_update(2);
+        time_resource.set_fixed_update_time(Some(time::Duration::from_millis(1005)));
+        time_resource.advance_time();
+        assert_eq!(time_resource.fixed_steps_this_update(), 2);
+        assert!((time_resource.fixed_step_interpolation_alpha() - 0.5).abs() < 0.001);
+
+        time_resource.set_simulation_time_paused(true, SimulationTimePauseReason::User);
+        time_resource.advance_time();
+        assert_eq!(time_resource.fixed_steps_this_update(), 0);
+    }
+}
@@ -1,5 +1,12 @@
 use minimum_math::Segment;
 use minimum_math::NormalizedRay;
+use minimum_math::Frustum;
+
+use super::CameraProjection;
+
+// The world space size of a gizmo scaled by world_space_ui_multiplier is roughly this many pixels
+// times the gizmo's unscaled size. This is the length of the diagonal of a 100x100 pixel square
+const UI_MULTIPLIER_PIXELS: f32 = 141.421_36;
 
 pub struct ViewportResource {
     // Size of the viewport in pixels
@@ -16,13 +23,14 @@ pub struct ViewportResource {
     world_space_view_matrix: glam::Mat4,
     world_space_view_matrix_inv: glam::Mat4,
 
-    // Normalized vector
     world_space_eye_position: glam::Vec3,
-    // world_space_eye_direction: glam::Vec3,
-    // world_space_up: glam::Vec3,
-    // fov: f32, // 0.0 = ortho?
-    // near_clip: f32,
-    // far_clip: f32,
+    // Normalized vector
+    world_space_eye_direction: glam::Vec3,
+    projection: CameraProjection,
+    // Vertical field of view in degrees. Only used by perspective projections
+    fov_y: f32,
+    near_clip: f32,
+    far_clip: f32,
 }
 
 impl ViewportResource {
@@ -36,11 +44,11 @@ impl ViewportResource {
             world_space_view_matrix: glam::Mat4::zero(),
             world_space_view_matrix_inv: glam::Mat4::zero(),
             world_space_eye_position: glam::Vec3::zero(),
-            // world_space_eye_direction: glam::Vec3::zero(),
-            // world_space_up: glam::Vec3::zero(),
-            // fov: 0.0,
-            // near_clip: 0.0,
-            // far_clip: 0.0,
+            world_space_eye_direction: glam::Vec3::new(0.0, 0.0, -1.0),
+            projection: CameraProjection::Orthographic,
+            fov_y: 0.0,
+            near_clip: 0.0,
+            far_clip: 0.0,
         }
     }
 
@@ -48,10 +56,45 @@ impl ViewportResource {
         self.world_space_eye_position
     }
 
+    pub fn world_space_eye_direction(&self) -> glam::Vec3 {
+        self.world_space_eye_direction
+    }
+
+    pub fn projection(&self) -> CameraProjection {
+        self.projection
+    }
+
+    pub fn fov_y(&self) -> f32 {
+        self.fov_y
+    }
+
+    pub fn near_clip(&self) -> f32 {
+        self.near_clip
+    }
+
+    pub fn far_clip(&self) -> f32 {
+        self.far_clip
+    }
+
+    // The volume of world space that is visible in the viewport. Useful for skipping things that
+    // can't be seen
+    pub fn world_space_frustum(&self) -> Frustum {
+        Frustum::from_view_proj_matrix(self.world_space_view_proj_matrix())
+    }
+
+    // Projects world space to normalized device coordinates, where Y points down like pixels do
+    pub fn world_space_view_proj_matrix(&self) -> glam::Mat4 {
+        self.world_space_proj_matrix * self.world_space_view_matrix
+    }
+
     pub fn screen_space_matrix(&self) -> &glam::Mat4 {
         &self.screen_space_matrix
     }
 
+    pub fn viewport_size_in_pixels(&self) -> glam::Vec2 {
+        self.size_in_pixels
+    }
+
     pub fn set_viewport_size_in_pixels(
         &mut self,
         size_in_pixels: glam::Vec2,
@@ -67,27 +110,31 @@ impl ViewportResource {
         self.screen_space_matrix_inv = matrix.inverse();
     }
 
+    // The projection matrix is expected to map depth to [0, 1]. projection, fov_y (in degrees),
+    // near_clip and far_clip should describe the same projection as proj_matrix
     pub fn set_world_space_view(
         &mut self,
         proj_matrix: glam::Mat4,
         view_matrix: glam::Mat4,
         eye: glam::Vec3,
-        // dir: glam::Vec3,
-        // up: glam::Vec3,
-        // fov: f32,
-        // near_clip: f32,
-        // far_clip: f32,
+        projection: CameraProjection,
+        fov_y: f32,
+        near_clip: f32,
+        far_clip: f32,
     ) {
         self.world_space_proj_matrix = proj_matrix;
         self.world_space_proj_matrix_inv = proj_matrix.inverse();
         self.world_space_view_matrix = view_matrix;
         self.world_space_view_matrix_inv = view_matrix.inverse();
         self.world_space_eye_position = eye;
-        // self.world_space_eye_direction = dir;
-        // self.world_space_up = up;
-        // self.fov = fov;
-        // self.near_clip = near_clip;
-        // self.far_clip = far_clip;
+        self.world_space_eye_direction = (self.world_space_view_matrix_inv
+            * glam::Vec4::new(0.0, 0.0, -1.0, 0.0))
+        .truncate()
+        .normalize();
+        self.projection = projection;
+        self.fov_y = fov_y;
+        self.near_clip = near_clip;
+        self.far_clip = far_clip;
     }
 
     // In: the normalized device coordinates. Top left: (-1, -1) Bottom right: (1, 1)
@@ -134,30 +181,65 @@ impl ViewportResource {
         position.truncate()
     }
 
+    // In: pixel coordinates within the viewport. Top left: (0, 0) Bottom right: self.size_in_pixels
+    // Out: The part of the line under the given position that is between the near and far planes
     pub fn viewport_space_to_segment(
         &self,
         viewport_position: glam::Vec2,
     ) -> Segment {
+        let ray = self.viewport_space_to_ray(viewport_position);
         Segment {
-            p0: self.viewport_space_to_world_space(viewport_position, 0.0),
-            p1: self.viewport_space_to_world_space(viewport_position, 1.0),
+            p0: ray.origin,
+            p1: ray.origin + ray.dir * ray.length,
         }
     }
 
+    // In: pixel coordinates within the viewport. Top left: (0, 0) Bottom right: self.size_in_pixels
+    // Out: A ray starting on the near plane and ending on the far plane. For perspective
+    //      projections, the ray points away from the eye position
     pub fn viewport_space_to_ray(
         &self,
         viewport_position: glam::Vec2,
     ) -> NormalizedRay {
-        let segment = self.viewport_space_to_segment(viewport_position);
-
-        let mut dir = segment.p1 - segment.p0;
-        let length = dir.length();
-        dir /= length;
-
-        NormalizedRay {
-            origin: segment.p0,
-            dir,
-            length,
+        match self.projection {
+            CameraProjection::Orthographic => {
+                let p0 = self.viewport_space_to_world_space(viewport_position, 0.0);
+                let p1 = self.viewport_space_to_world_space(viewport_position, 1.0);
+
+                let mut dir = p1 - p0;
+                let length = dir.length();
+                dir /= length;
+
+                NormalizedRay {
+                    origin: p0,
+                    dir,
+                    length,
+                }
+            }
+            CameraProjection::Perspective => {
+                // Unprojecting points close to the far plane loses a lot of precision when the far
+                // plane is distant, so only the near plane is unprojected. The direction is from
+                // the eye (the origin in view space) through that point.
+                let position = self.viewport_space_to_normalized_space(viewport_position);
+                let mut near_position = self.world_space_proj_matrix_inv
+                    * glam::Vec4::new(position.x(), position.y(), 0.0, 1.0);
+                near_position /= near_position.w();
+
+                let view_space_dir = near_position.truncate().normalize();
+                let dir = (self.world_space_view_matrix_inv * view_space_dir.extend(0.0))
+                    .truncate()
+                    .normalize();
+                let origin = (self.world_space_view_matrix_inv * near_position).truncate();
+
+                // The near and far planes are perpendicular to view space Z
+                let length = (self.far_clip - self.near_clip) / (-view_space_dir.z()).max(0.0001);
+
+                NormalizedRay {
+                    origin,
+                    dir,
+                    length,
+                }
+            }
         }
     }
 
@@ -234,24 +316,27 @@ impl ViewportResource {
         position.z()
     }
 
-    // I'm sure there's a much better way to do this, but it looks good and is consistent across FOV
-    // unlike just using distance. I'm not even sure exactly what it returns, but treat it like a
-    // unit-less ratio and scale world-space things by it.
+    // Scale for world space things (like gizmos) that should stay the same size on screen. Under
+    // perspective, this grows with the distance from the eye so that the size is consistent
+    // across FOV and depth. Treat it like a unit-less ratio and scale world-space things by it.
     pub fn world_space_ui_multiplier(
         &self,
         world_position: glam::Vec3,
     ) -> f32 {
-        // project to world space
-        let position = self.world_space_to_normalized_space_internal(world_position);
-        let position_2d =
-            self.normalized_space_to_viewport_space(glam::Vec2::new(position.x(), position.y()));
-        let depth = position.z();
-
-        // Determine the world space distance we would get if we translate in screen space
-        let offset_min =
-            self.viewport_space_to_world_space(position_2d - glam::Vec2::new(50.0, 50.0), depth);
-        let offset_max =
-            self.viewport_space_to_world_space(position_2d + glam::Vec2::new(50.0, 50.0), depth);
-        (offset_max - offset_min).length()
+        if self.size_in_pixels.y() <= 0.0 {
+            return 0.0;
+        }
+
+        // For both projections, the Y scale of the projection matrix is 1 / (half the height of
+        // the view). For perspective, that height is measured 1 unit in front of the eye
+        let y_scale = self.world_space_proj_matrix.y_axis().y().abs();
+        let mut world_units_per_pixel = 2.0 / (y_scale * self.size_in_pixels.y());
+
+        if self.projection == CameraProjection::Perspective {
+            let view_position = self.world_space_view_matrix * world_position.extend(1.0);
+            world_units_per_pixel *= (-view_position.z()).max(self.near_clip);
+        }
+
+        world_units_per_pixel * UI_MULTIPLIER_PIXELS
     }
 }
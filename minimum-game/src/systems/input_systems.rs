@@ -1,14 +1,35 @@
 use legion::*;
 
-use crate::resources::InputResource;
+use crate::resources::{InputResource, InputMapResource, TimeResource};
+use minimum_kernel::resources::AssetResource;
 
-// Call this to process input state
+// Call this to process input state. If an input recording is playing, this injects the events for
+// this frame. This goes before advance_time so that the recording's timestep applies to this frame
 pub fn update_input_resource(schedule: &mut legion::systems::Builder) {
     schedule.add_system(
-        SystemBuilder::new("input end frame")
+        SystemBuilder::new("update_input_resource")
             .write_resource::<InputResource>()
-            .build(|_, _, _input, _| {
-                //input.end_frame();
+            .write_resource::<TimeResource>()
+            .build(|_, _, (input, time_resource), _| {
+                input.play_frame();
+                input.update_fixed_update_time(time_resource);
+
+                // Uses system time so that double clicks still work while the simulation is paused
+                input.update_time(time_resource.system_time().total_time());
+            }),
+    );
+}
+
+// Evaluates the actions and axes in the input map. This goes after input events have been handled
+// and before anything that reads the input map
+pub fn update_input_map(schedule: &mut legion::systems::Builder) {
+    schedule.add_system(
+        SystemBuilder::new("update_input_map")
+            .read_resource::<InputResource>()
+            .read_resource::<AssetResource>()
+            .write_resource::<InputMapResource>()
+            .build(|_, _, (input, asset_resource, input_map), _| {
+                input_map.update(input.input_state(), asset_resource);
             }),
     );
 }
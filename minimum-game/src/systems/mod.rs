@@ -1,6 +1,8 @@
 mod input_systems;
 pub use input_systems::update_input_resource;
 pub use input_systems::input_reset_for_next_frame;
+pub use input_systems::update_input_map;
 
 mod time_systems;
 pub use time_systems::advance_time;
+pub use time_systems::fixed_update_schedule;
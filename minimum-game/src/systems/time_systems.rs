@@ -20,3 +20,23 @@ pub fn advance_time(schedule: &mut legion::systems::Builder) {
             }),
     );
 }
+
+// Wraps a schedule so that it runs on the fixed tick rather than the render tick. It runs once for
+// each fixed step that's due this update, which may be zero or several times in one frame. Add
+// the result to the update schedule with add_thread_local_fn somewhere after advance_time
+pub fn fixed_update_schedule(mut schedule: Schedule) -> impl FnMut(&mut World, &mut Resources) {
+    move |world, resources| {
+        let step_count = resources
+            .get::<TimeResource>()
+            .unwrap()
+            .fixed_steps_this_update();
+
+        for _ in 0..step_count {
+            resources
+                .get_mut::<TimeResource>()
+                .unwrap()
+                .advance_fixed_step();
+            schedule.execute(world, resources);
+        }
+    }
+}
@@ -9,6 +9,13 @@ pub fn normal_to_xy(normal: glam::Vec3) -> (glam::Vec3, glam::Vec3) {
     }
 }
 
+// Counter-clockwise rotation around +Z in radians, for drawing a 3D rotation in the XY plane.
+// Measured from where the rotation moves the X axis, so tilt out of the plane is ignored
+pub fn z_rotation_radians(rotation: glam::Quat) -> f32 {
+    let x_axis = rotation * glam::Vec3::unit_x();
+    f32::atan2(x_axis.y(), x_axis.x())
+}
+
 #[derive(Debug)]
 pub struct DistanceTo2dSegmentResult {
     pub t: f32,
@@ -290,6 +297,92 @@ pub fn ray_intersect_internal(
     }
 }
 
+// The volume visible through a camera, as six planes with normals pointing inward. A plane's xyz
+// is its normal and w is its distance, so a point p is on the inside when dot(xyz, p) + w >= 0
+#[derive(Debug, Copy, Clone)]
+pub struct Frustum {
+    // Left, right, bottom, top, near, far
+    pub planes: [glam::Vec4; 6],
+}
+
+impl Frustum {
+    // Extracts the planes from a projection * view matrix. Clip space depth is expected to be in
+    // the range [0, 1] (as produced by glam's perspective_rh and orthographic_rh)
+    pub fn from_view_proj_matrix(view_proj_matrix: glam::Mat4) -> Self {
+        let rows = view_proj_matrix.transpose();
+        let r0 = rows.x_axis();
+        let r1 = rows.y_axis();
+        let r2 = rows.z_axis();
+        let r3 = rows.w_axis();
+
+        let normalize = |plane: glam::Vec4| {
+            let length = plane.truncate().length();
+            if length > std::f32::EPSILON {
+                plane / length
+            } else {
+                plane
+            }
+        };
+
+        Frustum {
+            planes: [
+                normalize(r3 + r0),
+                normalize(r3 - r0),
+                normalize(r3 + r1),
+                normalize(r3 - r1),
+                normalize(r2),
+                normalize(r3 - r2),
+            ],
+        }
+    }
+
+    fn signed_distance(
+        plane: glam::Vec4,
+        point: glam::Vec3,
+    ) -> f32 {
+        plane.truncate().dot(point) + plane.w()
+    }
+
+    pub fn contains_point(
+        &self,
+        point: glam::Vec3,
+    ) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| Self::signed_distance(*plane, point) >= 0.0)
+    }
+
+    // Conservative, may return true for spheres near the corners that are actually outside
+    pub fn intersects_sphere(
+        &self,
+        center: glam::Vec3,
+        radius: f32,
+    ) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| Self::signed_distance(*plane, center) >= -radius)
+    }
+
+    // Conservative, may return true for boxes near the corners that are actually outside
+    pub fn intersects_aabb(
+        &self,
+        min: glam::Vec3,
+        max: glam::Vec3,
+    ) -> bool {
+        self.planes.iter().all(|plane| {
+            // The corner of the box furthest along the plane's normal
+            let normal = plane.truncate();
+            let corner = glam::Vec3::new(
+                if normal.x() >= 0.0 { max.x() } else { min.x() },
+                if normal.y() >= 0.0 { max.y() } else { min.y() },
+                if normal.z() >= 0.0 { max.z() } else { min.z() },
+            );
+
+            Self::signed_distance(*plane, corner) >= 0.0
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -791,4 +884,50 @@ mod tests {
         assert_eq!(result.distance_sq, 10000.0);
         assert!(dot.abs() < 0.0001);
     }
+
+    #[test]
+    fn frustum_perspective() {
+        let view = glam::Mat4::from_rotation_translation(
+            glam::Quat::identity(),
+            glam::Vec3::new(0.0, 0.0, 10.0),
+        )
+        .inverse();
+        let proj = glam::Mat4::perspective_rh(std::f32::consts::FRAC_PI_2, 1.0, 0.1, 100.0);
+        let frustum = Frustum::from_view_proj_matrix(proj * view);
+
+        assert!(frustum.contains_point(glam::Vec3::new(0.0, 0.0, 0.0)));
+        assert!(frustum.contains_point(glam::Vec3::new(9.0, -9.0, 0.0)));
+        assert!(!frustum.contains_point(glam::Vec3::new(11.0, 0.0, 0.0)));
+        assert!(!frustum.contains_point(glam::Vec3::new(0.0, 0.0, 11.0)));
+        assert!(!frustum.contains_point(glam::Vec3::new(0.0, 0.0, -91.0)));
+
+        assert!(frustum.intersects_sphere(glam::Vec3::new(11.0, 0.0, 0.0), 2.0));
+        assert!(!frustum.intersects_sphere(glam::Vec3::new(20.0, 0.0, 0.0), 2.0));
+
+        assert!(frustum.intersects_aabb(
+            glam::Vec3::new(9.0, -1.0, -1.0),
+            glam::Vec3::new(12.0, 1.0, 1.0)
+        ));
+        assert!(!frustum.intersects_aabb(
+            glam::Vec3::new(11.0, -1.0, -1.0),
+            glam::Vec3::new(12.0, 1.0, 1.0)
+        ));
+    }
+
+    #[test]
+    fn z_rotation_radians_of_z_rotation() {
+        let angle = 2.5;
+        assert_approx_eq!(
+            z_rotation_radians(glam::Quat::from_rotation_z(angle)),
+            angle
+        );
+        assert_approx_eq!(
+            z_rotation_radians(glam::Quat::from_rotation_z(-angle)),
+            -angle
+        );
+
+        // Tilting around X doesn't change the rotation around Z
+        let tilted = glam::Quat::from_rotation_z(angle) * glam::Quat::from_rotation_x(0.5);
+        assert_approx_eq!(z_rotation_radians(tilted), angle);
+    }
 }
@@ -16,6 +16,7 @@ pub use matrix::Mat4;
 pub mod functions;
 pub use functions::Segment;
 pub use functions::NormalizedRay;
+pub use functions::Frustum;
 
 pub mod bounds;
 pub use bounds::BoundingSphere;
@@ -123,6 +123,31 @@ impl TransformComponentDef {
         //glam::Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2)
     }
 
+    /// Set the world-space rotation, converting it to the euler angles used by rotation_quat
+    pub fn set_rotation_quat(
+        &mut self,
+        rotation: glam::Quat,
+    ) {
+        // rotation_quat is z * x * y, so the x rotation can be read straight out of the matrix
+        let matrix = glam::Mat3::from_quat(rotation);
+        let x_axis = matrix.x_axis();
+        let y_axis = matrix.y_axis();
+        let z_axis = matrix.z_axis();
+
+        let sin_x = y_axis.z().max(-1.0).min(1.0);
+        let x = sin_x.asin();
+        *self.rotation = if sin_x.abs() < 0.9999 {
+            glam::Vec3::new(
+                x,
+                f32::atan2(-x_axis.z(), z_axis.z()),
+                f32::atan2(-y_axis.x(), y_axis.y()),
+            )
+        } else {
+            // Gimbal lock, the y and z rotations are around the same axis
+            glam::Vec3::new(x, 0.0, f32::atan2(x_axis.y(), x_axis.x()))
+        };
+    }
+
     pub fn rotation_euler(&self) -> glam::Vec3 {
         *self.rotation
     }
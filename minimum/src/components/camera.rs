@@ -0,0 +1,203 @@
+use serde::{Deserialize, Serialize};
+use serde_diff::SerdeDiff;
+use type_uuid::TypeUuid;
+use imgui_inspect_derive::Inspect;
+use imgui_inspect::{InspectArgsDefault, InspectRenderDefault};
+use minimum_game::inspect::{render_enum, render_enum_mut};
+use minimum_game::resources::CameraProjection;
+use legion::Entity;
+use minimum_math::Vec2;
+
+// Edits a CameraProjection with a combo box
+pub struct CameraProjectionInspect;
+
+impl InspectRenderDefault<CameraProjection> for CameraProjectionInspect {
+    fn render(
+        data: &[&CameraProjection],
+        label: &'static str,
+        ui: &imgui::Ui,
+        _args: &InspectArgsDefault,
+    ) {
+        render_enum(data, label, ui);
+    }
+
+    fn render_mut(
+        data: &mut [&mut CameraProjection],
+        label: &'static str,
+        ui: &imgui::Ui,
+        _args: &InspectArgsDefault,
+    ) -> bool {
+        render_enum_mut(data, label, ui)
+    }
+}
+
+//
+// A camera that can be placed in a prefab. The active camera (see CameraResource) is positioned
+// at the transform on the same entity and determines the world space view. Cameras look along the
+// transform's -Z axis, so with no rotation they see the XY plane from above. Orthographic cameras
+// show x_half_extents world units either side of the camera, perspective cameras should be placed
+// at a positive Z.
+//
+#[derive(TypeUuid, Serialize, Deserialize, SerdeDiff, Debug, PartialEq, Clone, Inspect)]
+#[uuid = "3e8b1d52-6a9c-4f07-b2d4-8c5e1a7f3b69"]
+pub struct CameraComponentDef {
+    #[serde_diff(opaque)]
+    #[inspect(proxy_type = "CameraProjectionInspect")]
+    pub projection: CameraProjection,
+    // Orthographic only. Half the width of the view in world units
+    pub x_half_extents: f32,
+    // Perspective only. Vertical field of view in degrees
+    pub fov_y: f32,
+    // Orthographic cameras ignore near_clip and see far_clip in front of and behind them
+    pub near_clip: f32,
+    pub far_clip: f32,
+    // If no camera has been made active, the camera with the highest priority is used
+    pub priority: i32,
+    // Move towards the entity with a CameraTargetComponent
+    pub follow_target: bool,
+    // Time in seconds for the camera to cover most of the distance to the target. 0 snaps to it
+    pub follow_smoothing: f32,
+    // Offset from the target's position that the camera moves to
+    #[serde_diff(opaque)]
+    pub follow_offset: Vec2,
+    // Keep the view within bounds_min/bounds_max. For perspective cameras, only the camera's
+    // position is kept within the bounds
+    pub clamp_to_bounds: bool,
+    #[serde_diff(opaque)]
+    pub bounds_min: Vec2,
+    #[serde_diff(opaque)]
+    pub bounds_max: Vec2,
+}
+
+legion_prefab::register_component_type!(CameraComponentDef);
+
+impl Default for CameraComponentDef {
+    fn default() -> Self {
+        CameraComponentDef {
+            projection: CameraProjection::Orthographic,
+            x_half_extents: 10.0,
+            fov_y: 60.0,
+            near_clip: 0.1,
+            far_clip: 1000.0,
+            priority: 0,
+            follow_target: false,
+            follow_smoothing: 0.0,
+            follow_offset: Vec2::zero(),
+            clamp_to_bounds: false,
+            bounds_min: Vec2::zero(),
+            bounds_max: Vec2::zero(),
+        }
+    }
+}
+
+pub struct CameraComponent {
+    pub projection: CameraProjection,
+    pub x_half_extents: f32,
+    pub fov_y: f32,
+    pub near_clip: f32,
+    pub far_clip: f32,
+    pub priority: i32,
+    pub follow_target: bool,
+    // If set, this entity is followed instead of the entity with a CameraTargetComponent
+    pub follow_entity: Option<Entity>,
+    pub follow_smoothing: f32,
+    pub follow_offset: glam::Vec2,
+    pub clamp_to_bounds: bool,
+    pub bounds_min: glam::Vec2,
+    pub bounds_max: glam::Vec2,
+}
+
+impl From<CameraComponentDef> for CameraComponent {
+    fn from(from: CameraComponentDef) -> Self {
+        CameraComponent {
+            projection: from.projection,
+            x_half_extents: from.x_half_extents,
+            fov_y: from.fov_y,
+            near_clip: from.near_clip,
+            far_clip: from.far_clip,
+            priority: from.priority,
+            follow_target: from.follow_target,
+            follow_entity: None,
+            follow_smoothing: from.follow_smoothing,
+            follow_offset: *from.follow_offset,
+            clamp_to_bounds: from.clamp_to_bounds,
+            bounds_min: *from.bounds_min,
+            bounds_max: *from.bounds_max,
+        }
+    }
+}
+
+impl CameraComponent {
+    // Moves the camera towards the target, returning the new position
+    pub fn follow(
+        &self,
+        position: glam::Vec2,
+        target: glam::Vec2,
+        dt: f32,
+    ) -> glam::Vec2 {
+        let goal = target + self.follow_offset;
+        if self.follow_smoothing <= 0.0 {
+            return goal;
+        }
+
+        // Exponential smoothing so the result doesn't depend on the frame rate
+        let t = 1.0 - (-dt / self.follow_smoothing).exp();
+        position + (goal - position) * t
+    }
+
+    // Keeps the view (or the position for perspective cameras) within the bounds. If the bounds
+    // are smaller than the view, the view is centered on them
+    pub fn clamp_position(
+        &self,
+        position: glam::Vec2,
+        viewport_size_in_pixels: glam::Vec2,
+    ) -> glam::Vec2 {
+        if !self.clamp_to_bounds {
+            return position;
+        }
+
+        let half_extents = match self.projection {
+            CameraProjection::Orthographic => {
+                let aspect_ratio = viewport_size_in_pixels.x() / viewport_size_in_pixels.y();
+                if aspect_ratio.is_finite() && aspect_ratio > 0.0 {
+                    glam::Vec2::new(self.x_half_extents, self.x_half_extents / aspect_ratio)
+                } else {
+                    glam::Vec2::zero()
+                }
+            }
+            CameraProjection::Perspective => glam::Vec2::zero(),
+        };
+
+        let clamp_axis = |value: f32, min: f32, max: f32, half_extent: f32| {
+            if max - min < half_extent * 2.0 {
+                (min + max) * 0.5
+            } else {
+                value.max(min + half_extent).min(max - half_extent)
+            }
+        };
+
+        glam::Vec2::new(
+            clamp_axis(
+                position.x(),
+                self.bounds_min.x(),
+                self.bounds_max.x(),
+                half_extents.x(),
+            ),
+            clamp_axis(
+                position.y(),
+                self.bounds_min.y(),
+                self.bounds_max.y(),
+                half_extents.y(),
+            ),
+        )
+    }
+}
+
+//
+// Cameras with follow_target set move towards the entity with this component
+//
+#[derive(TypeUuid, Serialize, Deserialize, SerdeDiff, Debug, PartialEq, Clone, Default, Inspect)]
+#[uuid = "a71c4e93-0d5b-4b8f-9e26-f3d8b05c6a12"]
+pub struct CameraTargetComponent {}
+
+legion_prefab::register_component_type!(CameraTargetComponent);
@@ -3,10 +3,22 @@ pub mod daemon;
 pub mod components {
     pub use minimum_transform::components::*;
     pub use minimum_editor::components::*;
+
+    mod camera;
+    pub use self::camera::{
+        CameraComponent, CameraComponentDef, CameraProjectionInspect, CameraTargetComponent,
+    };
 }
 
 pub mod pipeline {
     pub use minimum_kernel::pipeline::*;
+    pub use minimum_game::pipeline::*;
+
+    mod prefab_document;
+    pub use self::prefab_document::{component_json, serialized_component_json, PrefabDocumentBuilder};
+
+    mod gltf;
+    pub use self::gltf::GltfImporter;
 }
 
 pub mod resources {
@@ -21,6 +33,9 @@ pub mod systems {
     pub use minimum_kernel::systems::*;
     pub use minimum_game::systems::*;
 
+    mod camera_systems;
+    pub use self::camera_systems::update_cameras;
+
     mod editor {
         pub use minimum_editor::systems::*;
     }
@@ -45,4 +60,6 @@ pub use minimum_editor as editor;
 
 pub use minimum_game::ImguiManager;
 
+pub use minimum_game::inspect;
 pub use minimum_game::input;
+pub use minimum_game::input_recording;
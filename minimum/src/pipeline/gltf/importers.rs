@@ -0,0 +1,188 @@
+use atelier_assets::importer::{ImportedAsset, Importer, ImporterValue};
+use atelier_assets::core::AssetUuid;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use type_uuid::TypeUuid;
+
+use minimum_transform::components::TransformComponentDef;
+use minimum_editor::components::EditorMetadataComponent;
+
+use legion_prefab::ComponentRegistration;
+use std::collections::HashMap;
+
+use crate::pipeline::{component_json, serialized_component_json, PrefabDocumentBuilder};
+
+use atelier_assets::importer as atelier_importer;
+
+#[derive(Default, Deserialize, Serialize, TypeUuid, Clone, Copy)]
+#[uuid = "d5d3e2a4-3b8b-4a0c-9a0e-1f6c2b8e7a51"]
+pub struct GltfImporterOptions {
+    /// glTF is +Y up. By default the scene is rotated 90 degrees around X so that it is +Z up
+    /// like the rest of the engine (and blender). Set this to keep the file's axes as-is.
+    pub keep_y_up: bool,
+}
+
+/// Retains the prefab UUID and the UUID of each entity between imports so that re-exporting
+/// a file from blender hot-reloads the existing prefab rather than replacing it.
+#[derive(Default, Deserialize, Serialize, TypeUuid)]
+#[uuid = "6a0b7c0e-54f1-4d5b-8d1e-3c2f9b0a4e67"]
+pub struct GltfImporterState {
+    pub id: Option<AssetUuid>,
+    // Keyed by node name, or "#<node index>" for unnamed nodes
+    pub entity_ids: HashMap<String, AssetUuid>,
+}
+
+/// Imports the node hierarchy of a .gltf/.glb file as a prefab. Each node becomes an entity with
+/// a `TransformComponentDef` (hierarchy is flattened to world space) and an
+/// `EditorMetadataComponent` holding the node name. Node extras are read as a JSON object where
+/// each key is the type name of a registered component (either the short name i.e.
+/// "DrawSkiaBoxComponentDef" or the full path) and the value is the serialized component.
+///
+/// Meshes, materials, and animations are ignored.
+#[derive(Default, TypeUuid)]
+#[uuid = "3f5d8c71-0b2e-4e9a-a6c4-7d1e2b9f8a03"]
+pub struct GltfImporter {}
+
+impl Importer for GltfImporter {
+    type State = GltfImporterState;
+    type Options = GltfImporterOptions;
+
+    fn version_static() -> u32 {
+        1
+    }
+
+    fn version(&self) -> u32 {
+        Self::version_static()
+    }
+
+    fn import(
+        &self,
+        source: &mut dyn Read,
+        options: &Self::Options,
+        state: &mut Self::State,
+    ) -> atelier_importer::Result<ImporterValue> {
+        ///////////////////////////////////////////////////////////////
+        // STEP 1: Read in the data
+        ///////////////////////////////////////////////////////////////
+
+        // Read in the data. from_slice handles both .gltf (json) and .glb (binary) files
+        let mut bytes = Vec::new();
+        source.read_to_end(&mut bytes)?;
+        let gltf = gltf::Gltf::from_slice(&bytes)
+            .map_err(|e| atelier_importer::Error::Boxed(Box::new(e)))?;
+
+        // Reuse the prefab ID from previous imports if we have one
+        let prefab_id = *state
+            .id
+            .get_or_insert_with(|| AssetUuid(*uuid::Uuid::new_v4().as_bytes()));
+
+        ///////////////////////////////////////////////////////////////
+        // STEP 2: Walk the node hierarchy, producing an entity per node
+        ///////////////////////////////////////////////////////////////
+
+        // Look up components by type name so that extras can refer to them
+        let components_by_name = {
+            let mut components_by_name = HashMap::new();
+            for registration in legion_prefab::iter_component_registrations() {
+                let type_name = registration.type_name();
+                let short_name = type_name.rsplit("::").next().unwrap_or(type_name);
+                components_by_name.insert(type_name.to_string(), registration.clone());
+                components_by_name.insert(short_name.to_string(), registration.clone());
+            }
+            components_by_name
+        };
+
+        let root_transform = if options.keep_y_up {
+            glam::Mat4::identity()
+        } else {
+            glam::Mat4::from_rotation_x(std::f32::consts::FRAC_PI_2)
+        };
+
+        // Prefer the default scene, otherwise take the first one. Files with no scenes are
+        // allowed by the spec, in which case we produce an empty prefab
+        let scene = gltf.default_scene().or_else(|| gltf.scenes().next());
+
+        let mut builder = PrefabDocumentBuilder::new(&state.entity_ids);
+        if let Some(scene) = scene {
+            for node in scene.nodes() {
+                import_node(&node, root_transform, &components_by_name, &mut builder)?;
+            }
+        }
+
+        ///////////////////////////////////////////////////////////////
+        // STEP 3: Deserialize the entities into a prefab
+        ///////////////////////////////////////////////////////////////
+
+        // Component data in extras is in the same form that components are saved in
+        let (prefab_asset, entity_ids) = builder.build(prefab_id)?;
+
+        // Drop IDs for nodes that no longer exist
+        state.entity_ids = entity_ids;
+
+        log::trace!(
+            "Imported {} entities from gltf",
+            prefab_asset.prefab.prefab_meta.entities.len()
+        );
+
+        Ok(ImporterValue {
+            assets: vec![ImportedAsset {
+                id: prefab_id,
+                search_tags: Vec::new(),
+                build_deps: Vec::new(),
+                load_deps: Vec::new(),
+                asset_data: Box::new(prefab_asset),
+                build_pipeline: None,
+            }],
+        })
+    }
+}
+
+// Appends the node and all its children to entities in the prefab format
+fn import_node(
+    node: &gltf::Node,
+    parent_transform: glam::Mat4,
+    components_by_name: &HashMap<String, ComponentRegistration>,
+    builder: &mut PrefabDocumentBuilder,
+) -> atelier_importer::Result<()> {
+    let local_transform = glam::Mat4::from_cols_array_2d(&node.transform().matrix());
+    let world_transform = parent_transform * local_transform;
+
+    let key = node
+        .name()
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| format!("#{}", node.index()));
+
+    let mut components = vec![
+        component_json(&TransformComponentDef::from_matrix(world_transform)),
+        component_json(&EditorMetadataComponent { name: key.clone() }),
+    ];
+
+    if let Some(extras) = node.extras() {
+        let extras: serde_json::Value = serde_json::from_str(extras.get())
+            .map_err(|e| atelier_importer::Error::Boxed(Box::new(e)))?;
+
+        if let serde_json::Value::Object(extras) = extras {
+            for (type_name, data) in extras {
+                match components_by_name.get(&type_name) {
+                    Some(registration) => {
+                        components.push(serialized_component_json(*registration.uuid(), data))
+                    }
+                    // Blender exports all custom properties as extras, so this isn't an error
+                    None => log::debug!(
+                        "Ignoring extra {} on gltf node {}, it is not a registered component",
+                        type_name,
+                        key
+                    ),
+                }
+            }
+        }
+    }
+
+    builder.add_entity(key, components);
+
+    for child in node.children() {
+        import_node(&child, world_transform, components_by_name, builder)?;
+    }
+
+    Ok(())
+}
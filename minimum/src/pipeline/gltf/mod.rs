@@ -0,0 +1,2 @@
+mod importers;
+pub use importers::GltfImporter;
@@ -0,0 +1,109 @@
+use atelier_assets::core::AssetUuid;
+use serde::Serialize;
+use type_uuid::TypeUuid;
+
+use minimum_kernel::pipeline::PrefabAsset;
+
+use legion_prefab::ComponentRegistration;
+use std::collections::HashMap;
+use prefab_format::ComponentTypeUuid;
+
+use atelier_assets::importer as atelier_importer;
+
+/// A component in the form it is saved in a prefab, for use with `PrefabDocumentBuilder`
+pub fn component_json<T: TypeUuid + Serialize>(component: &T) -> serde_json::Value {
+    serialized_component_json(T::UUID, serde_json::json!(component))
+}
+
+/// Same as `component_json`, for component data that is already serialized (for example, read
+/// from the source file)
+pub fn serialized_component_json(
+    type_uuid: ComponentTypeUuid,
+    data: serde_json::Value,
+) -> serde_json::Value {
+    serde_json::json!({
+        "type": uuid::Uuid::from_bytes(type_uuid).to_string(),
+        "data": data,
+    })
+}
+
+/// Used by importers that produce a prefab from some other file format. Entities are assembled as
+/// a document in the prefab format and then loaded the same way a .prefab file is, so component
+/// data is in the same form that components are saved in.
+///
+/// Each entity has a key that is stable between imports (like a node name). Entity UUIDs are
+/// reused for keys that were in the previous import so that reimporting the file hot-reloads the
+/// existing prefab rather than replacing it.
+pub struct PrefabDocumentBuilder<'a> {
+    previous_entity_ids: &'a HashMap<String, AssetUuid>,
+    entity_ids: HashMap<String, AssetUuid>,
+    entities: Vec<serde_json::Value>,
+}
+
+impl<'a> PrefabDocumentBuilder<'a> {
+    pub fn new(previous_entity_ids: &'a HashMap<String, AssetUuid>) -> Self {
+        PrefabDocumentBuilder {
+            previous_entity_ids,
+            entity_ids: Default::default(),
+            entities: Default::default(),
+        }
+    }
+
+    pub fn add_entity(
+        &mut self,
+        key: String,
+        components: Vec<serde_json::Value>,
+    ) {
+        let entity_id = self
+            .previous_entity_ids
+            .get(&key)
+            .copied()
+            .unwrap_or_else(|| AssetUuid(*uuid::Uuid::new_v4().as_bytes()));
+
+        if self.entity_ids.insert(key.clone(), entity_id).is_some() {
+            log::warn!(
+                "More than one entity was imported as {}, edits to these entities may be lost when the file is reimported",
+                key
+            );
+        }
+
+        self.entities.push(serde_json::json!({
+            "Entity": {
+                "id": uuid::Uuid::from_bytes(entity_id.0).to_string(),
+                "components": components,
+            }
+        }));
+    }
+
+    /// Returns the prefab and the entity UUIDs to pass to the next import. Keys that were not
+    /// added this time are dropped.
+    pub fn build(
+        self,
+        prefab_id: AssetUuid,
+    ) -> atelier_importer::Result<(PrefabAsset, HashMap<String, AssetUuid>)> {
+        let prefab_document = serde_json::json!({
+            "id": uuid::Uuid::from_bytes(prefab_id.0).to_string(),
+            "objects": self.entities,
+        });
+
+        let registered_components = {
+            let comp_registrations = legion_prefab::iter_component_registrations();
+            use std::iter::FromIterator;
+            let component_types: HashMap<ComponentTypeUuid, ComponentRegistration> =
+                HashMap::from_iter(comp_registrations.map(|reg| (*reg.uuid(), reg.clone())));
+
+            component_types
+        };
+
+        let prefab_serde_context = legion_prefab::PrefabSerdeContext {
+            registered_components: &registered_components,
+        };
+
+        let prefab_deser = legion_prefab::PrefabFormatDeserializer::new(prefab_serde_context);
+        prefab_format::deserialize(prefab_document, &prefab_deser)
+            .map_err(|e| atelier_importer::Error::Boxed(Box::new(e)))?;
+        let prefab = prefab_deser.prefab();
+
+        Ok((PrefabAsset { prefab }, self.entity_ids))
+    }
+}
@@ -0,0 +1,107 @@
+use legion::*;
+
+use minimum_transform::TransformComponent;
+use minimum_game::resources::{CameraResource, TimeResource, ViewportResource};
+
+use crate::components::{CameraComponent, CameraTargetComponent};
+
+// Moves cameras that follow a target, then copies the active camera into CameraResource and
+// updates the world space view of the viewport. The active camera is the one passed to
+// CameraResource::set_active_camera, or if that camera doesn't exist, the camera with the highest
+// priority. If there are no cameras, CameraResource is left as it is.
+pub fn update_cameras(schedule: &mut legion::systems::Builder) {
+    schedule.add_system(
+        SystemBuilder::new("update_cameras")
+            .read_resource::<TimeResource>()
+            .write_resource::<CameraResource>()
+            .write_resource::<ViewportResource>()
+            .read_component::<TransformComponent>()
+            .with_query(<(Read<TransformComponent>, Read<CameraTargetComponent>)>::query())
+            .with_query(<(Entity, Read<CameraComponent>)>::query())
+            .with_query(<(Entity, Write<TransformComponent>, Read<CameraComponent>)>::query())
+            .build(
+                |_,
+                 world,
+                 (time, camera_resource, viewport),
+                 (targets_query, cameras_query, cameras_mut_query)| {
+                    let dt = time.game_time().previous_update_dt();
+                    let viewport_size_in_pixels = viewport.viewport_size_in_pixels();
+
+                    let tagged_target = targets_query
+                        .iter(world)
+                        .next()
+                        .map(|(transform, _)| transform.position().truncate());
+
+                    // Find where each following camera is heading and pick the active camera
+                    let mut follow_targets = Vec::new();
+                    let mut requested_camera_exists = false;
+                    let mut highest_priority_camera: Option<(Entity, i32)> = None;
+                    for (entity, camera) in cameras_query.iter(world) {
+                        if camera_resource.requested_camera() == Some(*entity) {
+                            requested_camera_exists = true;
+                        }
+
+                        if highest_priority_camera
+                            .map(|(_, priority)| camera.priority > priority)
+                            .unwrap_or(true)
+                        {
+                            highest_priority_camera = Some((*entity, camera.priority));
+                        }
+
+                        if !camera.follow_target {
+                            continue;
+                        }
+
+                        let target = match camera.follow_entity {
+                            Some(follow_entity) => {
+                                world.entry_ref(follow_entity).ok().and_then(|entry| {
+                                    entry
+                                        .get_component::<TransformComponent>()
+                                        .ok()
+                                        .map(|transform| transform.position().truncate())
+                                })
+                            }
+                            None => tagged_target,
+                        };
+
+                        if let Some(target) = target {
+                            follow_targets.push((*entity, target));
+                        }
+                    }
+
+                    let active_camera = if requested_camera_exists {
+                        camera_resource.requested_camera()
+                    } else {
+                        highest_priority_camera.map(|(entity, _)| entity)
+                    };
+
+                    for (entity, transform, camera) in cameras_mut_query.iter_mut(world) {
+                        let position = transform.position();
+                        let mut position_2d = position.truncate();
+
+                        if let Some((_, target)) = follow_targets.iter().find(|(e, _)| e == entity)
+                        {
+                            position_2d = camera.follow(position_2d, *target, dt);
+                        }
+
+                        position_2d = camera.clamp_position(position_2d, viewport_size_in_pixels);
+                        transform.set_position(position_2d.extend(position.z()));
+
+                        if active_camera == Some(*entity) {
+                            camera_resource.position = position_2d;
+                            camera_resource.height = position.z();
+                            camera_resource.rotation = transform.rotation();
+                            camera_resource.projection = camera.projection;
+                            camera_resource.x_half_extents = camera.x_half_extents;
+                            camera_resource.fov_y = camera.fov_y;
+                            camera_resource.near_clip = camera.near_clip;
+                            camera_resource.far_clip = camera.far_clip;
+                        }
+                    }
+
+                    camera_resource.set_current_camera(active_camera);
+                    camera_resource.update_viewport(viewport);
+                },
+            ),
+    );
+}